@@ -1,8 +1,18 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
+use pg2sqlite_core::config::{Config, DEFAULT_CONFIG_FILE};
 use pg2sqlite_core::diagnostics::{WarningDestination, report_warnings};
+use pg2sqlite_core::transform::attach::SchemaLayout;
+use pg2sqlite_core::transform::composite::CompositeMode;
+use pg2sqlite_core::transform::range::RangeMode;
+use pg2sqlite_core::transform::rename::{NameCase, RenameRule, RenameTarget};
+use pg2sqlite_core::transform::strict::StrictNumeric;
+use pg2sqlite_core::transform::type_map::{IntervalMode, NumericMode, UuidMode};
+use pg2sqlite_core::transform::without_rowid::WithoutRowidMode;
 use pg2sqlite_core::{ConvertOptions, convert_pg_ddl_to_sqlite};
 
 #[derive(Parser, Debug)]
@@ -17,6 +27,10 @@ struct Cli {
     #[arg(short, long)]
     output: Option<PathBuf>,
 
+    /// Conversion profile (default: ./pg2sqlite.toml if present); command-line flags take precedence
+    #[arg(short, long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
     /// Filter by schema name (default: "public")
     #[arg(short, long, default_value = "public")]
     schema: String,
@@ -25,6 +39,30 @@ struct Cli {
     #[arg(long)]
     include_all_schemas: bool,
 
+    /// Convert schemas matching this glob instead of --schema (repeatable)
+    #[arg(long = "include-schema", value_name = "PATTERN")]
+    include_schemas: Vec<String>,
+
+    /// Skip schemas matching this glob (repeatable)
+    #[arg(long = "exclude-schema", value_name = "PATTERN")]
+    exclude_schemas: Vec<String>,
+
+    /// Also convert the tables and types of other schemas that converted tables depend on
+    #[arg(long)]
+    include_dependencies: bool,
+
+    /// Convert only tables matching this glob, as TABLE or SCHEMA.TABLE (repeatable)
+    #[arg(long = "include-table", value_name = "PATTERN")]
+    include_tables: Vec<String>,
+
+    /// Skip tables matching this glob, as TABLE or SCHEMA.TABLE (repeatable)
+    #[arg(long = "exclude-table", value_name = "PATTERN")]
+    exclude_tables: Vec<String>,
+
+    /// Skip columns matching this glob, as TABLE.COLUMN (repeatable)
+    #[arg(long = "exclude-column", value_name = "PATTERN")]
+    exclude_columns: Vec<String>,
+
     /// Emit PRAGMA foreign_keys = ON and include FK constraints
     #[arg(long)]
     enable_foreign_keys: bool,
@@ -36,27 +74,116 @@ struct Cli {
     /// Warning output destination: file path or "stderr" (default: stderr)
     #[arg(long)]
     emit_warnings: Option<String>,
+
+    /// UUID storage: "text" (36-char string) or "blob" (16 bytes)
+    #[arg(long, default_value = "text")]
+    uuid_mode: UuidMode,
+
+    /// Numeric/money storage: "native", "integer" (scaled minor units) or "text" (exact decimal)
+    #[arg(long, default_value = "native")]
+    numeric_mode: NumericMode,
+
+    /// Per-column numeric storage override as TABLE.COLUMN=MODE (repeatable)
+    #[arg(long = "numeric-column", value_name = "TABLE.COLUMN=MODE")]
+    numeric_columns: Vec<String>,
+
+    /// Range storage: "text", "split" (lower/upper columns) or "split-inclusive" (plus bound flags)
+    #[arg(long, default_value = "text")]
+    range_mode: RangeMode,
+
+    /// Interval storage: "text", "iso8601", "seconds" or "milliseconds"
+    #[arg(long, default_value = "text")]
+    interval_mode: IntervalMode,
+
+    /// Write PostGIS geometry columns as a SpatiaLite AddGeometryColumn script to this path
+    #[arg(long, value_name = "PATH")]
+    spatialite_script: Option<PathBuf>,
+
+    /// Write one DDL file per schema (SCHEMA.sql) into this directory; the output becomes the script attaching their databases
+    #[arg(long, value_name = "DIR")]
+    schema_dir: Option<PathBuf>,
+
+    /// Rewrite unique indexes on lower(col) as col COLLATE NOCASE
+    #[arg(long)]
+    lower_index_nocase: bool,
+
+    /// Composite type storage: "json" (object with json_valid CHECK) or "columns" (col_attr columns)
+    #[arg(long, default_value = "json")]
+    composite_mode: CompositeMode,
+
+    /// Emit STRICT tables (SQLite 3.37+) that enforce declared column types
+    #[arg(long)]
+    strict_tables: bool,
+
+    /// Declared type for NUMERIC columns in STRICT tables: "real", "text" or "any"
+    #[arg(long, default_value = "real")]
+    strict_numeric: StrictNumeric,
+
+    /// WITHOUT ROWID tables: "off", "auto" (composite or non-integer primary keys) or "all"
+    #[arg(long, default_value = "off")]
+    without_rowid: WithoutRowidMode,
+
+    /// Declare columns with their PostgreSQL type names (e.g. TIMESTAMPTZ TEXT, INTEGER[])
+    #[arg(long, conflicts_with = "strict_tables")]
+    preserve_type_names: bool,
+
+    /// Emit a _pg2sqlite_columns table recording each column's original PostgreSQL type
+    #[arg(long)]
+    metadata_table: bool,
+
+    /// Rename a table as TABLE=NEW (repeatable)
+    #[arg(long = "rename-table", value_name = "TABLE=NEW")]
+    rename_tables: Vec<String>,
+
+    /// Rename a column as TABLE.COLUMN=NEW, with the original table name (repeatable)
+    #[arg(long = "rename-column", value_name = "TABLE.COLUMN=NEW")]
+    rename_columns: Vec<String>,
+
+    /// Strip this prefix from table names (repeatable)
+    #[arg(long = "strip-table-prefix", value_name = "PREFIX")]
+    strip_table_prefixes: Vec<String>,
+
+    /// Case of output names not renamed explicitly: "preserve", "lower" or "snake"
+    #[arg(long, default_value = "preserve")]
+    name_case: NameCase,
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Read input file
     let input = std::fs::read_to_string(&cli.input)
         .with_context(|| format!("Failed to read input file: {}", cli.input.display()))?;
 
-    // Build options
-    let opts = ConvertOptions {
-        schema: if cli.include_all_schemas {
-            None
-        } else {
-            Some(cli.schema)
-        },
-        include_all_schemas: cli.include_all_schemas,
-        enable_foreign_keys: cli.enable_foreign_keys,
-        strict: cli.strict,
-        emit_warnings: cli.emit_warnings.as_ref().map(PathBuf::from),
-    };
+    // Load the conversion profile
+    let config_path = cli.config.clone().or_else(|| {
+        let default = PathBuf::from(DEFAULT_CONFIG_FILE);
+        default.is_file().then_some(default)
+    });
+    let mut opts = ConvertOptions::default();
+    if let Some(path) = &config_path {
+        Config::load(path)
+            .with_context(|| format!("Failed to load config: {}", path.display()))?
+            .apply(&mut opts);
+    }
+
+    // Command-line flags override the profile
+    apply_cli(&cli, &matches, &mut opts)?;
+    if opts.include_all_schemas {
+        opts.schema = None;
+    }
+    if opts.spatialite && cli.spatialite_script.is_none() {
+        anyhow::bail!("spatialite is enabled in the config; pass --spatialite-script PATH");
+    }
+    if opts.schema_layout == SchemaLayout::Attach && cli.schema_dir.is_none() {
+        anyhow::bail!("schema_layout is \"attach\" in the config; pass --schema-dir DIR");
+    }
+    if opts.preserve_type_names && opts.strict_tables {
+        anyhow::bail!(
+            "preserve_type_names cannot be combined with strict_tables; STRICT tables only accept plain SQLite type names"
+        );
+    }
 
     // Convert
     let result = convert_pg_ddl_to_sqlite(&input, &opts).context("Conversion failed")?;
@@ -65,6 +192,17 @@ fn main() -> Result<()> {
     let warn_dest = WarningDestination::from_option(opts.emit_warnings.as_deref());
     report_warnings(&result.warnings, &warn_dest).context("Failed to write warnings")?;
 
+    // Write one DDL file per schema
+    if let Some(dir) = &cli.schema_dir {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create schema directory: {}", dir.display()))?;
+        for database in &result.databases {
+            let path = dir.join(format!("{}.sql", database.schema));
+            std::fs::write(&path, &database.sqlite_sql)
+                .with_context(|| format!("Failed to write schema file: {}", path.display()))?;
+        }
+    }
+
     // Write output
     match &cli.output {
         Some(path) => {
@@ -76,5 +214,117 @@ fn main() -> Result<()> {
         }
     }
 
+    // Write SpatiaLite script
+    if let Some(path) = &cli.spatialite_script {
+        let script = result.spatialite_sql.as_deref().unwrap_or_default();
+        std::fs::write(path, script)
+            .with_context(|| format!("Failed to write SpatiaLite script: {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Overwrite `opts` with the flags given on the command line.
+fn apply_cli(cli: &Cli, matches: &ArgMatches, opts: &mut ConvertOptions) -> Result<()> {
+    let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+    if given("schema") {
+        opts.schema = Some(cli.schema.clone());
+    }
+    opts.include_all_schemas |= cli.include_all_schemas;
+    opts.include_schemas
+        .extend(cli.include_schemas.iter().cloned());
+    opts.exclude_schemas
+        .extend(cli.exclude_schemas.iter().cloned());
+    opts.include_dependencies |= cli.include_dependencies;
+    opts.include_tables
+        .extend(cli.include_tables.iter().cloned());
+    opts.exclude_tables
+        .extend(cli.exclude_tables.iter().cloned());
+    opts.exclude_columns
+        .extend(cli.exclude_columns.iter().cloned());
+    opts.enable_foreign_keys |= cli.enable_foreign_keys;
+    opts.strict |= cli.strict;
+    if let Some(dest) = &cli.emit_warnings {
+        opts.emit_warnings = Some(PathBuf::from(dest));
+    }
+    if given("uuid_mode") {
+        opts.uuid_mode = cli.uuid_mode;
+    }
+    if given("numeric_mode") {
+        opts.numeric_mode = cli.numeric_mode;
+    }
+    opts.numeric_columns
+        .extend(parse_numeric_columns(&cli.numeric_columns)?);
+    if given("range_mode") {
+        opts.range_mode = cli.range_mode;
+    }
+    if given("interval_mode") {
+        opts.interval_mode = cli.interval_mode;
+    }
+    opts.spatialite |= cli.spatialite_script.is_some();
+    if cli.schema_dir.is_some() {
+        opts.schema_layout = SchemaLayout::Attach;
+    }
+    opts.lower_index_nocase |= cli.lower_index_nocase;
+    if given("composite_mode") {
+        opts.composite_mode = cli.composite_mode;
+    }
+    opts.strict_tables |= cli.strict_tables;
+    if given("strict_numeric") {
+        opts.strict_numeric = cli.strict_numeric;
+    }
+    if given("without_rowid") {
+        opts.without_rowid = cli.without_rowid;
+    }
+    opts.preserve_type_names |= cli.preserve_type_names;
+    opts.metadata_table |= cli.metadata_table;
+    opts.rename.tables.extend(parse_renames(
+        "--rename-table",
+        "TABLE=NEW",
+        &cli.rename_tables,
+    )?);
+    opts.rename.columns.extend(parse_renames(
+        "--rename-column",
+        "TABLE.COLUMN=NEW",
+        &cli.rename_columns,
+    )?);
+    opts.rename.rules.extend(
+        cli.strip_table_prefixes
+            .iter()
+            .map(|prefix| RenameRule::strip_prefix(RenameTarget::Tables, prefix)),
+    );
+    if given("name_case") {
+        opts.rename.case = cli.name_case;
+    }
     Ok(())
 }
+
+/// Parse renames given as OLD=NEW.
+fn parse_renames(flag: &str, form: &str, specs: &[String]) -> Result<HashMap<String, String>> {
+    specs
+        .iter()
+        .map(|spec| {
+            let (old, new) = spec
+                .split_once('=')
+                .with_context(|| format!("Invalid {flag} '{spec}': expected {form}"))?;
+            Ok((old.to_string(), new.to_string()))
+        })
+        .collect()
+}
+
+/// Parse per-column numeric overrides given as TABLE.COLUMN=MODE.
+fn parse_numeric_columns(specs: &[String]) -> Result<HashMap<String, NumericMode>> {
+    let mut numeric_columns = HashMap::new();
+    for spec in specs {
+        let (column, mode) = spec.split_once('=').with_context(|| {
+            format!("Invalid --numeric-column '{spec}': expected TABLE.COLUMN=MODE")
+        })?;
+        let mode: NumericMode = mode
+            .parse()
+            .map_err(|e: String| anyhow::anyhow!(e))
+            .with_context(|| format!("Invalid --numeric-column '{spec}'"))?;
+        numeric_columns.insert(column.to_lowercase(), mode);
+    }
+    Ok(numeric_columns)
+}
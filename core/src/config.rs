@@ -0,0 +1,428 @@
+//! Conversion profiles: [`ConvertOptions`] and mapping overrides read from TOML.
+//!
+//! ```toml
+//! schema = "app"
+//! exclude_tables = ["audit_*"]
+//! enable_foreign_keys = true
+//! uuid_mode = "blob"
+//!
+//! [numeric_columns]
+//! "invoices.total" = "integer"
+//!
+//! [type_overrides.ltree]
+//! sqlite_type = "text"
+//! check = "{column} GLOB '[A-Za-z0-9_.]*'"
+//! suppress = ["LTREE_AS_TEXT"]
+//!
+//! [[column_overrides]]
+//! column = "audit_*.payload"
+//! sqlite_type = "blob"
+//!
+//! [rename]
+//! case = "snake"
+//! tables = { tbl_legacy = "archive" }
+//!
+//! [[rename.rules]]
+//! target = "tables"
+//! pattern = "^tbl_"
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::ConvertOptions;
+use crate::ir::SqliteType;
+use crate::transform::attach::SchemaLayout;
+use crate::transform::composite::CompositeMode;
+use crate::transform::overrides::{ColumnOverride, MappingOverride};
+use crate::transform::range::RangeMode;
+use crate::transform::rename::{NameCase, RenameRule, RenameTarget};
+use crate::transform::strict::StrictNumeric;
+use crate::transform::type_map::{IntervalMode, NumericMode, UuidMode};
+use crate::transform::without_rowid::WithoutRowidMode;
+
+/// Config file looked up in the working directory when none is given.
+pub const DEFAULT_CONFIG_FILE: &str = "pg2sqlite.toml";
+
+/// Errors that can occur while loading a config file.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid config: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Empty CHECK template for {target}")]
+    EmptyCheck { target: String },
+
+    #[error("preserve_type_names cannot be combined with strict_tables")]
+    PreserveTypeNamesStrict,
+}
+
+/// A conversion profile. Keys left out of the file keep their current value.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub schema: Option<String>,
+    pub include_all_schemas: Option<bool>,
+    pub include_schemas: Vec<String>,
+    pub exclude_schemas: Vec<String>,
+    pub include_dependencies: Option<bool>,
+    #[serde(deserialize_with = "parse")]
+    pub schema_layout: Option<SchemaLayout>,
+    pub include_tables: Vec<String>,
+    pub exclude_tables: Vec<String>,
+    pub exclude_columns: Vec<String>,
+    pub enable_foreign_keys: Option<bool>,
+    pub strict: Option<bool>,
+    pub emit_warnings: Option<PathBuf>,
+    #[serde(deserialize_with = "parse")]
+    pub uuid_mode: Option<UuidMode>,
+    #[serde(deserialize_with = "parse")]
+    pub numeric_mode: Option<NumericMode>,
+    #[serde(deserialize_with = "numeric_columns")]
+    pub numeric_columns: HashMap<String, NumericMode>,
+    #[serde(deserialize_with = "parse")]
+    pub range_mode: Option<RangeMode>,
+    #[serde(deserialize_with = "parse")]
+    pub interval_mode: Option<IntervalMode>,
+    pub spatialite: Option<bool>,
+    pub lower_index_nocase: Option<bool>,
+    #[serde(deserialize_with = "parse")]
+    pub composite_mode: Option<CompositeMode>,
+    pub strict_tables: Option<bool>,
+    #[serde(deserialize_with = "parse")]
+    pub strict_numeric: Option<StrictNumeric>,
+    #[serde(deserialize_with = "parse")]
+    pub without_rowid: Option<WithoutRowidMode>,
+    /// Rejected together with `strict_tables`: STRICT tables only accept plain SQLite type names.
+    pub preserve_type_names: Option<bool>,
+    pub metadata_table: Option<bool>,
+    /// Overrides keyed by PostgreSQL type, domain or extension type name.
+    #[serde(deserialize_with = "type_overrides")]
+    pub type_overrides: HashMap<String, MappingOverride>,
+    /// Overrides for `table.column` globs, tried in file order.
+    #[serde(deserialize_with = "column_overrides")]
+    pub column_overrides: Vec<ColumnOverride>,
+    pub rename: RenameConfig,
+}
+
+/// The `[rename]` section.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RenameConfig {
+    #[serde(deserialize_with = "parse")]
+    pub case: Option<NameCase>,
+    /// New table names, keyed by table name.
+    pub tables: HashMap<String, String>,
+    /// New column names, keyed by `table.column`.
+    pub columns: HashMap<String, String>,
+    /// Regex rules from `[[rename.rules]]`, applied in file order.
+    #[serde(deserialize_with = "rename_rules")]
+    pub rules: Vec<RenameRule>,
+}
+
+impl Config {
+    /// Parse a config from TOML text.
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        let config: Config = toml::from_str(text)?;
+        let checks = config
+            .type_overrides
+            .iter()
+            .map(|(name, m)| (format!("type '{name}'"), m))
+            .chain(
+                config
+                    .column_overrides
+                    .iter()
+                    .map(|o| (format!("column '{}'", o.pattern), &o.mapping)),
+            );
+        for (target, mapping) in checks {
+            if let Some(template) = &mapping.check
+                && template.trim().is_empty()
+            {
+                return Err(ConfigError::EmptyCheck { target });
+            }
+        }
+        if config.preserve_type_names == Some(true) && config.strict_tables == Some(true) {
+            return Err(ConfigError::PreserveTypeNamesStrict);
+        }
+        Ok(config)
+    }
+
+    /// Read and parse a config file.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml(&text)
+    }
+
+    /// Overwrite the options set in this config.
+    pub fn apply(&self, opts: &mut ConvertOptions) {
+        if let Some(schema) = &self.schema {
+            opts.schema = Some(schema.clone());
+        }
+        set(&mut opts.include_all_schemas, self.include_all_schemas);
+        opts.include_schemas
+            .extend(self.include_schemas.iter().cloned());
+        opts.exclude_schemas
+            .extend(self.exclude_schemas.iter().cloned());
+        set(&mut opts.include_dependencies, self.include_dependencies);
+        set(&mut opts.schema_layout, self.schema_layout);
+        opts.include_tables
+            .extend(self.include_tables.iter().cloned());
+        opts.exclude_tables
+            .extend(self.exclude_tables.iter().cloned());
+        opts.exclude_columns
+            .extend(self.exclude_columns.iter().cloned());
+        set(&mut opts.enable_foreign_keys, self.enable_foreign_keys);
+        set(&mut opts.strict, self.strict);
+        if let Some(path) = &self.emit_warnings {
+            opts.emit_warnings = Some(path.clone());
+        }
+        set(&mut opts.uuid_mode, self.uuid_mode);
+        set(&mut opts.numeric_mode, self.numeric_mode);
+        opts.numeric_columns.extend(self.numeric_columns.clone());
+        set(&mut opts.range_mode, self.range_mode);
+        set(&mut opts.interval_mode, self.interval_mode);
+        set(&mut opts.spatialite, self.spatialite);
+        set(&mut opts.lower_index_nocase, self.lower_index_nocase);
+        set(&mut opts.composite_mode, self.composite_mode);
+        set(&mut opts.strict_tables, self.strict_tables);
+        set(&mut opts.strict_numeric, self.strict_numeric);
+        set(&mut opts.without_rowid, self.without_rowid);
+        set(&mut opts.preserve_type_names, self.preserve_type_names);
+        set(&mut opts.metadata_table, self.metadata_table);
+        opts.type_overrides.extend(self.type_overrides.clone());
+        opts.column_overrides
+            .extend(self.column_overrides.iter().cloned());
+        set(&mut opts.rename.case, self.rename.case);
+        opts.rename.tables.extend(self.rename.tables.clone());
+        opts.rename.columns.extend(self.rename.columns.clone());
+        opts.rename.rules.extend(self.rename.rules.iter().cloned());
+    }
+}
+
+fn set<T>(option: &mut T, value: Option<T>) {
+    if let Some(value) = value {
+        *option = value;
+    }
+}
+
+/// One `[type_overrides.<name>]` or `[[column_overrides]]` entry.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OverrideEntry {
+    column: Option<String>,
+    #[serde(default, deserialize_with = "parse")]
+    sqlite_type: Option<SqliteType>,
+    check: Option<String>,
+    #[serde(default)]
+    suppress: Vec<String>,
+}
+
+impl OverrideEntry {
+    fn into_mapping(self) -> MappingOverride {
+        MappingOverride {
+            sqlite_type: self.sqlite_type,
+            check: self.check,
+            suppress: self
+                .suppress
+                .into_iter()
+                .map(|code| code.to_ascii_uppercase())
+                .collect(),
+        }
+    }
+}
+
+/// Deserialize an option from a string through its `FromStr` impl.
+fn parse<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = String>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| s.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+fn numeric_columns<'de, D>(deserializer: D) -> Result<HashMap<String, NumericMode>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    HashMap::<String, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(column, mode)| {
+            let mode = mode.parse().map_err(serde::de::Error::custom)?;
+            Ok((column.to_lowercase(), mode))
+        })
+        .collect()
+}
+
+fn type_overrides<'de, D>(deserializer: D) -> Result<HashMap<String, MappingOverride>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    HashMap::<String, OverrideEntry>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, entry)| {
+            if entry.column.is_some() {
+                return Err(serde::de::Error::custom(format!(
+                    "type override '{name}' cannot set `column`; use [[column_overrides]]"
+                )));
+            }
+            Ok((name.to_lowercase(), entry.into_mapping()))
+        })
+        .collect()
+}
+
+fn column_overrides<'de, D>(deserializer: D) -> Result<Vec<ColumnOverride>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<OverrideEntry>::deserialize(deserializer)?
+        .into_iter()
+        .map(|mut entry| {
+            let pattern = entry
+                .column
+                .take()
+                .ok_or_else(|| serde::de::Error::missing_field("column"))?;
+            Ok(ColumnOverride {
+                pattern,
+                mapping: entry.into_mapping(),
+            })
+        })
+        .collect()
+}
+
+/// One `[[rename.rules]]` entry.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RenameRuleEntry {
+    #[serde(default, deserialize_with = "parse")]
+    target: Option<RenameTarget>,
+    pattern: String,
+    #[serde(default)]
+    replacement: String,
+}
+
+fn rename_rules<'de, D>(deserializer: D) -> Result<Vec<RenameRule>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<RenameRuleEntry>::deserialize(deserializer)?
+        .into_iter()
+        .map(|entry| {
+            RenameRule::new(
+                entry.target.unwrap_or_default(),
+                &entry.pattern,
+                &entry.replacement,
+            )
+            .map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_profile() {
+        let config = Config::from_toml(
+            r#"
+            schema = "app"
+            include_schemas = ["app", "billing"]
+            exclude_schemas = ["pg_*"]
+            schema_layout = "attach"
+            exclude_columns = ["users.password_hash"]
+            enable_foreign_keys = true
+            uuid_mode = "BLOB"
+            without_rowid = "auto"
+
+            [numeric_columns]
+            "Invoices.Total" = "integer"
+
+            [type_overrides.LTREE]
+            sqlite_type = "blob"
+            suppress = ["ltree_as_text"]
+
+            [[column_overrides]]
+            column = "audit_*.payload"
+            check = "json_valid({column})"
+
+            [rename]
+            case = "snake"
+            columns = { "users.LegacyId" = "old_id" }
+
+            [[rename.rules]]
+            target = "tables"
+            pattern = "^tbl_(.*)$"
+            replacement = "${1}s"
+            "#,
+        )
+        .unwrap();
+
+        let mut opts = ConvertOptions {
+            strict: true,
+            ..Default::default()
+        };
+        config.apply(&mut opts);
+        assert_eq!(opts.schema.as_deref(), Some("app"));
+        assert_eq!(opts.include_schemas, ["app", "billing"]);
+        assert_eq!(opts.exclude_schemas, ["pg_*"]);
+        assert_eq!(opts.schema_layout, SchemaLayout::Attach);
+        assert_eq!(opts.exclude_columns, ["users.password_hash"]);
+        assert!(opts.enable_foreign_keys);
+        assert!(opts.strict);
+        assert_eq!(opts.uuid_mode, UuidMode::Blob);
+        assert_eq!(opts.without_rowid, WithoutRowidMode::Auto);
+        assert_eq!(
+            opts.numeric_columns.get("invoices.total"),
+            Some(&NumericMode::Integer)
+        );
+        assert_eq!(
+            opts.type_overrides["ltree"],
+            MappingOverride {
+                sqlite_type: Some(SqliteType::Blob),
+                check: None,
+                suppress: vec!["LTREE_AS_TEXT".to_string()],
+            }
+        );
+        assert_eq!(opts.column_overrides[0].pattern, "audit_*.payload");
+        assert_eq!(opts.rename.case, NameCase::Snake);
+        assert_eq!(opts.rename.columns["users.LegacyId"], "old_id");
+        assert_eq!(opts.rename.rules[0].target, RenameTarget::Tables);
+        assert_eq!(
+            opts.rename.rules[0]
+                .pattern
+                .replace("tbl_user", &opts.rename.rules[0].replacement),
+            "users"
+        );
+    }
+
+    #[test]
+    fn test_invalid_profiles() {
+        for text in [
+            "uuid_mode = \"binary\"",
+            "unknown_option = true",
+            "[type_overrides.ltree]\nsqlite_type = \"varchar\"",
+            "[type_overrides.ltree]\ncolumn = \"t.c\"",
+            "[[column_overrides]]\nsqlite_type = \"text\"",
+            "[[column_overrides]]\ncolumn = \"t.c\"\ncheck = \" \"",
+            "[rename]\ncase = \"camel\"",
+            "[[rename.rules]]\npattern = \"(unclosed\"",
+            "preserve_type_names = true\nstrict_tables = true",
+        ] {
+            assert!(Config::from_toml(text).is_err(), "{text}");
+        }
+    }
+}
@@ -68,10 +68,13 @@ impl std::fmt::Display for Warning {
 // Type mapping warnings
 pub const TYPE_WIDTH_IGNORED: &str = "TYPE_WIDTH_IGNORED";
 pub const NUMERIC_PRECISION_LOSS: &str = "NUMERIC_PRECISION_LOSS";
+pub const NUMERIC_AS_INTEGER: &str = "NUMERIC_AS_INTEGER";
+pub const NUMERIC_AS_TEXT: &str = "NUMERIC_AS_TEXT";
 pub const BOOLEAN_AS_INTEGER: &str = "BOOLEAN_AS_INTEGER";
 pub const DATETIME_TEXT_STORAGE: &str = "DATETIME_TEXT_STORAGE";
 pub const TIMEZONE_LOSS: &str = "TIMEZONE_LOSS";
 pub const UUID_AS_TEXT: &str = "UUID_AS_TEXT";
+pub const UUID_AS_BLOB: &str = "UUID_AS_BLOB";
 pub const JSONB_LOSS: &str = "JSONB_LOSS";
 pub const ENUM_AS_TEXT: &str = "ENUM_AS_TEXT";
 pub const ARRAY_LOSSY: &str = "ARRAY_LOSSY";
@@ -79,13 +82,27 @@ pub const DOMAIN_FLATTENED: &str = "DOMAIN_FLATTENED";
 pub const VARCHAR_LENGTH_IGNORED: &str = "VARCHAR_LENGTH_IGNORED";
 pub const CHAR_LENGTH_IGNORED: &str = "CHAR_LENGTH_IGNORED";
 pub const INTERVAL_AS_TEXT: &str = "INTERVAL_AS_TEXT";
+pub const INTERVAL_AS_ISO8601: &str = "INTERVAL_AS_ISO8601";
+pub const INTERVAL_AS_INTEGER: &str = "INTERVAL_AS_INTEGER";
 pub const MONEY_AS_TEXT: &str = "MONEY_AS_TEXT";
 pub const NETWORK_AS_TEXT: &str = "NETWORK_AS_TEXT";
 pub const GEO_AS_TEXT: &str = "GEO_AS_TEXT";
 pub const BIT_AS_TEXT: &str = "BIT_AS_TEXT";
 pub const XML_AS_TEXT: &str = "XML_AS_TEXT";
 pub const RANGE_AS_TEXT: &str = "RANGE_AS_TEXT";
+pub const RANGE_SPLIT: &str = "RANGE_SPLIT";
+pub const CITEXT_AS_NOCASE: &str = "CITEXT_AS_NOCASE";
+pub const HSTORE_AS_JSON: &str = "HSTORE_AS_JSON";
+pub const LTREE_AS_TEXT: &str = "LTREE_AS_TEXT";
+pub const GEOMETRY_AS_WKB: &str = "GEOMETRY_AS_WKB";
+pub const EXTENSION_NOT_DECLARED: &str = "EXTENSION_NOT_DECLARED";
+pub const COMPOSITE_AS_JSON: &str = "COMPOSITE_AS_JSON";
+pub const COMPOSITE_EXPANDED: &str = "COMPOSITE_EXPANDED";
+pub const COLLATION_AS_NOCASE: &str = "COLLATION_AS_NOCASE";
+pub const COLLATION_UNSUPPORTED: &str = "COLLATION_UNSUPPORTED";
 pub const TYPE_UNKNOWN: &str = "TYPE_UNKNOWN";
+pub const STRICT_TYPE_CHANGED: &str = "STRICT_TYPE_CHANGED";
+pub const TYPE_OVERRIDDEN: &str = "TYPE_OVERRIDDEN";
 
 // Serial/identity warnings
 pub const SERIAL_TO_ROWID: &str = "SERIAL_TO_ROWID";
@@ -96,7 +113,19 @@ pub const IDENTITY_NO_PK: &str = "IDENTITY_NO_PK";
 // Expression warnings
 pub const NEXTVAL_REMOVED: &str = "NEXTVAL_REMOVED";
 pub const CAST_REMOVED: &str = "CAST_REMOVED";
+pub const CAST_LOSSY: &str = "CAST_LOSSY";
+pub const ILIKE_AS_LIKE: &str = "ILIKE_AS_LIKE";
+pub const LIKE_CASE_INSENSITIVE: &str = "LIKE_CASE_INSENSITIVE";
+pub const FUNCTION_NOT_BUILTIN: &str = "FUNCTION_NOT_BUILTIN";
 pub const DEFAULT_UNSUPPORTED: &str = "DEFAULT_UNSUPPORTED";
+pub const UUID_DEFAULT_EMULATED: &str = "UUID_DEFAULT_EMULATED";
+pub const NUMERIC_EXPR_UNSCALED: &str = "NUMERIC_EXPR_UNSCALED";
+pub const RANGE_EXPR_UNSUPPORTED: &str = "RANGE_EXPR_UNSUPPORTED";
+pub const INTERVAL_EXPR_LOSSY: &str = "INTERVAL_EXPR_LOSSY";
+pub const HSTORE_DEFAULT_UNSUPPORTED: &str = "HSTORE_DEFAULT_UNSUPPORTED";
+pub const SPATIAL_INDEX_UNSUPPORTED: &str = "SPATIAL_INDEX_UNSUPPORTED";
+pub const COMPOSITE_EXPR_UNSUPPORTED: &str = "COMPOSITE_EXPR_UNSUPPORTED";
+pub const STRICT_DEFAULT_INVALID: &str = "STRICT_DEFAULT_INVALID";
 
 // Constraint warnings
 pub const FK_CYCLE_DETECTED: &str = "FK_CYCLE_DETECTED";
@@ -112,6 +141,18 @@ pub const EXPRESSION_INDEX_UNSUPPORTED: &str = "EXPRESSION_INDEX_UNSUPPORTED";
 
 // Schema warnings
 pub const SCHEMA_PREFIXED: &str = "SCHEMA_PREFIXED";
+pub const AMBIGUOUS_REFERENCE: &str = "AMBIGUOUS_REFERENCE";
+pub const CROSS_SCHEMA_FK_EMULATED: &str = "CROSS_SCHEMA_FK_EMULATED";
+pub const CROSS_SCHEMA_FK_DROPPED: &str = "CROSS_SCHEMA_FK_DROPPED";
+pub const DEPENDENCY_INCLUDED: &str = "DEPENDENCY_INCLUDED";
+
+// Table warnings
+pub const WITHOUT_ROWID_SKIPPED: &str = "WITHOUT_ROWID_SKIPPED";
+pub const WITHOUT_ROWID_ROW_SIZE: &str = "WITHOUT_ROWID_ROW_SIZE";
+
+// Filter warnings
+pub const FILTER_DEPENDENT_DROPPED: &str = "FILTER_DEPENDENT_DROPPED";
+pub const FILTER_DEPENDENT_REWRITTEN: &str = "FILTER_DEPENDENT_REWRITTEN";
 
 // Sequence warnings
 pub const SEQUENCE_IGNORED: &str = "SEQUENCE_IGNORED";
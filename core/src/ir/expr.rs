@@ -1,5 +1,26 @@
 //! Expression types for default values, CHECK constraints, and WHERE clauses.
 
+/// Binding strength of SQLite operators, from loosest to tightest.
+mod precedence {
+    /// Raw SQL and operators SQLite doesn't know; always parenthesized as operands.
+    pub const UNKNOWN: u8 = 0;
+    pub const OR: u8 = 1;
+    pub const AND: u8 = 2;
+    pub const NOT: u8 = 3;
+    /// `=`, `<>`, `IS`, `IN`, `LIKE`, `BETWEEN`, `IS NULL`
+    pub const EQUALITY: u8 = 4;
+    pub const COMPARISON: u8 = 5;
+    pub const BITWISE: u8 = 6;
+    pub const ADDITIVE: u8 = 7;
+    pub const MULTIPLICATIVE: u8 = 8;
+    pub const CONCAT: u8 = 9;
+    pub const COLLATE: u8 = 10;
+    /// Prefix `-`, `+` and `~`
+    pub const UNARY: u8 = 11;
+    /// Literals, names, calls and bracketed forms
+    pub const ATOM: u8 = 12;
+}
+
 /// An expression node in the IR.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
@@ -13,7 +34,7 @@ pub enum Expr {
     BooleanLiteral(bool),
     /// NULL literal
     Null,
-    /// Column reference (e.g., `status`)
+    /// Column reference by normalized name (e.g., `status`), dot-separated when qualified
     ColumnRef(String),
     /// Function call (e.g., `now()`, `lower(col)`)
     FunctionCall { name: String, args: Vec<Expr> },
@@ -53,15 +74,196 @@ pub enum Expr {
     },
     /// Parenthesized expression
     Nested(std::boxed::Box<Expr>),
+    /// Composite field access (e.g., `(address).zip`)
+    FieldAccess {
+        expr: std::boxed::Box<Expr>,
+        field: String,
+    },
+    /// Collation override (e.g., `name COLLATE "C"`)
+    Collate {
+        expr: std::boxed::Box<Expr>,
+        collation: String,
+    },
+    /// CASE expression; `operand` is set for the simple `CASE x WHEN …` form
+    Case {
+        operand: Option<std::boxed::Box<Expr>>,
+        branches: Vec<(Expr, Expr)>,
+        else_result: Option<std::boxed::Box<Expr>>,
+    },
+    /// Pattern match (e.g., `name LIKE 'a%'`, `name NOT ILIKE 'A%'`)
+    Like {
+        expr: std::boxed::Box<Expr>,
+        pattern: std::boxed::Box<Expr>,
+        kind: LikeKind,
+        negated: bool,
+        escape: Option<String>,
+    },
+    /// EXTRACT(field FROM expr), with the field name lowercased
+    Extract {
+        field: String,
+        expr: std::boxed::Box<Expr>,
+    },
+    /// SUBSTRING(expr FROM start FOR length)
+    Substring {
+        expr: std::boxed::Box<Expr>,
+        from: Option<std::boxed::Box<Expr>>,
+        length: Option<std::boxed::Box<Expr>>,
+    },
+    /// POSITION(needle IN haystack)
+    Position {
+        needle: std::boxed::Box<Expr>,
+        haystack: std::boxed::Box<Expr>,
+    },
+    /// Time zone conversion (e.g., `created_at AT TIME ZONE 'UTC'`)
+    AtTimeZone {
+        expr: std::boxed::Box<Expr>,
+        zone: std::boxed::Box<Expr>,
+    },
+    /// Typed literal (e.g., `DATE '2020-01-01'`, `INTERVAL '1 day'`)
+    TypedLiteral { type_name: String, value: String },
     /// nextval('sequence_name') — PG-specific, removed during transform
     NextVal(String),
     /// CURRENT_TIMESTAMP (SQLite built-in)
     CurrentTimestamp,
     /// Raw SQL string for expressions that can't be decomposed further
     Raw(String),
+    /// Expression a transform pass built in SQLite form; expression mapping
+    /// unwraps it instead of translating it as PG
+    Native(std::boxed::Box<Expr>),
+}
+
+/// Flavor of a pattern match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LikeKind {
+    Like,
+    ILike,
+    SimilarTo,
+}
+
+impl LikeKind {
+    /// The operator keyword.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            LikeKind::Like => "LIKE",
+            LikeKind::ILike => "ILIKE",
+            LikeKind::SimilarTo => "SIMILAR TO",
+        }
+    }
 }
 
 impl Expr {
+    /// Build a function call expression.
+    pub fn func(name: &str, args: Vec<Expr>) -> Expr {
+        Expr::FunctionCall {
+            name: name.to_string(),
+            args,
+        }
+    }
+
+    /// Build a binary operation expression.
+    pub fn binary(left: Expr, op: &str, right: Expr) -> Expr {
+        Expr::BinaryOp {
+            left: std::boxed::Box::new(left),
+            op: op.to_string(),
+            right: std::boxed::Box::new(right),
+        }
+    }
+
+    /// Mark an expression as already in SQLite form.
+    pub fn native(expr: Expr) -> Expr {
+        Expr::Native(std::boxed::Box::new(expr))
+    }
+
+    /// Combine two boolean expressions with AND, parenthesizing the left side.
+    pub fn and(left: Expr, right: Expr) -> Expr {
+        Expr::binary(Expr::Nested(std::boxed::Box::new(left)), "AND", right)
+    }
+
+    /// Visit this expression and all sub-expressions in pre-order, allowing in-place rewrites.
+    ///
+    /// Children are visited after `f` runs on their parent, so a replacement
+    /// node is itself walked.
+    pub fn walk_mut<F: FnMut(&mut Expr)>(&mut self, f: &mut F) {
+        f(self);
+        match self {
+            Expr::FunctionCall { args, .. } => {
+                for arg in args {
+                    arg.walk_mut(f);
+                }
+            }
+            Expr::Cast { expr, .. }
+            | Expr::UnaryOp { expr, .. }
+            | Expr::IsNull { expr, .. }
+            | Expr::Nested(expr)
+            | Expr::Native(expr)
+            | Expr::FieldAccess { expr, .. }
+            | Expr::Collate { expr, .. } => expr.walk_mut(f),
+            Expr::BinaryOp { left, right, .. }
+            | Expr::Like {
+                expr: left,
+                pattern: right,
+                ..
+            }
+            | Expr::Position {
+                needle: left,
+                haystack: right,
+            }
+            | Expr::AtTimeZone {
+                expr: left,
+                zone: right,
+            } => {
+                left.walk_mut(f);
+                right.walk_mut(f);
+            }
+            Expr::Extract { expr, .. } => expr.walk_mut(f),
+            Expr::Case {
+                operand,
+                branches,
+                else_result,
+            } => {
+                if let Some(operand) = operand {
+                    operand.walk_mut(f);
+                }
+                for (condition, result) in branches {
+                    condition.walk_mut(f);
+                    result.walk_mut(f);
+                }
+                if let Some(else_result) = else_result {
+                    else_result.walk_mut(f);
+                }
+            }
+            Expr::Substring { expr, from, length } => {
+                expr.walk_mut(f);
+                for part in [from, length].into_iter().flatten() {
+                    part.walk_mut(f);
+                }
+            }
+            Expr::InList { expr, list, .. } => {
+                expr.walk_mut(f);
+                for item in list {
+                    item.walk_mut(f);
+                }
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                expr.walk_mut(f);
+                low.walk_mut(f);
+                high.walk_mut(f);
+            }
+            Expr::IntegerLiteral(_)
+            | Expr::FloatLiteral(_)
+            | Expr::StringLiteral(_)
+            | Expr::BooleanLiteral(_)
+            | Expr::Null
+            | Expr::ColumnRef(_)
+            | Expr::TypedLiteral { .. }
+            | Expr::NextVal(_)
+            | Expr::CurrentTimestamp
+            | Expr::Raw(_) => {}
+        }
+    }
+
     /// Render this expression as a SQL string.
     pub fn to_sql(&self) -> String {
         match self {
@@ -80,7 +282,7 @@ impl Expr {
                 // Quote each dot-separated identifier part to prevent injection
                 name.split('.')
                     .map(|part| {
-                        let ident = super::Ident::new(part);
+                        let ident = super::Ident::quoted(part);
                         ident.to_sql()
                     })
                     .collect::<Vec<_>>()
@@ -94,16 +296,29 @@ impl Expr {
                 format!("CAST({} AS {type_name})", expr.to_sql())
             }
             Expr::BinaryOp { left, op, right } => {
-                format!("{} {op} {}", left.to_sql(), right.to_sql())
+                // Left-associative: a right operand of equal strength needs parentheses,
+                // except under AND and OR where grouping doesn't change the result
+                let (left_min, right_min) = match binary_precedence(op) {
+                    Some(p) if p <= precedence::AND => (p, p),
+                    Some(p) => (p, p + 1),
+                    None => (precedence::ATOM, precedence::ATOM),
+                };
+                format!(
+                    "{} {op} {}",
+                    left.operand_sql(left_min),
+                    right.operand_sql(right_min)
+                )
             }
             Expr::UnaryOp { op, expr } => {
-                format!("{op} {}", expr.to_sql())
+                let min = unary_precedence(op).unwrap_or(precedence::ATOM);
+                format!("{op} {}", expr.operand_sql(min))
             }
             Expr::IsNull { expr, negated } => {
+                let expr = expr.operand_sql(precedence::EQUALITY);
                 if *negated {
-                    format!("{} IS NOT NULL", expr.to_sql())
+                    format!("{expr} IS NOT NULL")
                 } else {
-                    format!("{} IS NULL", expr.to_sql())
+                    format!("{expr} IS NULL")
                 }
             }
             Expr::InList {
@@ -113,7 +328,11 @@ impl Expr {
             } => {
                 let items: Vec<String> = list.iter().map(|e| e.to_sql()).collect();
                 let not = if *negated { "NOT " } else { "" };
-                format!("{} {not}IN ({})", expr.to_sql(), items.join(", "))
+                format!(
+                    "{} {not}IN ({})",
+                    expr.operand_sql(precedence::EQUALITY),
+                    items.join(", ")
+                )
             }
             Expr::Between {
                 expr,
@@ -124,15 +343,176 @@ impl Expr {
                 let not = if *negated { "NOT " } else { "" };
                 format!(
                     "{} {not}BETWEEN {} AND {}",
-                    expr.to_sql(),
-                    low.to_sql(),
-                    high.to_sql()
+                    expr.operand_sql(precedence::EQUALITY),
+                    low.operand_sql(precedence::EQUALITY + 1),
+                    high.operand_sql(precedence::EQUALITY + 1)
                 )
             }
             Expr::Nested(inner) => format!("({})", inner.to_sql()),
+            Expr::FieldAccess { expr, field } => {
+                format!(
+                    "({}).{}",
+                    expr.to_sql(),
+                    super::Ident::quoted(field).to_sql()
+                )
+            }
+            Expr::Collate { expr, collation } => {
+                format!(
+                    "{} COLLATE {collation}",
+                    expr.operand_sql(precedence::COLLATE)
+                )
+            }
+            Expr::Case {
+                operand,
+                branches,
+                else_result,
+            } => {
+                let mut out = String::from("CASE");
+                if let Some(operand) = operand {
+                    out.push_str(&format!(" {}", operand.to_sql()));
+                }
+                for (condition, result) in branches {
+                    out.push_str(&format!(
+                        " WHEN {} THEN {}",
+                        condition.to_sql(),
+                        result.to_sql()
+                    ));
+                }
+                if let Some(else_result) = else_result {
+                    out.push_str(&format!(" ELSE {}", else_result.to_sql()));
+                }
+                out.push_str(" END");
+                out
+            }
+            Expr::Like {
+                expr,
+                pattern,
+                kind,
+                negated,
+                escape,
+            } => {
+                let not = if *negated { "NOT " } else { "" };
+                let mut out = format!(
+                    "{} {not}{} {}",
+                    expr.operand_sql(precedence::EQUALITY),
+                    kind.keyword(),
+                    pattern.operand_sql(precedence::EQUALITY + 1)
+                );
+                if let Some(escape) = escape {
+                    out.push_str(&format!(
+                        " ESCAPE {}",
+                        Expr::StringLiteral(escape.clone()).to_sql()
+                    ));
+                }
+                out
+            }
+            Expr::Extract { field, expr } => {
+                format!("EXTRACT({} FROM {})", field.to_uppercase(), expr.to_sql())
+            }
+            Expr::Substring { expr, from, length } => {
+                let mut out = format!("SUBSTRING({}", expr.to_sql());
+                if let Some(from) = from {
+                    out.push_str(&format!(" FROM {}", from.to_sql()));
+                }
+                if let Some(length) = length {
+                    out.push_str(&format!(" FOR {}", length.to_sql()));
+                }
+                out.push(')');
+                out
+            }
+            Expr::Position { needle, haystack } => {
+                format!("POSITION({} IN {})", needle.to_sql(), haystack.to_sql())
+            }
+            Expr::AtTimeZone { expr, zone } => {
+                format!(
+                    "{} AT TIME ZONE {}",
+                    expr.operand_sql(precedence::COLLATE),
+                    zone.operand_sql(precedence::COLLATE)
+                )
+            }
+            Expr::TypedLiteral { type_name, value } => {
+                format!(
+                    "{type_name} {}",
+                    Expr::StringLiteral(value.clone()).to_sql()
+                )
+            }
             Expr::NextVal(seq) => format!("nextval('{seq}')"),
             Expr::CurrentTimestamp => "CURRENT_TIMESTAMP".to_string(),
             Expr::Raw(sql) => sql.clone(),
+            Expr::Native(expr) => expr.to_sql(),
         }
     }
+
+    /// How tightly this expression's outermost operator binds in SQLite.
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::BinaryOp { op, .. } => binary_precedence(op).unwrap_or(precedence::UNKNOWN),
+            Expr::UnaryOp { op, .. } => unary_precedence(op).unwrap_or(precedence::UNKNOWN),
+            Expr::IsNull { .. }
+            | Expr::InList { .. }
+            | Expr::Between { .. }
+            | Expr::Like { .. } => precedence::EQUALITY,
+            Expr::Collate { .. } | Expr::AtTimeZone { .. } => precedence::COLLATE,
+            Expr::IntegerLiteral(n) if *n < 0 => precedence::UNARY,
+            Expr::FloatLiteral(n) if n.is_sign_negative() => precedence::UNARY,
+            // X'…' BLOB literals are atoms
+            Expr::Raw(sql)
+                if sql.len() >= 3
+                    && sql.get(..2).is_some_and(|p| p.eq_ignore_ascii_case("x'"))
+                    && sql.ends_with('\'')
+                    && sql[2..].matches('\'').count() == 1 =>
+            {
+                precedence::ATOM
+            }
+            Expr::Raw(_) => precedence::UNKNOWN,
+            Expr::Native(expr) => expr.precedence(),
+            _ => precedence::ATOM,
+        }
+    }
+
+    /// Render as an operand that must bind at least as tightly as `min`.
+    fn operand_sql(&self, min: u8) -> String {
+        if self.precedence() < min {
+            format!("({})", self.to_sql())
+        } else {
+            self.to_sql()
+        }
+    }
+}
+
+fn binary_precedence(op: &str) -> Option<u8> {
+    let p = match op.to_ascii_uppercase().as_str() {
+        "OR" => precedence::OR,
+        "AND" => precedence::AND,
+        "="
+        | "=="
+        | "<>"
+        | "!="
+        | "IS"
+        | "IS NOT"
+        | "IS DISTINCT FROM"
+        | "IS NOT DISTINCT FROM"
+        | "LIKE"
+        | "NOT LIKE"
+        | "GLOB"
+        | "NOT GLOB"
+        | "REGEXP"
+        | "NOT REGEXP"
+        | "MATCH" => precedence::EQUALITY,
+        "<" | "<=" | ">" | ">=" => precedence::COMPARISON,
+        "&" | "|" | "<<" | ">>" => precedence::BITWISE,
+        "+" | "-" => precedence::ADDITIVE,
+        "*" | "/" | "%" => precedence::MULTIPLICATIVE,
+        "||" | "->" | "->>" => precedence::CONCAT,
+        _ => return None,
+    };
+    Some(p)
+}
+
+fn unary_precedence(op: &str) -> Option<u8> {
+    match op.to_ascii_uppercase().as_str() {
+        "NOT" => Some(precedence::NOT),
+        "-" | "+" | "~" => Some(precedence::UNARY),
+        _ => None,
+    }
 }
@@ -3,7 +3,7 @@ pub mod ident;
 pub mod model;
 pub mod types;
 
-pub use expr::Expr;
+pub use expr::{Expr, LikeKind};
 pub use ident::{Ident, QualifiedName};
 pub use model::*;
 pub use types::{PgType, SqliteType};
@@ -10,9 +10,18 @@ pub struct SchemaModel {
     pub indexes: Vec<Index>,
     pub sequences: Vec<Sequence>,
     pub enums: Vec<EnumDef>,
+    pub composites: Vec<CompositeDef>,
     pub domains: Vec<DomainDef>,
     pub alter_constraints: Vec<AlterConstraint>,
     pub identity_columns: Vec<AlterIdentity>,
+    /// Schemas created by CREATE SCHEMA statements.
+    pub schemas: Vec<Ident>,
+    /// Extensions named in CREATE EXTENSION statements (lowercased).
+    pub extensions: Vec<String>,
+    /// Collations defined by CREATE COLLATION statements.
+    pub collations: Vec<CollationDef>,
+    /// Rows for the `_pg2sqlite_columns` metadata table; empty when not requested.
+    pub column_metadata: Vec<ColumnMetadata>,
 }
 
 /// A parsed CREATE TABLE statement.
@@ -21,6 +30,10 @@ pub struct Table {
     pub name: QualifiedName,
     pub columns: Vec<Column>,
     pub constraints: Vec<TableConstraint>,
+    /// Render as a `WITHOUT ROWID` table.
+    pub without_rowid: bool,
+    /// Comment rendered above the CREATE TABLE statement.
+    pub note: Option<String>,
 }
 
 /// A column definition within a table.
@@ -36,6 +49,19 @@ pub struct Column {
     pub autoincrement: bool,
     pub references: Option<ForeignKeyRef>,
     pub check: Option<Expr>,
+    /// Column collation: the PG collation name after parsing, the SQLite
+    /// collation (e.g. `NOCASE`) after transformation.
+    pub collation: Option<String>,
+}
+
+impl Column {
+    /// Add a column-level CHECK, AND-ing it onto any existing one.
+    pub fn add_check(&mut self, check: Expr) {
+        self.check = Some(match self.check.take() {
+            Some(existing) => Expr::and(existing, check),
+            None => check,
+        });
+    }
 }
 
 /// Table-level constraint.
@@ -165,11 +191,50 @@ pub struct EnumDef {
     pub values: Vec<String>,
 }
 
+/// A CREATE TYPE ... AS (...) composite type statement.
+#[derive(Debug, Clone)]
+pub struct CompositeDef {
+    pub name: QualifiedName,
+    pub attributes: Vec<CompositeAttribute>,
+}
+
+/// An attribute of a composite type.
+#[derive(Debug, Clone)]
+pub struct CompositeAttribute {
+    pub name: Ident,
+    pub pg_type: PgType,
+}
+
+/// A CREATE COLLATION statement.
+#[derive(Debug, Clone)]
+pub struct CollationDef {
+    pub name: QualifiedName,
+    /// ICU or libc locale; for `FROM existing`, the source collation name.
+    pub locale: Option<String>,
+    pub deterministic: bool,
+}
+
+/// The original PostgreSQL type of one rendered column.
+#[derive(Debug, Clone)]
+pub struct ColumnMetadata {
+    pub table: String,
+    pub column: String,
+    /// Declared PG type, with domains resolved to their base type.
+    pub pg_type: String,
+    pub sqlite_type: String,
+    /// How values are encoded, e.g. `boolean-integer` or `scaled-integer(2)`.
+    pub strategy: String,
+    pub domain: Option<String>,
+    pub enum_labels: Option<Vec<String>>,
+}
+
 /// A CREATE DOMAIN statement.
 #[derive(Debug, Clone)]
 pub struct DomainDef {
     pub name: QualifiedName,
     pub base_type: PgType,
+    /// PG collation name, as for [`Column::collation`] before transformation.
+    pub collation: Option<String>,
     pub not_null: bool,
     pub default: Option<Expr>,
     pub check: Option<Expr>,
@@ -89,11 +89,27 @@ pub enum PgType {
     TsTzRange,
     DateRange,
 
+    // Extension types
+    Citext,
+    Hstore,
+    Ltree,
+    /// PostGIS `geometry` / `geography`, e.g. `geometry(Point,4326)`.
+    Geometry {
+        geography: bool,
+        subtype: Option<String>,
+        srid: Option<u32>,
+    },
+
     // Enum (user-defined)
     Enum {
         name: String,
     },
 
+    // Composite (user-defined)
+    Composite {
+        name: String,
+    },
+
     // Domain (user-defined)
     Domain {
         name: String,
@@ -118,6 +134,8 @@ pub enum SqliteType {
     Real,
     Numeric,
     Blob,
+    /// Untyped column; only valid in STRICT tables.
+    Any,
 }
 
 impl std::fmt::Display for SqliteType {
@@ -128,6 +146,38 @@ impl std::fmt::Display for SqliteType {
             SqliteType::Real => write!(f, "REAL"),
             SqliteType::Numeric => write!(f, "NUMERIC"),
             SqliteType::Blob => write!(f, "BLOB"),
+            SqliteType::Any => write!(f, "ANY"),
+        }
+    }
+}
+
+impl std::str::FromStr for SqliteType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "integer" => Ok(SqliteType::Integer),
+            "text" => Ok(SqliteType::Text),
+            "real" => Ok(SqliteType::Real),
+            "numeric" => Ok(SqliteType::Numeric),
+            "blob" => Ok(SqliteType::Blob),
+            "any" => Ok(SqliteType::Any),
+            other => Err(format!(
+                "unknown SQLite type '{other}' (expected integer, text, real, numeric, blob or any)"
+            )),
+        }
+    }
+}
+
+impl PgType {
+    /// Name of the PG extension that provides this type, if any.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            PgType::Citext => Some("citext"),
+            PgType::Hstore => Some("hstore"),
+            PgType::Ltree => Some("ltree"),
+            PgType::Geometry { .. } => Some("postgis"),
+            _ => None,
         }
     }
 }
@@ -204,7 +254,23 @@ impl std::fmt::Display for PgType {
             PgType::TsRange => write!(f, "tsrange"),
             PgType::TsTzRange => write!(f, "tstzrange"),
             PgType::DateRange => write!(f, "daterange"),
+            PgType::Citext => write!(f, "citext"),
+            PgType::Hstore => write!(f, "hstore"),
+            PgType::Ltree => write!(f, "ltree"),
+            PgType::Geometry {
+                geography,
+                subtype,
+                srid,
+            } => {
+                write!(f, "{}", if *geography { "geography" } else { "geometry" })?;
+                match (subtype, srid) {
+                    (Some(subtype), Some(srid)) => write!(f, "({subtype},{srid})"),
+                    (Some(subtype), None) => write!(f, "({subtype})"),
+                    _ => Ok(()),
+                }
+            }
             PgType::Enum { name } => write!(f, "{name}"),
+            PgType::Composite { name } => write!(f, "{name}"),
             PgType::Domain { name } => write!(f, "{name}"),
             PgType::Array { element } => write!(f, "{element}[]"),
             PgType::Other { name } => write!(f, "{name}"),
@@ -1,15 +1,26 @@
 //! pg2sqlite-core: PostgreSQL 16 DDL to SQLite3 DDL converter library.
 
+pub mod config;
 pub mod diagnostics;
 pub mod ir;
 pub mod pg;
 pub mod sqlite;
 pub mod transform;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use diagnostics::warning::Warning;
 use diagnostics::{StrictViolation, check_strict};
+use transform::attach::SchemaLayout;
+use transform::composite::CompositeMode;
+use transform::filter::FilterOptions;
+use transform::overrides::{ColumnOverride, MappingOverride};
+use transform::range::RangeMode;
+use transform::rename::{RenameError, RenameOptions};
+use transform::strict::StrictNumeric;
+use transform::type_map::{IntervalMode, NumericMode, TypeMapOptions, UuidMode};
+use transform::without_rowid::WithoutRowidMode;
 
 /// Options for the DDL conversion.
 #[derive(Debug, Clone)]
@@ -18,12 +29,61 @@ pub struct ConvertOptions {
     pub schema: Option<String>,
     /// If true, include all schemas (bypass schema filtering).
     pub include_all_schemas: bool,
+    /// Convert schemas matching one of these globs instead of `schema`.
+    pub include_schemas: Vec<String>,
+    /// Skip schemas matching one of these globs.
+    pub exclude_schemas: Vec<String>,
+    /// Also convert the tables and types of other schemas that converted tables depend on.
+    pub include_dependencies: bool,
+    /// Prefix colliding tables in one database, or write one database per schema.
+    pub schema_layout: SchemaLayout,
+    /// Convert only tables matching one of these `table` or `schema.table` globs (all when empty).
+    pub include_tables: Vec<String>,
+    /// Skip tables matching one of these `table` or `schema.table` globs.
+    pub exclude_tables: Vec<String>,
+    /// Skip columns matching one of these `table.column` globs.
+    pub exclude_columns: Vec<String>,
     /// If true, emit `PRAGMA foreign_keys = ON;` and include FK constraints.
     pub enable_foreign_keys: bool,
     /// If true, fail on lossy conversions.
     pub strict: bool,
     /// Path for warning output (None = stderr).
     pub emit_warnings: Option<PathBuf>,
+    /// Storage representation for `uuid` columns.
+    pub uuid_mode: UuidMode,
+    /// Storage representation for `numeric`/`decimal` and `money` columns.
+    pub numeric_mode: NumericMode,
+    /// Per-column numeric mode overrides, keyed by `table.column`.
+    pub numeric_columns: HashMap<String, NumericMode>,
+    /// Storage representation for range-typed columns.
+    pub range_mode: RangeMode,
+    /// Storage representation for interval columns.
+    pub interval_mode: IntervalMode,
+    /// If true, move PostGIS geometry columns into a SpatiaLite script.
+    pub spatialite: bool,
+    /// If true, rewrite unique indexes on `lower(col)` as `col COLLATE NOCASE`.
+    pub lower_index_nocase: bool,
+    /// Storage representation for composite-typed columns.
+    pub composite_mode: CompositeMode,
+    /// If true, declare every table `STRICT` (SQLite 3.37+).
+    pub strict_tables: bool,
+    /// Declared type for NUMERIC columns in STRICT tables.
+    pub strict_numeric: StrictNumeric,
+    /// Which tables to declare `WITHOUT ROWID`.
+    pub without_rowid: WithoutRowidMode,
+    /// If true, declare columns with their PostgreSQL type names.
+    ///
+    /// STRICT tables only accept plain SQLite type names, so this is ignored when
+    /// `strict_tables` is set; the CLI and config files reject the combination.
+    pub preserve_type_names: bool,
+    /// If true, emit the `_pg2sqlite_columns` table recording each column's original type.
+    pub metadata_table: bool,
+    /// Mapping overrides keyed by PostgreSQL type, domain or extension type name.
+    pub type_overrides: HashMap<String, MappingOverride>,
+    /// Mapping overrides for `table.column` globs; the first match wins over type overrides.
+    pub column_overrides: Vec<ColumnOverride>,
+    /// Table and column renames applied to the output.
+    pub rename: RenameOptions,
 }
 
 impl Default for ConvertOptions {
@@ -31,9 +91,32 @@ impl Default for ConvertOptions {
         Self {
             schema: Some("public".to_string()),
             include_all_schemas: false,
+            include_schemas: Vec::new(),
+            exclude_schemas: Vec::new(),
+            include_dependencies: false,
+            schema_layout: SchemaLayout::Prefix,
+            include_tables: Vec::new(),
+            exclude_tables: Vec::new(),
+            exclude_columns: Vec::new(),
             enable_foreign_keys: false,
             strict: false,
             emit_warnings: None,
+            uuid_mode: UuidMode::Text,
+            numeric_mode: NumericMode::Native,
+            numeric_columns: HashMap::new(),
+            range_mode: RangeMode::Text,
+            interval_mode: IntervalMode::Text,
+            spatialite: false,
+            lower_index_nocase: false,
+            composite_mode: CompositeMode::Json,
+            strict_tables: false,
+            strict_numeric: StrictNumeric::Real,
+            without_rowid: WithoutRowidMode::Off,
+            preserve_type_names: false,
+            metadata_table: false,
+            type_overrides: HashMap::new(),
+            column_overrides: Vec::new(),
+            rename: RenameOptions::default(),
         }
     }
 }
@@ -43,10 +126,24 @@ impl Default for ConvertOptions {
 pub struct ConvertResult {
     /// The generated SQLite DDL text.
     pub sqlite_sql: String,
+    /// SpatiaLite `AddGeometryColumn` script, when requested and geometry columns exist.
+    pub spatialite_sql: Option<String>,
+    /// DDL of each schema's database in the attach layout, where `sqlite_sql`
+    /// is the script attaching them; empty otherwise.
+    pub databases: Vec<SchemaDatabase>,
     /// Warnings emitted during conversion.
     pub warnings: Vec<Warning>,
 }
 
+/// The DDL of one schema's database in the attach layout.
+#[derive(Debug)]
+pub struct SchemaDatabase {
+    /// Schema name; the database is attached as `<schema>.db`.
+    pub schema: String,
+    /// The generated SQLite DDL text.
+    pub sqlite_sql: String,
+}
+
 /// Errors that can occur during conversion.
 #[derive(Debug, thiserror::Error)]
 pub enum ConvertError {
@@ -55,6 +152,9 @@ pub enum ConvertError {
 
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Invalid rename: {0}")]
+    Rename(#[from] RenameError),
 }
 
 /// Convert PostgreSQL DDL text to SQLite DDL.
@@ -74,21 +174,71 @@ pub fn convert_pg_ddl_to_sqlite(
     let normalize_opts = pg::normalize::NormalizeOptions {
         schema: opts.schema.clone(),
         include_all_schemas: opts.include_all_schemas,
+        include_schemas: opts.include_schemas.clone(),
+        exclude_schemas: opts.exclude_schemas.clone(),
+        include_dependencies: opts.include_dependencies,
     };
-    pg::normalize::normalize(&mut model, &normalize_opts);
+    pg::normalize::normalize(&mut model, &normalize_opts, &mut warnings);
+    // Tables pulled in from other schemas may collide with selected ones
+    let pulled_dependencies = model.tables.iter().any(|t| t.note.is_some());
+
+    // 2a. Drop filtered tables and columns, and the objects depending on them
+    let filter_opts = FilterOptions {
+        schema: opts.schema.clone(),
+        include_tables: opts.include_tables.clone(),
+        exclude_tables: opts.exclude_tables.clone(),
+        exclude_columns: opts.exclude_columns.clone(),
+    };
+    transform::filter::apply_filters(&mut model, &filter_opts, &mut warnings);
+
+    // 2b. Record declared column types for overrides and the metadata table
+    let mut origins = transform::metadata::collect_origins(&model);
+
+    // 2c. Catalog tables and types by qualified name for the lookups below
+    let catalog = transform::catalog::Catalog::build(&model);
 
     // 3. Plan (merge ALTERs, resolve SERIAL/sequences)
-    transform::planner::plan(&mut model, &mut warnings);
+    transform::planner::plan(&mut model, &catalog, &mut warnings);
+
+    // 3a. Apply type and column overrides from the config
+    let suppressions = transform::overrides::apply_overrides(
+        &mut model,
+        &origins,
+        &opts.type_overrides,
+        &opts.column_overrides,
+        &mut warnings,
+    );
 
     // 4. Transform types
+    transform::collation::apply_collations(&mut model, opts.lower_index_nocase, &mut warnings);
+    transform::composite::apply_composite_mode(
+        &mut model,
+        &catalog,
+        opts.composite_mode,
+        &mut warnings,
+    );
+    transform::extension::apply_extension_types(&mut model, &mut warnings);
+    transform::range::apply_range_mode(&mut model, opts.range_mode, &mut warnings);
+    transform::uuid::apply_uuid_mode(&mut model, opts.uuid_mode, &mut warnings);
+    transform::expr_map::type_column_casts(&mut model);
+    let type_opts = TypeMapOptions {
+        uuid_mode: opts.uuid_mode,
+        numeric_mode: opts.numeric_mode,
+        numeric_columns: opts.numeric_columns.clone(),
+        interval_mode: opts.interval_mode,
+    };
     for table in &mut model.tables {
         for col in &mut table.columns {
             let obj = format!("{}.{}", table.name.name.normalized, col.name.normalized);
-            col.sqlite_type = Some(transform::type_map::map_type(
-                &col.pg_type,
-                &obj,
-                &mut warnings,
-            ));
+            // Overridden columns already have their type
+            if col.sqlite_type.is_none() {
+                col.sqlite_type = Some(transform::type_map::map_type(
+                    &col.pg_type,
+                    &type_opts,
+                    &obj,
+                    &mut warnings,
+                ));
+            }
 
             // Transform default expressions
             if let Some(default) = &col.default {
@@ -107,33 +257,113 @@ pub fn convert_pg_ddl_to_sqlite(
     // 6. Transform indexes
     transform::index::transform_indexes(&mut model, &mut warnings);
 
-    // 7. Resolve names (schema stripping, collision handling)
-    transform::name_resolve::resolve_names(&mut model, opts.include_all_schemas, &mut warnings);
+    // 6a. Rescale literals for exact numeric storage modes
+    transform::numeric::apply_numeric_mode(&mut model, &type_opts, &mut warnings);
 
-    // 8. Topological sort (if FK enabled)
-    if opts.enable_foreign_keys {
-        transform::topo::topological_sort(&mut model.tables, &mut warnings);
-    } else {
-        // Alphabetical order when FKs disabled
-        model
-            .tables
-            .sort_by(|a, b| a.name.name.normalized.cmp(&b.name.name.normalized));
+    // 6b. Convert interval literals for interval storage modes
+    transform::interval::apply_interval_mode(&mut model, opts.interval_mode, &mut warnings);
+
+    // 6c. Restrict column types and defaults for STRICT tables
+    if opts.strict_tables {
+        transform::strict::apply_strict_tables(&mut model, opts.strict_numeric, &mut warnings);
     }
 
-    // 9. Render SQLite DDL
-    let sqlite_sql = sqlite::render::render(&model, opts.enable_foreign_keys);
+    // 6d. Choose WITHOUT ROWID tables
+    transform::without_rowid::apply_without_rowid(&mut model, opts.without_rowid, &mut warnings);
+
+    // 7. Resolve names (schema stripping, collision handling), or keep
+    // each table's schema for the attach layout
+    match opts.schema_layout {
+        SchemaLayout::Prefix => transform::name_resolve::resolve_names(
+            &mut model,
+            &catalog,
+            normalize_opts.multi_schema() || pulled_dependencies,
+            &mut warnings,
+        ),
+        SchemaLayout::Attach => transform::attach::qualify_names(
+            &mut model,
+            &catalog,
+            opts.schema.as_deref().unwrap_or("public"),
+            &mut warnings,
+        ),
+    }
+
+    // 7a. Apply table and column renames
+    transform::rename::apply_renames(&mut model, &mut origins, &opts.rename)?;
 
-    // 10. Check strict mode
+    // 7b. Move geometry columns into the SpatiaLite script
+    let spatialite_sql = if opts.spatialite {
+        transform::extension::extract_spatialite(&mut model, &mut warnings)
+    } else {
+        None
+    };
+
+    // 8-9. Order and render each database
+    let mut databases = Vec::new();
+    let sqlite_sql = match opts.schema_layout {
+        SchemaLayout::Prefix => render_database(model, &origins, opts, &mut warnings),
+        SchemaLayout::Attach => {
+            let triggers = transform::attach::extract_cross_schema_fks(
+                &mut model,
+                opts.enable_foreign_keys,
+                &mut warnings,
+            );
+            for part in transform::attach::split_by_schema(model, origins) {
+                databases.push(SchemaDatabase {
+                    sqlite_sql: render_database(part.model, &part.origins, opts, &mut warnings),
+                    schema: part.schema,
+                });
+            }
+            let schemas: Vec<String> = databases.iter().map(|d| d.schema.clone()).collect();
+            transform::attach::render_attach_script(&schemas, &triggers, opts.enable_foreign_keys)
+        }
+    };
+
+    // 10. Drop warnings suppressed by overrides, then check strict mode
+    transform::overrides::suppress_warnings(&mut warnings, &suppressions);
     if opts.strict {
         check_strict(&warnings)?;
     }
 
     Ok(ConvertResult {
         sqlite_sql,
+        spatialite_sql,
+        databases,
         warnings,
     })
 }
 
+/// Build the metadata rows, order the tables and render one database.
+fn render_database(
+    mut model: ir::SchemaModel,
+    origins: &[HashMap<String, transform::metadata::ColumnOrigin>],
+    opts: &ConvertOptions,
+    warnings: &mut Vec<Warning>,
+) -> String {
+    // 7c. Build the column metadata rows
+    if opts.metadata_table {
+        transform::metadata::build_column_metadata(&mut model, origins, opts.interval_mode);
+    }
+
+    // 8. Topological sort (if FK enabled)
+    if opts.enable_foreign_keys {
+        transform::topo::topological_sort(&mut model.tables, warnings);
+    } else {
+        // Alphabetical order when FKs disabled
+        model
+            .tables
+            .sort_by(|a, b| a.name.name.normalized.cmp(&b.name.name.normalized));
+    }
+
+    // 9. Render SQLite DDL
+    sqlite::render::render(
+        &model,
+        opts.enable_foreign_keys,
+        opts.strict_tables,
+        opts.preserve_type_names,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -280,6 +510,731 @@ mod tests {
         assert!(!result.sqlite_sql.contains("id INTEGER NOT NULL"));
     }
 
+    #[test]
+    fn test_uuid_default_text_mode() {
+        let input = "CREATE TABLE t (id UUID PRIMARY KEY DEFAULT gen_random_uuid());";
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(4))")
+        );
+        assert!(
+            !result
+                .warnings
+                .iter()
+                .any(|w| w.code == "DEFAULT_UNSUPPORTED")
+        );
+    }
+
+    #[test]
+    fn test_uuid_blob_mode_with_fk() {
+        let input = r#"
+            CREATE TABLE users (id UUID PRIMARY KEY DEFAULT uuid_generate_v4());
+            CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id UUID REFERENCES users(id));
+        "#;
+        let opts = ConvertOptions {
+            enable_foreign_keys: true,
+            uuid_mode: UuidMode::Blob,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("id BLOB PRIMARY KEY DEFAULT (unhex(hex(randomblob(6))")
+        );
+        // The generated default is SQLite already; random() must not be translated again
+        let default = transform::uuid::uuid_v4_expr(UuidMode::Blob).to_sql();
+        assert!(default.contains("substr('89AB', 1 + (abs(random()) % 4), 1)"));
+        assert!(result.sqlite_sql.contains(&format!("DEFAULT ({default})")));
+        assert!(result.sqlite_sql.contains("CHECK (length(id) = 16)"));
+        assert!(
+            result
+                .sqlite_sql
+                .contains("user_id BLOB CHECK (length(user_id) = 16) REFERENCES users(id)")
+        );
+        assert!(!result.warnings.iter().any(|w| w.code == "UUID_AS_TEXT"));
+    }
+
+    #[test]
+    fn test_uuid_blob_mode_literal_default() {
+        let input = r#"
+            CREATE TABLE tokens (
+                owner UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000',
+                CHECK (owner <> 'ffffffff-ffff-ffff-ffff-ffffffffffff'::uuid)
+            );
+        "#;
+        let opts = ConvertOptions {
+            uuid_mode: UuidMode::Blob,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        // The default must satisfy the length CHECK of its own column
+        assert!(result.sqlite_sql.contains(
+            "owner BLOB NOT NULL DEFAULT X'00000000000000000000000000000000' CHECK (length(owner) = 16)"
+        ));
+        assert!(
+            result
+                .sqlite_sql
+                .contains("CHECK (owner <> X'FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF')")
+        );
+    }
+
+    #[test]
+    fn test_numeric_per_column_modes() {
+        let input = r#"
+            CREATE TABLE invoices (
+                id INTEGER PRIMARY KEY,
+                total NUMERIC(12, 2) NOT NULL DEFAULT 0.00 CHECK (total >= 0),
+                rate NUMERIC(8, 4) DEFAULT '1.5'::numeric,
+                fee MONEY DEFAULT 2.5
+            );
+        "#;
+        let mut opts = ConvertOptions {
+            numeric_mode: NumericMode::Integer,
+            ..Default::default()
+        };
+        opts.numeric_columns
+            .insert("invoices.rate".to_string(), NumericMode::Text);
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("total INTEGER NOT NULL DEFAULT 0 CHECK (total >= 0)")
+        );
+        assert!(
+            result
+                .sqlite_sql
+                .contains("rate TEXT DEFAULT '1.5000' CHECK (")
+        );
+        assert!(result.sqlite_sql.contains("fee INTEGER DEFAULT 250"));
+        assert!(
+            !result
+                .warnings
+                .iter()
+                .any(|w| w.code == "NUMERIC_PRECISION_LOSS" || w.code == "MONEY_AS_TEXT")
+        );
+    }
+
+    #[test]
+    fn test_range_split_mode() {
+        let input = r#"
+            CREATE TABLE bookings (
+                id INTEGER PRIMARY KEY,
+                during TSTZRANGE NOT NULL,
+                CHECK (upper(during) > lower(during))
+            );
+            CREATE INDEX idx_bookings_start ON bookings (lower(during));
+        "#;
+        let opts = ConvertOptions {
+            range_mode: RangeMode::Split,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(result.sqlite_sql.contains("during_lower TEXT,"));
+        assert!(result.sqlite_sql.contains("during_upper TEXT,"));
+        assert!(
+            result
+                .sqlite_sql
+                .contains("CHECK (during_upper > during_lower)")
+        );
+        assert!(
+            result
+                .sqlite_sql
+                .contains("CHECK (during_lower <= during_upper)")
+        );
+        assert!(
+            result
+                .sqlite_sql
+                .contains("CREATE INDEX idx_bookings_start ON bookings (during_lower);")
+        );
+        assert!(!result.warnings.iter().any(|w| w.code == "RANGE_AS_TEXT"));
+    }
+
+    #[test]
+    fn test_interval_seconds_mode() {
+        let input = r#"
+            CREATE TABLE jobs (
+                id INTEGER PRIMARY KEY,
+                timeout INTERVAL NOT NULL DEFAULT '30 minutes'::interval,
+                run_at TIMESTAMP DEFAULT now() + interval '1 day',
+                CHECK (timeout <= interval '1 day')
+            );
+        "#;
+        let opts = ConvertOptions {
+            interval_mode: IntervalMode::Seconds,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("timeout INTEGER NOT NULL DEFAULT 1800")
+        );
+        assert!(
+            result
+                .sqlite_sql
+                .contains("run_at TEXT DEFAULT (datetime('now', '+1 days'))")
+        );
+        assert!(result.sqlite_sql.contains("CHECK (timeout <= 86400)"));
+    }
+
+    #[test]
+    fn test_extension_types() {
+        let input = r#"
+            CREATE EXTENSION IF NOT EXISTS citext;
+            CREATE EXTENSION IF NOT EXISTS postgis;
+            CREATE TABLE places (
+                id INTEGER PRIMARY KEY,
+                name CITEXT NOT NULL,
+                geom geometry(Point,4326) NOT NULL
+            );
+            CREATE UNIQUE INDEX places_name_key ON places (name);
+            CREATE INDEX places_geom_idx ON places USING gist (geom);
+        "#;
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("name TEXT COLLATE NOCASE NOT NULL")
+        );
+        assert!(result.sqlite_sql.contains("geom BLOB NOT NULL"));
+        assert!(result.spatialite_sql.is_none());
+
+        let opts = ConvertOptions {
+            spatialite: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(!result.sqlite_sql.contains("geom"));
+        let script = result.spatialite_sql.unwrap();
+        assert!(script.contains("AddGeometryColumn('places', 'geom', 4326, 'POINT', 'XY', 1)"));
+        assert!(script.contains("CreateSpatialIndex('places', 'geom')"));
+    }
+
+    #[test]
+    fn test_composite_modes() {
+        let input = r#"
+            CREATE TYPE address AS (street text, zip text);
+            CREATE TABLE shops (
+                id INTEGER PRIMARY KEY,
+                addr address DEFAULT ROW('Main St', '12345'),
+                CHECK ((addr).zip <> '')
+            );
+        "#;
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        assert!(result.sqlite_sql.contains(
+            r#"addr TEXT DEFAULT '{"street":"Main St","zip":"12345"}' CHECK ((json_valid(addr))"#
+        ));
+        assert!(
+            result
+                .sqlite_sql
+                .contains("CHECK (json_extract(addr, '$.zip') <> '')")
+        );
+
+        let opts = ConvertOptions {
+            composite_mode: CompositeMode::Columns,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("addr_street TEXT DEFAULT 'Main St'")
+        );
+        assert!(result.sqlite_sql.contains("addr_zip TEXT DEFAULT '12345'"));
+        assert!(result.sqlite_sql.contains("CHECK (addr_zip <> '')"));
+    }
+
+    #[test]
+    fn test_strict_tables() {
+        let input = r#"
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY,
+                price NUMERIC(10,2) DEFAULT 9.99,
+                qty INTEGER DEFAULT 'n/a'
+            );
+        "#;
+        let opts = ConvertOptions {
+            strict_tables: true,
+            strict_numeric: StrictNumeric::Text,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(result.sqlite_sql.contains(") STRICT;"));
+        assert!(result.sqlite_sql.contains("price TEXT DEFAULT 9.99"));
+        assert!(result.sqlite_sql.contains("qty INTEGER\n"));
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.code == "STRICT_DEFAULT_INVALID")
+        );
+
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        assert!(!result.sqlite_sql.contains("STRICT"));
+        assert!(result.sqlite_sql.contains("price NUMERIC"));
+    }
+
+    #[test]
+    fn test_without_rowid() {
+        let input = r#"
+            CREATE TABLE users (id SERIAL PRIMARY KEY, email TEXT);
+            CREATE TABLE user_roles (
+                user_id INTEGER NOT NULL,
+                role_id INTEGER NOT NULL,
+                PRIMARY KEY (user_id, role_id)
+            );
+        "#;
+        let opts = ConvertOptions {
+            without_rowid: WithoutRowidMode::Auto,
+            strict_tables: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("PRIMARY KEY (user_id, role_id)\n) WITHOUT ROWID, STRICT;")
+        );
+        assert!(result.sqlite_sql.contains("email TEXT\n) STRICT;"));
+
+        let opts = ConvertOptions {
+            without_rowid: WithoutRowidMode::All,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert_eq!(result.sqlite_sql.matches("WITHOUT ROWID").count(), 1);
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.code == "WITHOUT_ROWID_SKIPPED" && w.object.as_deref() == Some("users"))
+        );
+    }
+
+    #[test]
+    fn test_preserve_type_names_keeps_rowid_alias() {
+        let input = r#"
+            CREATE TABLE a (id bigint PRIMARY KEY, n bigint);
+            CREATE TABLE b (id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY);
+            CREATE TABLE c (id bigint, PRIMARY KEY (id));
+            CREATE TABLE d (x bigint, y bigint, PRIMARY KEY (x, y));
+        "#;
+        let opts = ConvertOptions {
+            preserve_type_names: true,
+            ..Default::default()
+        };
+        let sql = convert_pg_ddl_to_sqlite(input, &opts).unwrap().sqlite_sql;
+        assert!(sql.contains("CREATE TABLE a (\n  id INTEGER PRIMARY KEY,\n  n BIGINT\n"));
+        assert!(sql.contains("CREATE TABLE b (\n  id INTEGER PRIMARY KEY\n"));
+        assert!(sql.contains("CREATE TABLE c (\n  id INTEGER PRIMARY KEY\n"));
+        assert!(sql.contains("  x BIGINT,\n  y BIGINT,\n  PRIMARY KEY (x, y)"));
+    }
+
+    #[test]
+    fn test_preserve_type_names() {
+        let input = r#"
+            CREATE TABLE events (
+                id UUID PRIMARY KEY,
+                created_at TIMESTAMPTZ NOT NULL,
+                price DECIMAL(10,2),
+                active BOOLEAN,
+                payload JSONB,
+                location POINT,
+                tags INTEGER[],
+                labels VARCHAR(20)[]
+            );
+        "#;
+        let opts = ConvertOptions {
+            preserve_type_names: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(result.sqlite_sql.contains(
+            "CREATE TABLE events (
+  id UUID TEXT PRIMARY KEY,
+  created_at TIMESTAMPTZ TEXT NOT NULL,
+  price NUMERIC(10,2),
+  active BOOLEAN,
+  payload JSONB TEXT,
+  location TEXT,
+  tags INTEGER[],
+  labels VARCHAR[]
+);"
+        ));
+    }
+
+    #[test]
+    fn test_metadata_table() {
+        let input = r#"
+            CREATE TYPE mood AS ENUM ('happy', 'it''s "fine"');
+            CREATE DOMAIN email AS varchar(255) NOT NULL CHECK (VALUE LIKE '%@%');
+            CREATE TABLE users (
+                id UUID PRIMARY KEY,
+                contact email,
+                active BOOLEAN,
+                feeling mood,
+                created_at TIMESTAMPTZ
+            );
+        "#;
+        let opts = ConvertOptions {
+            metadata_table: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("CREATE TABLE _pg2sqlite_columns ("));
+        assert!(sql.contains("('users', 'id', 'uuid', 'TEXT', 'uuid-text', NULL, NULL)"));
+        assert!(
+            sql.contains("('users', 'contact', 'varchar(255)', 'TEXT', 'text', 'email', NULL)")
+        );
+        assert!(sql.contains("contact TEXT NOT NULL CHECK (contact GLOB '*@*')"));
+        assert!(sql.contains("'boolean-integer'"));
+        assert!(sql.contains(
+            r#"('users', 'feeling', 'mood', 'TEXT', 'enum', NULL, '["happy","it''s \"fine\""]')"#
+        ));
+
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        assert!(!result.sqlite_sql.contains("_pg2sqlite_columns"));
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let input = r#"
+            CREATE EXTENSION ltree;
+            CREATE DOMAIN email AS citext;
+            CREATE TABLE docs (
+                id UUID PRIMARY KEY,
+                path ltree NOT NULL,
+                owner email,
+                body JSONB,
+                meta JSONB
+            );
+        "#;
+        let config = config::Config::from_toml(
+            r#"
+            uuid_mode = "blob"
+            strict = true
+
+            [type_overrides.ltree]
+            sqlite_type = "text"
+            check = "{column} GLOB '[A-Za-z0-9_.]*'"
+            suppress = ["LTREE_AS_TEXT", "TYPE_OVERRIDDEN"]
+
+            [type_overrides.email]
+            sqlite_type = "text"
+
+            [type_overrides.jsonb]
+            suppress = ["JSONB_LOSS"]
+
+            [[column_overrides]]
+            column = "docs.me*"
+            sqlite_type = "blob"
+            "#,
+        )
+        .unwrap();
+        let mut opts = ConvertOptions::default();
+        config.apply(&mut opts);
+
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("id BLOB PRIMARY KEY CHECK (length(id) = 16)"));
+        assert!(sql.contains("path TEXT NOT NULL CHECK (path GLOB '[A-Za-z0-9_.]*')"));
+        assert!(sql.contains("owner TEXT,"));
+        assert!(!sql.contains("owner TEXT COLLATE NOCASE"));
+        assert!(sql.contains("body TEXT,"));
+        assert!(sql.contains("meta BLOB\n"));
+
+        let codes: Vec<(&str, &str)> = result
+            .warnings
+            .iter()
+            .map(|w| (w.code, w.object.as_deref().unwrap_or_default()))
+            .collect();
+        assert!(!codes.contains(&("LTREE_AS_TEXT", "docs.path")));
+        assert!(!codes.contains(&("TYPE_OVERRIDDEN", "docs.path")));
+        assert!(!codes.contains(&("JSONB_LOSS", "docs.body")));
+        assert!(codes.contains(&("TYPE_OVERRIDDEN", "docs.owner")));
+        assert!(codes.contains(&("TYPE_OVERRIDDEN", "docs.meta")));
+    }
+
+    #[test]
+    fn test_table_and_column_filters() {
+        let input = r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                email TEXT NOT NULL,
+                password_hash TEXT NOT NULL CHECK (length(password_hash) = 60)
+            );
+            CREATE TABLE audit_log (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));
+            CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));
+            ALTER TABLE orders ADD CONSTRAINT orders_audit_fkey
+                FOREIGN KEY (id) REFERENCES audit_log(id);
+            CREATE UNIQUE INDEX idx_users_login ON users (email, password_hash);
+        "#;
+        let opts = ConvertOptions {
+            enable_foreign_keys: true,
+            exclude_tables: vec!["audit_*".to_string()],
+            exclude_columns: vec!["users.password_hash".to_string()],
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(!sql.contains("audit_log"));
+        assert!(!sql.contains("password_hash"));
+        assert!(!sql.contains("idx_users_login"));
+        assert!(sql.contains("user_id INTEGER REFERENCES users(id)"));
+
+        let dropped: Vec<&str> = result
+            .warnings
+            .iter()
+            .filter(|w| w.code == "FILTER_DEPENDENT_DROPPED")
+            .filter_map(|w| w.object.as_deref())
+            .collect();
+        assert_eq!(dropped, ["orders.orders_audit_fkey", "idx_users_login"]);
+        assert!(
+            !result
+                .warnings
+                .iter()
+                .any(|w| w.code == "ALTER_TARGET_MISSING")
+        );
+    }
+
+    #[test]
+    fn test_quoted_identifiers_keep_case() {
+        let input = r#"
+            CREATE TYPE "Mood" AS ENUM ('sad', 'happy');
+            CREATE TABLE "UserProfile" (
+                "userId" SERIAL PRIMARY KEY,
+                "createdAt" TEXT NOT NULL CHECK ("createdAt" <> ''),
+                "Mood" "Mood"
+            );
+            CREATE TABLE posts (id INTEGER PRIMARY KEY, "authorId" INTEGER);
+            ALTER TABLE posts ADD CONSTRAINT posts_author_fkey
+                FOREIGN KEY ("authorId") REFERENCES public."UserProfile" ("userId");
+            CREATE INDEX "Idx_Posts_Author" ON posts ("authorId");
+        "#;
+        let opts = ConvertOptions {
+            enable_foreign_keys: true,
+            metadata_table: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("CREATE TABLE \"UserProfile\" (\n  \"userId\" INTEGER PRIMARY KEY,"));
+        assert!(sql.contains("\"createdAt\" TEXT NOT NULL CHECK (\"createdAt\" <> '')"));
+        assert!(sql.contains("FOREIGN KEY (\"authorId\") REFERENCES \"UserProfile\"(\"userId\")"));
+        assert!(sql.contains("CREATE INDEX \"Idx_Posts_Author\" ON posts (\"authorId\");"));
+        // The quoted enum type resolves, and the parent is created first
+        assert!(sql.contains("('UserProfile', 'Mood', 'Mood', 'TEXT', 'enum'"));
+        assert!(sql.find("\"UserProfile\" (").unwrap() < sql.find("posts (").unwrap());
+        assert!(
+            !result
+                .warnings
+                .iter()
+                .any(|w| w.code == "ALTER_TARGET_MISSING")
+        );
+    }
+
+    #[test]
+    fn test_search_path_resolution() {
+        let input = r#"
+            SELECT pg_catalog.set_config('search_path', '', false);
+            CREATE TABLE public.accounts (id INTEGER PRIMARY KEY, name TEXT);
+            SET search_path TO billing, public;
+            CREATE TABLE accounts (id INTEGER PRIMARY KEY);
+            CREATE TABLE invoices (id INTEGER PRIMARY KEY, account_id INTEGER REFERENCES accounts(id));
+            CREATE INDEX idx_invoices_account ON invoices (account_id);
+            SET search_path TO public;
+            CREATE TABLE users (id INTEGER PRIMARY KEY, account_id INTEGER);
+            ALTER TABLE users ADD CONSTRAINT users_account_fkey
+                FOREIGN KEY (account_id) REFERENCES accounts(id);
+        "#;
+        let opts = ConvertOptions {
+            schema: Some("billing".to_string()),
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("CREATE TABLE accounts (\n  id INTEGER PRIMARY KEY\n);"));
+        assert!(sql.contains("CREATE INDEX idx_invoices_account ON invoices (account_id);"));
+        assert!(!sql.contains("users"));
+
+        let opts = ConvertOptions {
+            schema: None,
+            include_all_schemas: true,
+            enable_foreign_keys: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("account_id INTEGER REFERENCES billing__accounts(id)"));
+        assert!(sql.contains("FOREIGN KEY (account_id) REFERENCES public__accounts(id)"));
+    }
+
+    #[test]
+    fn test_schema_aware_lookups() {
+        let input = r#"
+            CREATE TYPE public.status AS ENUM ('active', 'closed');
+            CREATE TYPE billing.status AS ENUM ('open', 'paid');
+            CREATE TABLE public.accounts (id INTEGER PRIMARY KEY, state public.status);
+            CREATE TABLE billing.accounts (id INTEGER PRIMARY KEY, code TEXT, state billing.status);
+            CREATE TABLE billing.invoices (id INTEGER PRIMARY KEY, account_id INTEGER);
+            ALTER TABLE billing.accounts ADD CONSTRAINT accounts_code_key UNIQUE (code);
+            ALTER TABLE billing.invoices ADD CONSTRAINT invoices_account_fkey
+                FOREIGN KEY (account_id) REFERENCES billing.accounts(id);
+            ALTER TABLE accounts ADD CONSTRAINT accounts_id_check CHECK (id > 0);
+            CREATE TABLE ledger (id INTEGER PRIMARY KEY, state status);
+        "#;
+        let opts = ConvertOptions {
+            schema: None,
+            include_all_schemas: true,
+            enable_foreign_keys: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        let billing = sql.find("CREATE TABLE billing__accounts").unwrap();
+        let public = sql.find("CREATE TABLE public__accounts").unwrap();
+        let invoices = sql.find("CREATE TABLE invoices").unwrap();
+        assert!(sql[billing..].contains("UNIQUE (code)\n);\n\nCREATE TABLE ledger"));
+        assert!(!sql.contains("id > 0"));
+        assert!(sql.contains("FOREIGN KEY (account_id) REFERENCES billing__accounts(id)"));
+        assert!(billing < invoices && public < invoices);
+        let enums = result
+            .warnings
+            .iter()
+            .filter(|w| w.code == "ENUM_AS_TEXT")
+            .count();
+        assert_eq!(enums, 2);
+
+        let ambiguous: Vec<&str> = result
+            .warnings
+            .iter()
+            .filter(|w| w.code == "AMBIGUOUS_REFERENCE")
+            .map(|w| w.message.as_str())
+            .collect();
+        assert_eq!(ambiguous.len(), 2, "{ambiguous:?}");
+        assert!(ambiguous[0].contains("public.accounts, billing.accounts"));
+        assert!(ambiguous[1].contains("public.status, billing.status"));
+    }
+
+    #[test]
+    fn test_renames() {
+        let input = r#"
+            CREATE TABLE tbl_user (id INTEGER PRIMARY KEY, emailaddr TEXT CHECK (emailaddr LIKE '%@%'));
+            CREATE TABLE tbl_order (
+                id INTEGER PRIMARY KEY,
+                user_id INTEGER REFERENCES tbl_user(id),
+                total NUMERIC(10,2) DEFAULT 0
+            );
+            CREATE INDEX idx_order_user ON tbl_order (user_id) WHERE total > 0;
+        "#;
+        let opts = ConvertOptions {
+            enable_foreign_keys: true,
+            metadata_table: true,
+            rename: RenameOptions {
+                tables: HashMap::from([("tbl_order".to_string(), "orders".to_string())]),
+                columns: HashMap::from([
+                    ("tbl_user.emailaddr".to_string(), "email".to_string()),
+                    ("tbl_order.total".to_string(), "amount".to_string()),
+                ]),
+                rules: vec![transform::rename::RenameRule::strip_prefix(
+                    transform::rename::RenameTarget::Tables,
+                    "tbl_",
+                )],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(!sql.contains("tbl_"));
+        assert!(sql.contains("email TEXT CHECK (email GLOB '*@*')"));
+        assert!(sql.contains("user_id INTEGER REFERENCES user(id)"));
+        assert!(sql.contains("amount NUMERIC DEFAULT 0"));
+        assert!(sql.contains("CREATE INDEX idx_order_user ON orders (user_id) WHERE amount > 0;"));
+        assert!(sql.contains("('orders', 'amount', 'numeric(10,2)'"));
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(sql).unwrap();
+        let mut stmt = conn
+            .prepare("SELECT table_name, column_name FROM _pg2sqlite_columns ORDER BY 1, 2")
+            .unwrap();
+        let columns: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let expected = [
+            ("orders", "amount"),
+            ("orders", "id"),
+            ("orders", "user_id"),
+            ("user", "email"),
+            ("user", "id"),
+        ];
+        assert_eq!(
+            columns,
+            expected.map(|(t, c)| (t.to_string(), c.to_string()))
+        );
+        assert!(
+            conn.execute_batch("INSERT INTO orders (id, user_id) VALUES (1, 1);")
+                .is_err()
+        );
+        conn.execute_batch(
+            "INSERT INTO user (id, email) VALUES (1, 'a@b');
+             INSERT INTO orders (id, user_id) VALUES (1, 1);",
+        )
+        .unwrap();
+
+        let opts = ConvertOptions {
+            rename: RenameOptions {
+                tables: HashMap::from([("tbl_order".to_string(), "TBL_USER".to_string())]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = convert_pg_ddl_to_sqlite(input, &opts).unwrap_err();
+        assert!(matches!(err, ConvertError::Rename(_)));
+    }
+
+    #[test]
+    fn test_collations() {
+        let input = r#"
+            CREATE COLLATION public.ci (provider = icu, locale = 'und-u-ks-level2', deterministic = false);
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                email TEXT COLLATE public.ci NOT NULL,
+                login TEXT COLLATE "C",
+                name TEXT COLLATE "en_US"
+            );
+            CREATE UNIQUE INDEX users_login_key ON users (lower(login));
+        "#;
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        assert!(
+            result
+                .sqlite_sql
+                .contains("email TEXT COLLATE NOCASE NOT NULL")
+        );
+        assert!(result.sqlite_sql.contains("login TEXT COLLATE BINARY"));
+        assert!(!result.sqlite_sql.contains("en_US"));
+        assert!(result.sqlite_sql.contains("(lower(login))"));
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.code == "COLLATION_UNSUPPORTED")
+        );
+
+        let opts = ConvertOptions {
+            lower_index_nocase: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        assert!(result.sqlite_sql.contains("(login COLLATE NOCASE)"));
+    }
+
     #[test]
     fn test_include_all_schemas() {
         let input = r#"
@@ -294,4 +1249,212 @@ mod tests {
         assert!(result.sqlite_sql.contains("users"));
         assert!(result.sqlite_sql.contains("accounts"));
     }
+
+    #[test]
+    fn test_include_and_exclude_schemas() {
+        let input = r#"
+            CREATE TABLE public.users (id INTEGER PRIMARY KEY);
+            CREATE TABLE billing.users (id INTEGER PRIMARY KEY);
+            CREATE TABLE auth.users (id INTEGER PRIMARY KEY);
+            CREATE TABLE auth.sessions (id INTEGER PRIMARY KEY);
+            CREATE TABLE billing.invoices (
+                id INTEGER PRIMARY KEY,
+                user_id INTEGER REFERENCES billing.users(id),
+                session_id INTEGER REFERENCES auth.sessions(id)
+            );
+        "#;
+        let opts = ConvertOptions {
+            include_schemas: vec!["public".into(), "billing".into()],
+            exclude_schemas: vec!["auth".into()],
+            enable_foreign_keys: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("CREATE TABLE public__users"));
+        assert!(sql.contains("user_id INTEGER REFERENCES billing__users(id)"));
+        assert!(sql.contains("session_id INTEGER\n"));
+        assert!(!sql.contains("sessions"));
+        assert!(!sql.contains("auth"));
+        assert!(result.warnings.iter().any(|w| {
+            w.code == "FILTER_DEPENDENT_DROPPED" && w.message.contains("schema auth is excluded")
+        }));
+    }
+
+    #[test]
+    fn test_include_dependencies() {
+        let input = r#"
+            CREATE TYPE auth.role AS ENUM ('admin', 'member');
+            CREATE TABLE auth.users (id INTEGER PRIMARY KEY, role auth.role);
+            CREATE TABLE public.users (id INTEGER PRIMARY KEY);
+            CREATE TABLE public.posts (
+                id INTEGER PRIMARY KEY,
+                author_id INTEGER REFERENCES auth.users(id)
+            );
+        "#;
+        let opts = ConvertOptions {
+            include_dependencies: true,
+            enable_foreign_keys: true,
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains(
+            "-- auth.users included as a dependency of public.posts\nCREATE TABLE auth__users"
+        ));
+        assert!(sql.contains("role TEXT"));
+        assert!(sql.contains("CREATE TABLE public__users"));
+        assert!(sql.contains("author_id INTEGER REFERENCES auth__users(id)"));
+        assert!(
+            result.warnings.iter().any(
+                |w| w.code == "DEPENDENCY_INCLUDED" && w.object.as_deref() == Some("auth.role")
+            )
+        );
+
+        let without = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        assert!(!without.sqlite_sql.contains("auth__users"));
+    }
+
+    #[test]
+    fn test_structured_expressions() {
+        let input = r#"
+            CREATE TABLE events (
+                id INTEGER PRIMARY KEY,
+                name TEXT CHECK (name NOT ILIKE 'test%'),
+                kind TEXT CHECK (CASE kind WHEN 'a' THEN true ELSE POSITION('x' IN kind) > 0 END),
+                happened_on DATE DEFAULT DATE '2020-01-01'
+                    CHECK (EXTRACT(year FROM happened_on) >= 2000)
+            );
+        "#;
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("CHECK (name NOT LIKE 'test%')"));
+        assert!(sql.contains("CHECK (CASE kind WHEN 'a' THEN 1 ELSE instr(kind, 'x') > 0 END)"));
+        assert!(sql.contains(
+            "DEFAULT '2020-01-01' CHECK (CAST(strftime('%Y', happened_on) AS INTEGER) >= 2000)"
+        ));
+        assert!(result.warnings.iter().any(|w| w.code == "ILIKE_AS_LIKE"));
+    }
+
+    #[test]
+    fn test_casts_follow_operand_type() {
+        let input = r#"
+            CREATE TABLE t (
+                id BIGINT CHECK (id::bigint <> 0),
+                price NUMERIC(10,2) CHECK (price::integer < 100),
+                n INTEGER CHECK (n::numeric / 2 < 10)
+            );
+        "#;
+        let result = convert_pg_ddl_to_sqlite(input, &ConvertOptions::default()).unwrap();
+        let sql = &result.sqlite_sql;
+        assert!(sql.contains("CHECK (CAST(id AS INTEGER) <> 0)"));
+        assert!(sql.contains("CHECK (CAST(round(price) AS INTEGER) < 100)"));
+        assert!(sql.contains("CHECK (CAST(n AS REAL) / 2 < 10)"));
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(sql).unwrap();
+        // Beyond 2^53 a detour through REAL would land on a neighbouring value
+        conn.execute("INSERT INTO t (id) VALUES (9007199254740993)", [])
+            .unwrap();
+        let id: i64 = conn
+            .query_row("SELECT CAST(id AS INTEGER) FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(id, 9007199254740993);
+        assert!(
+            conn.execute("INSERT INTO t (price) VALUES (99.5)", [])
+                .is_err()
+        );
+        assert!(
+            conn.execute("INSERT INTO t (price) VALUES (99.4)", [])
+                .is_ok()
+        );
+        // 19 / 2 is 9.5 in PG; integer division would let 20 through
+        assert!(conn.execute("INSERT INTO t (n) VALUES (20)", []).is_err());
+        assert!(conn.execute("INSERT INTO t (n) VALUES (19)", []).is_ok());
+    }
+
+    #[test]
+    fn test_attach_layout() {
+        let input = r#"
+            CREATE TABLE public.tbl_accounts (id INTEGER PRIMARY KEY);
+            CREATE TABLE billing.tbl_accounts (id INTEGER PRIMARY KEY);
+            CREATE TABLE billing.invoices (
+                id INTEGER PRIMARY KEY,
+                account_id INTEGER REFERENCES billing.tbl_accounts(id),
+                owner_id INTEGER REFERENCES public.tbl_accounts(id)
+            );
+        "#;
+        let opts = ConvertOptions {
+            schema: None,
+            include_all_schemas: true,
+            enable_foreign_keys: true,
+            schema_layout: SchemaLayout::Attach,
+            rename: RenameOptions {
+                rules: vec![transform::rename::RenameRule::strip_prefix(
+                    transform::rename::RenameTarget::Tables,
+                    "tbl_",
+                )],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = convert_pg_ddl_to_sqlite(input, &opts).unwrap();
+        let schemas: Vec<&str> = result.databases.iter().map(|d| d.schema.as_str()).collect();
+        assert_eq!(schemas, ["billing", "public"]);
+        let billing = &result.databases[0].sqlite_sql;
+        assert!(billing.contains("CREATE TABLE accounts"));
+        assert!(billing.contains("account_id INTEGER REFERENCES accounts(id)"));
+        assert!(billing.contains("owner_id INTEGER\n"));
+        assert!(!billing.contains("__"));
+        assert!(
+            result.databases[1]
+                .sqlite_sql
+                .contains("CREATE TABLE accounts")
+        );
+
+        let script = &result.sqlite_sql;
+        assert!(script.contains("ATTACH DATABASE 'billing.db' AS billing;"));
+        assert!(script.contains("BEFORE INSERT ON billing.invoices"));
+        assert!(script.contains("SELECT 1 FROM public.accounts WHERE id = NEW.owner_id"));
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.code == "CROSS_SCHEMA_FK_EMULATED")
+        );
+
+        // The triggers enforce the key across the attached databases
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        for database in &result.databases {
+            conn.execute_batch(&format!(
+                "ATTACH DATABASE ':memory:' AS {0}; {1}",
+                database.schema,
+                database.sqlite_sql.replace(
+                    "CREATE TABLE ",
+                    &format!("CREATE TABLE {}.", database.schema)
+                )
+            ))
+            .unwrap();
+        }
+        let triggers: String = script
+            .lines()
+            .filter(|line| !line.starts_with("ATTACH"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        conn.execute_batch(&triggers).unwrap();
+        conn.execute_batch("INSERT INTO public.accounts (id) VALUES (1);")
+            .unwrap();
+        assert!(
+            conn.execute_batch("INSERT INTO billing.invoices (id, owner_id) VALUES (1, 1);")
+                .is_ok()
+        );
+        assert!(
+            conn.execute_batch("INSERT INTO billing.invoices (id, owner_id) VALUES (2, 2);")
+                .is_err()
+        );
+        assert!(
+            conn.execute_batch("DELETE FROM public.accounts WHERE id = 1;")
+                .is_err()
+        );
+    }
 }
@@ -1,2 +1,3 @@
 pub mod normalize;
 pub mod parser;
+pub mod search_path;
@@ -1,12 +1,22 @@
 /// Schema filtering and identifier normalization for parsed PG DDL.
-use crate::ir::SchemaModel;
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{PgType, QualifiedName, SchemaModel, TableConstraint};
+use crate::transform::catalog::{Catalog, Lookup, TypeRef};
+use crate::transform::overrides::glob_match;
 
 /// Options for schema normalization.
 pub struct NormalizeOptions {
-    /// Schema to include (default: "public").
+    /// Schema to include (default: "public"); unqualified names are assumed to be in it.
     pub schema: Option<String>,
     /// If true, include all schemas (bypass schema filtering).
     pub include_all_schemas: bool,
+    /// Include schemas matching one of these globs instead of `schema`.
+    pub include_schemas: Vec<String>,
+    /// Drop schemas matching one of these globs.
+    pub exclude_schemas: Vec<String>,
+    /// If true, also keep the tables and types that included tables need
+    /// from other schemas, except from `exclude_schemas`.
+    pub include_dependencies: bool,
 }
 
 impl Default for NormalizeOptions {
@@ -14,61 +24,324 @@ impl Default for NormalizeOptions {
         Self {
             schema: Some("public".to_string()),
             include_all_schemas: false,
+            include_schemas: Vec::new(),
+            exclude_schemas: Vec::new(),
+            include_dependencies: false,
         }
     }
 }
 
+impl NormalizeOptions {
+    /// Whether tables from several schemas may be converted together.
+    pub fn multi_schema(&self) -> bool {
+        self.include_all_schemas || !self.include_schemas.is_empty()
+    }
+
+    /// Whether objects of `schema` are converted; `None` stands for an
+    /// unqualified name, which is assumed to be in the target schema.
+    pub fn includes(&self, schema: Option<&str>) -> bool {
+        let Some(schema) = schema.or(self.schema.as_deref()) else {
+            return true;
+        };
+        let matches = |patterns: &[String]| patterns.iter().any(|p| glob_match(p, schema));
+        if matches(&self.exclude_schemas) {
+            return false;
+        }
+        if self.include_all_schemas {
+            true
+        } else if !self.include_schemas.is_empty() {
+            matches(&self.include_schemas)
+        } else {
+            schema == self.schema.as_deref().unwrap_or("public")
+        }
+    }
+
+    fn includes_name(&self, name: &QualifiedName) -> bool {
+        self.includes(name.schema.as_ref().map(|s| s.normalized.as_str()))
+    }
+
+    /// Whether `name` is in a schema excluded by `exclude_schemas`.
+    fn excludes_name(&self, name: &QualifiedName) -> bool {
+        name.schema.as_ref().is_some_and(|s| {
+            self.exclude_schemas
+                .iter()
+                .any(|p| glob_match(p, &s.normalized))
+        })
+    }
+}
+
 /// Filter and normalize the schema model based on options.
-pub fn normalize(model: &mut SchemaModel, opts: &NormalizeOptions) {
-    if opts.include_all_schemas {
-        return;
+///
+/// Foreign keys from included tables into excluded schemas are dropped and
+/// reported.
+pub fn normalize(model: &mut SchemaModel, opts: &NormalizeOptions, warnings: &mut Vec<Warning>) {
+    let mut selection = Selection {
+        tables: model
+            .tables
+            .iter()
+            .map(|t| opts.includes_name(&t.name))
+            .collect(),
+        enums: model
+            .enums
+            .iter()
+            .map(|e| opts.includes_name(&e.name))
+            .collect(),
+        composites: model
+            .composites
+            .iter()
+            .map(|c| opts.includes_name(&c.name))
+            .collect(),
+        domains: model
+            .domains
+            .iter()
+            .map(|d| opts.includes_name(&d.name))
+            .collect(),
+    };
+    if opts.include_dependencies {
+        include_dependencies(model, opts, &mut selection, warnings);
     }
+    let pulled: Vec<QualifiedName> = model
+        .tables
+        .iter()
+        .zip(&selection.tables)
+        .filter(|(t, keep)| **keep && !opts.includes_name(&t.name))
+        .map(|(t, _)| t.name.clone())
+        .collect();
+    let kept = |name: &QualifiedName| {
+        opts.includes_name(name) || pulled.iter().any(|p| same_name(p, name))
+    };
 
-    let target_schema = opts.schema.as_deref().unwrap_or("public");
+    retain_selected(&mut model.tables, &selection.tables);
+    retain_selected(&mut model.enums, &selection.enums);
+    retain_selected(&mut model.composites, &selection.composites);
+    retain_selected(&mut model.domains, &selection.domains);
+    model.indexes.retain(|idx| opts.includes_name(&idx.table));
+    model.sequences.retain(|seq| opts.includes_name(&seq.name));
+    model.alter_constraints.retain(|ac| kept(&ac.table));
+    model.identity_columns.retain(|ic| kept(&ic.table));
 
-    // Filter tables by schema
-    model.tables.retain(|t| {
-        match &t.name.schema {
-            Some(s) => s.normalized == target_schema,
-            None => true, // Unqualified names are assumed to be in the target schema
+    let mut excluded_fk = |owner: &QualifiedName, name: Option<&str>, target: &QualifiedName| {
+        if kept(target) {
+            return false;
+        }
+        let obj = match name {
+            Some(name) => format!("{}.{name}", owner.name.normalized),
+            None => owner.name.normalized.clone(),
+        };
+        let schema = match &target.schema {
+            Some(s) => s.normalized.clone(),
+            None => opts.schema.clone().unwrap_or_default(),
+        };
+        warnings.push(
+            Warning::new(
+                warning::FILTER_DEPENDENT_DROPPED,
+                Severity::Info,
+                format!("FOREIGN KEY to {target} dropped: schema {schema} is excluded"),
+            )
+            .with_object(obj),
+        );
+        true
+    };
+    for table in &mut model.tables {
+        for col in &mut table.columns {
+            if let Some(fk) = &col.references
+                && excluded_fk(&table.name, Some(&col.name.normalized), &fk.table)
+            {
+                col.references = None;
+            }
         }
+        table.constraints.retain(|c| match c {
+            TableConstraint::ForeignKey {
+                name, ref_table, ..
+            } => !excluded_fk(
+                &table.name,
+                name.as_ref().map(|n| n.normalized.as_str()),
+                ref_table,
+            ),
+            _ => true,
+        });
+    }
+    model.alter_constraints.retain(|ac| match &ac.constraint {
+        TableConstraint::ForeignKey {
+            name, ref_table, ..
+        } => !excluded_fk(
+            &ac.table,
+            name.as_ref().map(|n| n.normalized.as_str()),
+            ref_table,
+        ),
+        _ => true,
     });
+}
 
-    // Filter indexes by table schema
-    model.indexes.retain(|idx| match &idx.table.schema {
-        Some(s) => s.normalized == target_schema,
-        None => true,
-    });
+/// Which objects of each kind are kept, by index into the model's lists.
+struct Selection {
+    tables: Vec<bool>,
+    enums: Vec<bool>,
+    composites: Vec<bool>,
+    domains: Vec<bool>,
+}
 
-    // Filter sequences by schema
-    model.sequences.retain(|seq| match &seq.name.schema {
-        Some(s) => s.normalized == target_schema,
-        None => true,
-    });
+impl Selection {
+    fn type_flag(&mut self, type_ref: TypeRef) -> &mut bool {
+        match type_ref {
+            TypeRef::Enum(i) => &mut self.enums[i],
+            TypeRef::Composite(i) => &mut self.composites[i],
+            TypeRef::Domain(i) => &mut self.domains[i],
+        }
+    }
+}
 
-    // Filter enums by schema
-    model.enums.retain(|e| match &e.name.schema {
-        Some(s) => s.normalized == target_schema,
-        None => true,
-    });
+/// Select the foreign key targets and user-defined column types of the
+/// selected tables, transitively, and note why each was pulled in.
+fn include_dependencies(
+    model: &mut SchemaModel,
+    opts: &NormalizeOptions,
+    selection: &mut Selection,
+    warnings: &mut Vec<Warning>,
+) {
+    let catalog = Catalog::build(model);
+    let mut pending: Vec<usize> = (0..model.tables.len())
+        .filter(|&i| selection.tables[i])
+        .collect();
+    let mut pulled_tables: Vec<(usize, String)> = Vec::new();
+    // (type name, schema to resolve it from, referring object)
+    let mut type_refs: Vec<(String, Option<QualifiedName>, String)> = Vec::new();
 
-    // Filter domains by schema
-    model.domains.retain(|d| match &d.name.schema {
-        Some(s) => s.normalized == target_schema,
-        None => true,
-    });
+    while let Some(i) = pending.pop() {
+        let table = &model.tables[i];
+        let referrer = table.name.to_string();
+        let alters = model
+            .alter_constraints
+            .iter()
+            .filter(|ac| catalog.table_index(&model.tables, &ac.table) == Lookup::Found(i))
+            .map(|ac| &ac.constraint);
+        let mut targets: Vec<&QualifiedName> = table
+            .constraints
+            .iter()
+            .chain(alters)
+            .filter_map(|c| match c {
+                TableConstraint::ForeignKey { ref_table, .. } => Some(ref_table),
+                _ => None,
+            })
+            .collect();
+        targets.extend(
+            table
+                .columns
+                .iter()
+                .filter_map(|c| c.references.as_ref().map(|fk| &fk.table)),
+        );
+        for target in targets {
+            if let Lookup::Found(j) = catalog.table_index(&model.tables, target)
+                && !selection.tables[j]
+                && !opts.excludes_name(&model.tables[j].name)
+            {
+                selection.tables[j] = true;
+                pulled_tables.push((j, referrer.clone()));
+                pending.push(j);
+            }
+        }
+        for col in &table.columns {
+            if let Some(name) = user_type_name(&col.pg_type) {
+                type_refs.push((
+                    name,
+                    Some(table.name.clone()),
+                    format!("{referrer}.{}", col.name.normalized),
+                ));
+            }
+        }
+    }
 
-    // Filter alter constraints by table schema
-    model.alter_constraints.retain(|ac| match &ac.table.schema {
-        Some(s) => s.normalized == target_schema,
-        None => true,
-    });
+    // Types of selected domains and composites need their own dependencies too
+    for (i, domain) in model.domains.iter().enumerate() {
+        if selection.domains[i]
+            && let Some(name) = user_type_name(&domain.base_type)
+        {
+            type_refs.push((name, Some(domain.name.clone()), domain.name.to_string()));
+        }
+    }
+    for (i, composite) in model.composites.iter().enumerate() {
+        if selection.composites[i] {
+            for attr in &composite.attributes {
+                if let Some(name) = user_type_name(&attr.pg_type) {
+                    type_refs.push((
+                        name,
+                        Some(composite.name.clone()),
+                        composite.name.to_string(),
+                    ));
+                }
+            }
+        }
+    }
 
-    // Filter identity columns by table schema
-    model.identity_columns.retain(|ic| match &ic.table.schema {
-        Some(s) => s.normalized == target_schema,
-        None => true,
-    });
+    while let Some((name, context, referrer)) = type_refs.pop() {
+        let schema = context.as_ref().and_then(|c| c.schema.as_ref());
+        let Lookup::Found(type_ref) = catalog.user_type(&name, schema) else {
+            continue;
+        };
+        let (type_name, nested): (&QualifiedName, Vec<&PgType>) = match type_ref {
+            TypeRef::Enum(i) => (&model.enums[i].name, Vec::new()),
+            TypeRef::Composite(i) => (
+                &model.composites[i].name,
+                model.composites[i]
+                    .attributes
+                    .iter()
+                    .map(|a| &a.pg_type)
+                    .collect(),
+            ),
+            TypeRef::Domain(i) => (&model.domains[i].name, vec![&model.domains[i].base_type]),
+        };
+        let flag = selection.type_flag(type_ref);
+        if *flag || opts.excludes_name(type_name) {
+            continue;
+        }
+        *flag = true;
+        warnings.push(
+            Warning::new(
+                warning::DEPENDENCY_INCLUDED,
+                Severity::Info,
+                format!("type {type_name} included as a dependency of {referrer}"),
+            )
+            .with_object(type_name.to_string()),
+        );
+        for pg_type in nested {
+            if let Some(nested) = user_type_name(pg_type) {
+                type_refs.push((nested, Some(type_name.clone()), type_name.to_string()));
+            }
+        }
+    }
+
+    for (j, referrer) in pulled_tables {
+        let table = &mut model.tables[j];
+        let note = format!("{} included as a dependency of {referrer}", table.name);
+        warnings.push(
+            Warning::new(
+                warning::DEPENDENCY_INCLUDED,
+                Severity::Info,
+                format!("table {note}"),
+            )
+            .with_object(table.name.to_string()),
+        );
+        table.note = Some(note);
+    }
+}
+
+/// The user-defined type a column type names, looking through arrays.
+fn user_type_name(pg_type: &PgType) -> Option<String> {
+    match pg_type {
+        PgType::Other { name } => Some(name.clone()),
+        PgType::Array { element } => user_type_name(element),
+        _ => None,
+    }
+}
+
+fn retain_selected<T>(items: &mut Vec<T>, selected: &[bool]) {
+    let mut selected = selected.iter();
+    items.retain(|_| *selected.next().unwrap());
+}
+
+fn same_name(a: &QualifiedName, b: &QualifiedName) -> bool {
+    a.name.normalized == b.name.normalized
+        && a.schema.as_ref().map(|s| &s.normalized) == b.schema.as_ref().map(|s| &s.normalized)
 }
 
 #[cfg(test)]
@@ -85,7 +358,7 @@ mod tests {
         let (mut model, _) = parser::parse(sql);
         assert_eq!(model.tables.len(), 2);
 
-        normalize(&mut model, &NormalizeOptions::default());
+        normalize(&mut model, &NormalizeOptions::default(), &mut Vec::new());
         assert_eq!(model.tables.len(), 1);
         assert_eq!(model.tables[0].name.name.normalized, "users");
     }
@@ -102,7 +375,9 @@ mod tests {
             &NormalizeOptions {
                 schema: None,
                 include_all_schemas: true,
+                ..Default::default()
             },
+            &mut Vec::new(),
         );
         assert_eq!(model.tables.len(), 2);
     }
@@ -111,7 +386,7 @@ mod tests {
     fn test_normalize_unqualified_passes() {
         let sql = "CREATE TABLE users (id INTEGER);";
         let (mut model, _) = parser::parse(sql);
-        normalize(&mut model, &NormalizeOptions::default());
+        normalize(&mut model, &NormalizeOptions::default(), &mut Vec::new());
         assert_eq!(model.tables.len(), 1);
     }
 
@@ -126,10 +401,119 @@ mod tests {
             &mut model,
             &NormalizeOptions {
                 schema: Some("myschema".to_string()),
-                include_all_schemas: false,
+                ..Default::default()
             },
+            &mut Vec::new(),
         );
         assert_eq!(model.tables.len(), 1);
         assert_eq!(model.tables[0].name.name.normalized, "users");
     }
+
+    #[test]
+    fn test_normalize_schema_lists() {
+        let sql = r#"
+            CREATE TABLE public.users (id INTEGER);
+            CREATE TABLE billing.invoices (
+                id INTEGER,
+                user_id INTEGER REFERENCES public.users(id),
+                session_id INTEGER REFERENCES auth.sessions(id)
+            );
+            CREATE TABLE auth.sessions (id INTEGER);
+            CREATE TABLE pg_temp.scratch (id INTEGER);
+            CREATE TABLE ledger (id INTEGER);
+            ALTER TABLE billing.invoices ADD CONSTRAINT invoices_session_fkey
+                FOREIGN KEY (session_id) REFERENCES auth.sessions(id);
+        "#;
+        let opts = NormalizeOptions {
+            include_schemas: vec!["public".into(), "billing".into()],
+            exclude_schemas: vec!["auth".into()],
+            ..Default::default()
+        };
+        let (mut model, _) = parser::parse(sql);
+        let mut warnings = Vec::new();
+        normalize(&mut model, &opts, &mut warnings);
+        let names: Vec<String> = model.tables.iter().map(|t| t.name.to_string()).collect();
+        assert_eq!(names, ["public.users", "billing.invoices", "ledger"]);
+        let invoices = &model.tables[1];
+        assert!(invoices.columns[1].references.is_some());
+        assert!(invoices.columns[2].references.is_none());
+        assert!(model.alter_constraints.is_empty());
+        assert_eq!(warnings.len(), 2);
+        assert!(
+            warnings
+                .iter()
+                .all(|w| w.code == warning::FILTER_DEPENDENT_DROPPED)
+        );
+        assert_eq!(warnings[0].object.as_deref(), Some("invoices.session_id"));
+        assert!(opts.multi_schema());
+
+        let all_but_auth = NormalizeOptions {
+            schema: None,
+            include_all_schemas: true,
+            exclude_schemas: vec!["auth".into(), "pg_*".into()],
+            ..Default::default()
+        };
+        let (mut model, _) = parser::parse(sql);
+        normalize(&mut model, &all_but_auth, &mut Vec::new());
+        assert_eq!(model.tables.len(), 3);
+    }
+
+    #[test]
+    fn test_normalize_include_dependencies() {
+        let sql = r#"
+            CREATE TYPE auth.role AS ENUM ('admin', 'member');
+            CREATE DOMAIN auth.email AS text;
+            CREATE TABLE auth.orgs (id INTEGER);
+            CREATE TABLE auth.users (
+                id INTEGER,
+                org_id INTEGER REFERENCES auth.orgs(id),
+                role auth.role,
+                email auth.email
+            );
+            CREATE TABLE auth.sessions (id INTEGER);
+            CREATE TABLE audit.log (id INTEGER);
+            CREATE TABLE public.posts (
+                id INTEGER,
+                author_id INTEGER REFERENCES auth.users(id),
+                log_id INTEGER REFERENCES audit.log(id)
+            );
+        "#;
+        let opts = NormalizeOptions {
+            exclude_schemas: vec!["audit".into()],
+            include_dependencies: true,
+            ..Default::default()
+        };
+        let (mut model, _) = parser::parse(sql);
+        let mut warnings = Vec::new();
+        normalize(&mut model, &opts, &mut warnings);
+        let names: Vec<String> = model.tables.iter().map(|t| t.name.to_string()).collect();
+        assert_eq!(names, ["auth.orgs", "auth.users", "public.posts"]);
+        assert_eq!(
+            model.tables[0].note.as_deref(),
+            Some("auth.orgs included as a dependency of auth.users")
+        );
+        assert_eq!(
+            model.tables[1].note.as_deref(),
+            Some("auth.users included as a dependency of public.posts")
+        );
+        assert!(model.tables[2].note.is_none());
+        assert_eq!(model.enums.len(), 1);
+        assert_eq!(model.domains.len(), 1);
+        assert!(model.tables[2].columns[2].references.is_none());
+
+        let included: Vec<&str> = warnings
+            .iter()
+            .filter(|w| w.code == warning::DEPENDENCY_INCLUDED)
+            .filter_map(|w| w.object.as_deref())
+            .collect();
+        assert_eq!(included.len(), 4);
+        for object in ["auth.orgs", "auth.users", "auth.role", "auth.email"] {
+            assert!(included.contains(&object), "{object}");
+        }
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.code == warning::FILTER_DEPENDENT_DROPPED)
+        );
+    }
 }
@@ -4,16 +4,21 @@
 use sqlparser::ast::{
     self, AlterColumnOperation, AlterTableOperation, Array, ArrayElemTypeDef, BinaryOperator,
     ColumnDef, ColumnOption, CreateIndex, DataType, Expr as SqlExpr, ObjectName, ObjectNamePart,
-    ReferentialAction, Statement, TableConstraint as SqlConstraint, UserDefinedTypeRepresentation,
-    ValueWithSpan,
+    ReferentialAction, SchemaName, Statement, TableConstraint as SqlConstraint,
+    UserDefinedTypeRepresentation, ValueWithSpan,
 };
 use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+use super::search_path::SearchPath;
 
 use crate::diagnostics::warning::{self, Severity, Warning};
 use crate::ir::{
-    AlterConstraint, AlterIdentity, Column, EnumDef, Expr, FkAction, ForeignKeyRef, Ident, Index,
-    IndexColumn, IndexMethod, PgType, QualifiedName, SchemaModel, Sequence, Table, TableConstraint,
+    AlterConstraint, AlterIdentity, CollationDef, Column, CompositeAttribute, CompositeDef,
+    DomainDef, EnumDef, Expr, FkAction, ForeignKeyRef, Ident, Index, IndexColumn, IndexMethod,
+    LikeKind, PgType, QualifiedName, SchemaModel, Sequence, Table, TableConstraint,
 };
 
 /// Strip the parenthesized sequence-options block from `AS IDENTITY (...)` statements.
@@ -54,6 +59,198 @@ fn strip_identity_options(input: &str) -> String {
     result
 }
 
+/// Remove CREATE COLLATION statements and return their definitions.
+///
+/// sqlparser cannot parse CREATE COLLATION, and a single unparseable
+/// statement would fail the whole dump, so they are lifted out beforehand.
+fn extract_collations(input: &str) -> (String, Vec<CollationDef>) {
+    let upper = input.to_ascii_uppercase();
+    let mut result = String::with_capacity(input.len());
+    let mut collations = Vec::new();
+    let mut pos = 0;
+
+    while let Some(idx) = upper[pos..].find("CREATE COLLATION") {
+        let start = pos + idx;
+        let end = find_statement_end(input, start);
+        result.push_str(&input[pos..start]);
+        if let Some(def) = parse_create_collation(&input[start..end]) {
+            collations.push(def);
+        }
+        pos = end;
+    }
+    result.push_str(&input[pos..]);
+
+    (result, collations)
+}
+
+/// Remove `NOT NULL` / `NULL` from CREATE DOMAIN statements and return the
+/// names of the NOT NULL domains.
+///
+/// sqlparser rejects a nullability clause on a domain, or reads it as part of
+/// the DEFAULT expression.
+fn strip_domain_nullability(input: &str) -> (String, Vec<QualifiedName>) {
+    let upper = input.to_ascii_uppercase();
+    let mut result = String::with_capacity(input.len());
+    let mut not_null = Vec::new();
+    let mut pos = 0;
+
+    while let Some(idx) = upper[pos..].find("CREATE DOMAIN") {
+        let start = pos + idx;
+        let end = find_statement_end(input, start);
+        result.push_str(&input[pos..start]);
+        let sql = &input[start..end];
+        match strip_nullability(sql) {
+            Some((stripped, is_not_null)) => {
+                if is_not_null && let Some(name) = domain_name(&stripped) {
+                    not_null.push(name);
+                }
+                result.push_str(&stripped);
+            }
+            None => result.push_str(sql),
+        }
+        pos = end;
+    }
+    result.push_str(&input[pos..]);
+
+    (result, not_null)
+}
+
+/// Drop top-level `NOT NULL` / `NULL` clauses from one statement, reporting
+/// whether a NOT NULL was present.
+fn strip_nullability(sql: &str) -> Option<(String, bool)> {
+    let dialect = PostgreSqlDialect {};
+    let tokens = Tokenizer::new(&dialect, sql).tokenize().ok()?;
+    let is_keyword = |t: &Token, k: Keyword| matches!(t, Token::Word(w) if w.keyword == k);
+    let significant: Vec<usize> = (0..tokens.len())
+        .filter(|&i| !matches!(tokens[i], Token::Whitespace(_)))
+        .collect();
+
+    let mut skip = std::collections::HashSet::new();
+    let mut not_null = false;
+    let mut depth = 0usize;
+    for (n, &i) in significant.iter().enumerate() {
+        match &tokens[i] {
+            Token::LParen => depth += 1,
+            Token::RParen => depth = depth.saturating_sub(1),
+            t if depth == 0 && is_keyword(t, Keyword::NULL) => {
+                let prev = n.checked_sub(1).map(|p| &tokens[significant[p]]);
+                let prev_prev = n.checked_sub(2).map(|p| &tokens[significant[p]]);
+                match prev {
+                    Some(t) if is_keyword(t, Keyword::NOT) => {
+                        if prev_prev.is_some_and(|t| is_keyword(t, Keyword::IS)) {
+                            continue;
+                        }
+                        skip.insert(significant[n - 1]);
+                        skip.insert(i);
+                        not_null = true;
+                    }
+                    Some(
+                        Token::Word(_)
+                        | Token::RParen
+                        | Token::SingleQuotedString(_)
+                        | Token::Number(..),
+                    ) if !prev.is_some_and(|t| {
+                        is_keyword(t, Keyword::DEFAULT) || is_keyword(t, Keyword::IS)
+                    }) =>
+                    {
+                        skip.insert(i);
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let stripped = tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !skip.contains(i))
+        .map(|(_, t)| t.to_string())
+        .collect();
+    Some((stripped, not_null))
+}
+
+/// The name of the domain created by a CREATE DOMAIN statement.
+fn domain_name(sql: &str) -> Option<QualifiedName> {
+    let dialect = PostgreSqlDialect {};
+    let mut parser = Parser::new(&dialect).try_with_sql(sql).ok()?;
+    if !parser.parse_keywords(&[Keyword::CREATE, Keyword::DOMAIN]) {
+        return None;
+    }
+    Some(convert_object_name(&parser.parse_object_name(false).ok()?))
+}
+
+/// Find the position just past the ';' ending the statement at `start`.
+fn find_statement_end(input: &str, start: usize) -> usize {
+    let bytes = input.as_bytes();
+    let mut quote = None;
+    for (i, &b) in bytes.iter().enumerate().skip(start) {
+        match (quote, b) {
+            (None, b'\'' | b'"') => quote = Some(b),
+            (Some(q), _) if b == q => quote = None,
+            (None, b';') => return i + 1,
+            _ => {}
+        }
+    }
+    input.len()
+}
+
+/// Parse `CREATE COLLATION name (provider = icu, locale = '…', deterministic = false)`
+/// or `CREATE COLLATION name FROM existing`.
+fn parse_create_collation(sql: &str) -> Option<CollationDef> {
+    let dialect = PostgreSqlDialect {};
+    let mut parser = Parser::new(&dialect).try_with_sql(sql).ok()?;
+    if !parser.parse_keywords(&[Keyword::CREATE, Keyword::COLLATION]) {
+        return None;
+    }
+    let _ = parser.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+    let name = convert_object_name(&parser.parse_object_name(false).ok()?);
+
+    if parser.parse_keyword(Keyword::FROM) {
+        let from = parser.parse_object_name(false).ok()?;
+        return Some(CollationDef {
+            name,
+            locale: Some(collation_name(&from)),
+            deterministic: true,
+        });
+    }
+
+    let mut locale = None;
+    let mut lc_collate = None;
+    let mut deterministic = true;
+    parser.expect_token(&Token::LParen).ok()?;
+    loop {
+        let key = parser.parse_identifier().ok()?.value.to_lowercase();
+        parser.expect_token(&Token::Eq).ok()?;
+        let value = match parser.next_token().token {
+            Token::SingleQuotedString(s) => s,
+            Token::Word(w) => w.value,
+            Token::Number(n, _) => n,
+            _ => return None,
+        };
+        match key.as_str() {
+            "locale" => locale = Some(value),
+            "lc_collate" => lc_collate = Some(value),
+            "deterministic" => {
+                deterministic =
+                    !matches!(value.to_lowercase().as_str(), "false" | "off" | "no" | "0");
+            }
+            _ => {}
+        }
+        if !parser.consume_token(&Token::Comma) {
+            break;
+        }
+    }
+    parser.expect_token(&Token::RParen).ok()?;
+
+    Some(CollationDef {
+        name,
+        locale: locale.or(lc_collate),
+        deterministic,
+    })
+}
+
 /// Find the position of the closing ')' matching the '(' at `start`.
 fn find_matching_paren(input: &str, start: usize) -> Option<usize> {
     let bytes = input.as_bytes();
@@ -84,7 +281,9 @@ pub fn parse(input: &str) -> (SchemaModel, Vec<Warning>) {
     let mut model = SchemaModel::default();
     let mut warnings = Vec::new();
 
-    let cleaned = strip_identity_options(input);
+    let (cleaned, collations) = extract_collations(&strip_identity_options(input));
+    model.collations = collations;
+    let (cleaned, not_null_domains) = strip_domain_nullability(&cleaned);
     let statements = match Parser::parse_sql(&dialect, &cleaned) {
         Ok(stmts) => stmts,
         Err(e) => {
@@ -97,29 +296,68 @@ pub fn parse(input: &str) -> (SchemaModel, Vec<Warning>) {
         }
     };
 
+    let mut search_path = SearchPath::default();
     for stmt in statements {
+        search_path.apply(&stmt);
+        let exists = |tables: &[Table], name: &QualifiedName| {
+            tables.iter().any(|t| same_name(&t.name, name))
+        };
         match stmt {
             Statement::CreateTable(ct) => {
-                if let Some(table) = parse_create_table(&ct, &mut warnings) {
+                if let Some(mut table) = parse_create_table(&ct, &mut warnings) {
+                    search_path.qualify_new(&mut table.name);
+                    let own_name = table.name.clone();
+                    let is_known = |name: &QualifiedName| {
+                        same_name(&own_name, name) || exists(&model.tables, name)
+                    };
+                    for col in &mut table.columns {
+                        if let Some(fk) = &mut col.references {
+                            search_path.qualify_ref(&mut fk.table, is_known);
+                        }
+                    }
+                    for constraint in &mut table.constraints {
+                        if let TableConstraint::ForeignKey { ref_table, .. } = constraint {
+                            search_path.qualify_ref(ref_table, is_known);
+                        }
+                    }
                     model.tables.push(table);
                 }
             }
             Statement::CreateIndex(ci) => {
-                if let Some(idx) = parse_create_index(&ci, &mut warnings) {
+                if let Some(mut idx) = parse_create_index(&ci, &mut warnings) {
+                    search_path.qualify_ref(&mut idx.table, |n| exists(&model.tables, n));
                     model.indexes.push(idx);
                 }
             }
             Statement::CreateSequence { name, .. } => {
+                let mut name = convert_object_name(&name);
+                search_path.qualify_new(&mut name);
                 model.sequences.push(Sequence {
-                    name: convert_object_name(&name),
+                    name,
                     owned_by: None,
                 });
             }
+            Statement::CreateSchema { schema_name, .. } => {
+                let name = match &schema_name {
+                    SchemaName::Simple(name) | SchemaName::NamedAuthorization(name, _) => {
+                        convert_object_name(name).name
+                    }
+                    SchemaName::UnnamedAuthorization(role) => convert_ident(role),
+                };
+                model.schemas.push(name);
+            }
             Statement::AlterTable(alter_table) => {
-                let table_name = convert_object_name(&alter_table.name);
+                let mut table_name = convert_object_name(&alter_table.name);
+                search_path.qualify_ref(&mut table_name, |n| exists(&model.tables, n));
                 for op in &alter_table.operations {
                     match parse_alter_table_op(&table_name, op, &mut warnings) {
-                        AlterResult::Constraint(c) => model.alter_constraints.push(c),
+                        AlterResult::Constraint(mut c) => {
+                            if let TableConstraint::ForeignKey { ref_table, .. } = &mut c.constraint
+                            {
+                                search_path.qualify_ref(ref_table, |n| exists(&model.tables, n));
+                            }
+                            model.alter_constraints.push(c);
+                        }
                         AlterResult::Identity(id) => model.identity_columns.push(id),
                         AlterResult::None => {}
                     }
@@ -130,12 +368,52 @@ pub fn parse(input: &str) -> (SchemaModel, Vec<Warning>) {
                 representation: Some(UserDefinedTypeRepresentation::Enum { labels }),
                 ..
             } => {
-                let values: Vec<String> = labels.into_iter().map(|v| v.to_string()).collect();
-                model.enums.push(EnumDef {
-                    name: convert_object_name(&name),
-                    values,
+                let values: Vec<String> = labels.into_iter().map(|v| v.value).collect();
+                let mut name = convert_object_name(&name);
+                search_path.qualify_new(&mut name);
+                model.enums.push(EnumDef { name, values });
+            }
+            Statement::CreateType {
+                name,
+                representation: Some(UserDefinedTypeRepresentation::Composite { attributes }),
+            } => {
+                let mut name = convert_object_name(&name);
+                search_path.qualify_new(&mut name);
+                model.composites.push(CompositeDef {
+                    name,
+                    attributes: attributes
+                        .iter()
+                        .map(|attr| CompositeAttribute {
+                            name: convert_ident(&attr.name),
+                            pg_type: convert_data_type(&attr.data_type),
+                        })
+                        .collect(),
+                });
+            }
+            Statement::CreateDomain(cd) => {
+                let mut name = convert_object_name(&cd.name);
+                let not_null = not_null_domains.contains(&name);
+                search_path.qualify_new(&mut name);
+                let check = cd
+                    .constraints
+                    .iter()
+                    .filter_map(|c| match c {
+                        SqlConstraint::Check(ck) => Some(convert_sql_expr(&ck.expr)),
+                        _ => None,
+                    })
+                    .reduce(Expr::and);
+                model.domains.push(DomainDef {
+                    not_null,
+                    name,
+                    base_type: convert_data_type(&cd.data_type),
+                    collation: cd.collation.as_ref().map(|c| c.value.clone()),
+                    default: cd.default.as_ref().map(convert_sql_expr),
+                    check,
                 });
             }
+            Statement::CreateExtension(ext) => {
+                model.extensions.push(ext.name.value.to_lowercase());
+            }
             // Skip non-DDL statements silently
             _ => {}
         }
@@ -163,11 +441,13 @@ fn parse_create_table(ct: &ast::CreateTable, warnings: &mut [Warning]) -> Option
         name,
         columns,
         constraints,
+        without_rowid: false,
+        note: None,
     })
 }
 
 fn parse_column(col_def: &ColumnDef) -> Column {
-    let name = Ident::new(&col_def.name.value);
+    let name = convert_ident(&col_def.name);
     let pg_type = convert_data_type(&col_def.data_type);
     let mut not_null = false;
     let mut default = None;
@@ -175,6 +455,7 @@ fn parse_column(col_def: &ColumnDef) -> Column {
     let mut is_unique = false;
     let mut references = None;
     let mut check = None;
+    let mut collation = None;
 
     for opt in &col_def.options {
         match &opt.option {
@@ -190,7 +471,7 @@ fn parse_column(col_def: &ColumnDef) -> Column {
                 is_unique = true;
             }
             ColumnOption::ForeignKey(fk) => {
-                let ref_col = fk.referred_columns.first().map(|c| Ident::new(&c.value));
+                let ref_col = fk.referred_columns.first().map(convert_ident);
                 references = Some(ForeignKeyRef {
                     table: convert_object_name(&fk.foreign_table),
                     column: ref_col,
@@ -201,6 +482,9 @@ fn parse_column(col_def: &ColumnDef) -> Column {
             ColumnOption::Check(ck) => {
                 check = Some(convert_sql_expr(&ck.expr));
             }
+            ColumnOption::Collation(name) => {
+                collation = Some(collation_name(name));
+            }
             _ => {}
         }
     }
@@ -216,6 +500,7 @@ fn parse_column(col_def: &ColumnDef) -> Column {
         autoincrement: false,
         references,
         check,
+        collation,
     }
 }
 
@@ -228,10 +513,10 @@ fn parse_table_constraint(
             let cols: Vec<Ident> = pk
                 .columns
                 .iter()
-                .map(|c| Ident::new(&c.column.expr.to_string()))
+                .map(|c| index_expr_ident(&c.column.expr))
                 .collect();
             Some(TableConstraint::PrimaryKey {
-                name: pk.name.as_ref().map(|n| Ident::new(&n.value)),
+                name: pk.name.as_ref().map(convert_ident),
                 columns: cols,
             })
         }
@@ -239,28 +524,24 @@ fn parse_table_constraint(
             let cols: Vec<Ident> = uq
                 .columns
                 .iter()
-                .map(|c| Ident::new(&c.column.expr.to_string()))
+                .map(|c| index_expr_ident(&c.column.expr))
                 .collect();
             Some(TableConstraint::Unique {
-                name: uq.name.as_ref().map(|n| Ident::new(&n.value)),
+                name: uq.name.as_ref().map(convert_ident),
                 columns: cols,
             })
         }
         SqlConstraint::ForeignKey(fk) => Some(TableConstraint::ForeignKey {
-            name: fk.name.as_ref().map(|n| Ident::new(&n.value)),
-            columns: fk.columns.iter().map(|c| Ident::new(&c.value)).collect(),
+            name: fk.name.as_ref().map(convert_ident),
+            columns: fk.columns.iter().map(convert_ident).collect(),
             ref_table: convert_object_name(&fk.foreign_table),
-            ref_columns: fk
-                .referred_columns
-                .iter()
-                .map(|c| Ident::new(&c.value))
-                .collect(),
+            ref_columns: fk.referred_columns.iter().map(convert_ident).collect(),
             on_delete: fk.on_delete.as_ref().and_then(convert_referential_action),
             on_update: fk.on_update.as_ref().and_then(convert_referential_action),
             deferrable: false,
         }),
         SqlConstraint::Check(ck) => Some(TableConstraint::Check {
-            name: ck.name.as_ref().map(|n| Ident::new(&n.value)),
+            name: ck.name.as_ref().map(convert_ident),
             expr: convert_sql_expr(&ck.expr),
         }),
         _ => None,
@@ -269,17 +550,16 @@ fn parse_table_constraint(
 
 fn parse_create_index(ci: &CreateIndex, _warnings: &mut [Warning]) -> Option<Index> {
     let index_name = ci.name.as_ref()?;
-    let name = Ident::new(&index_name.to_string());
+    let name = convert_object_name(index_name).name;
     let table = convert_object_name(&ci.table_name);
 
     let mut columns = Vec::new();
     for col in &ci.columns {
-        let col_name = col.column.expr.to_string();
-        // Check if this looks like a function call / expression
-        if col_name.contains('(') {
-            columns.push(IndexColumn::Expression(Expr::Raw(col_name)));
-        } else {
-            columns.push(IndexColumn::Column(Ident::new(&col_name)));
+        match &col.column.expr {
+            SqlExpr::Identifier(ident) => {
+                columns.push(IndexColumn::Column(convert_ident(ident)));
+            }
+            expr => columns.push(IndexColumn::Expression(convert_sql_expr(expr))),
         }
     }
 
@@ -332,7 +612,7 @@ fn parse_alter_table_op(
             ..
         } => AlterResult::Identity(AlterIdentity {
             table: table.clone(),
-            column: Ident::new(&column_name.value),
+            column: convert_ident(column_name),
         }),
         _ => AlterResult::None,
     }
@@ -340,25 +620,53 @@ fn parse_alter_table_op(
 
 /// Convert sqlparser ObjectName to our QualifiedName.
 fn convert_object_name(name: &ObjectName) -> QualifiedName {
-    let parts: Vec<&str> = name
+    let mut parts: Vec<Ident> = name
         .0
         .iter()
         .filter_map(|part| match part {
-            ObjectNamePart::Identifier(ident) => Some(ident.value.as_str()),
+            ObjectNamePart::Identifier(ident) => Some(convert_ident(ident)),
             _ => None,
         })
         .collect();
-    match parts.len() {
-        1 => QualifiedName::new(Ident::new(parts[0])),
-        2 => QualifiedName::with_schema(Ident::new(parts[0]), Ident::new(parts[1])),
-        _ => {
-            // Take the last two parts as schema.table
-            let len = parts.len();
-            QualifiedName::with_schema(Ident::new(parts[len - 2]), Ident::new(parts[len - 1]))
+    // Take the last two parts as schema.table
+    let name = parts.pop().unwrap_or_else(|| Ident::new(""));
+    match parts.pop() {
+        Some(schema) => QualifiedName::with_schema(schema, name),
+        None => QualifiedName::new(name),
+    }
+}
+
+/// Whether two names denote the same object, schemas compared when both are qualified.
+fn same_name(a: &QualifiedName, b: &QualifiedName) -> bool {
+    a.name.normalized == b.name.normalized
+        && match (&a.schema, &b.schema) {
+            (Some(x), Some(y)) => x.normalized == y.normalized,
+            _ => true,
         }
+}
+
+/// Convert a sqlparser identifier, folding it to lowercase unless it was quoted.
+fn convert_ident(ident: &ast::Ident) -> Ident {
+    if ident.quote_style.is_some() {
+        Ident::quoted(&ident.value)
+    } else {
+        Ident::new(&ident.value)
+    }
+}
+
+/// Column named by a key or index element, or its SQL text for expressions.
+fn index_expr_ident(expr: &SqlExpr) -> Ident {
+    match expr {
+        SqlExpr::Identifier(ident) => convert_ident(ident),
+        expr => Ident::new(&expr.to_string()),
     }
 }
 
+/// Unqualified, unquoted collation name (`pg_catalog."C"` → `C`).
+fn collation_name(name: &ObjectName) -> String {
+    convert_object_name(name).name.raw
+}
+
 /// Convert sqlparser DataType to our PgType.
 fn convert_data_type(dt: &DataType) -> PgType {
     match dt {
@@ -383,10 +691,10 @@ fn convert_data_type(dt: &DataType) -> PgType {
         },
         DataType::Date => PgType::Date,
         DataType::Time(_, tz) => PgType::Time {
-            with_tz: matches!(tz, ast::TimezoneInfo::WithTimeZone),
+            with_tz: matches!(tz, ast::TimezoneInfo::WithTimeZone | ast::TimezoneInfo::Tz),
         },
         DataType::Timestamp(_, tz) => PgType::Timestamp {
-            with_tz: matches!(tz, ast::TimezoneInfo::WithTimeZone),
+            with_tz: matches!(tz, ast::TimezoneInfo::WithTimeZone | ast::TimezoneInfo::Tz),
         },
         DataType::Interval { .. } => PgType::Interval,
         DataType::Bytea => PgType::Bytea,
@@ -402,13 +710,13 @@ fn convert_data_type(dt: &DataType) -> PgType {
         DataType::Array(_) => PgType::Other {
             name: dt.to_string(),
         },
-        DataType::Custom(name, _) => {
+        DataType::Custom(name, modifiers) => {
             // Use the last part of the name to handle schema-qualified types (e.g., pg_catalog.serial)
             let type_name = name
                 .0
                 .iter()
                 .filter_map(|part| match part {
-                    ObjectNamePart::Identifier(ident) => Some(ident.value.to_lowercase()),
+                    ObjectNamePart::Identifier(ident) => Some(convert_ident(ident).normalized),
                     _ => None,
                 })
                 .next_back()
@@ -435,6 +743,14 @@ fn convert_data_type(dt: &DataType) -> PgType {
                 "tsrange" => PgType::TsRange,
                 "tstzrange" => PgType::TsTzRange,
                 "daterange" => PgType::DateRange,
+                "citext" => PgType::Citext,
+                "hstore" => PgType::Hstore,
+                "ltree" => PgType::Ltree,
+                "geometry" | "geography" => PgType::Geometry {
+                    geography: type_name == "geography",
+                    subtype: modifiers.first().cloned(),
+                    srid: modifiers.get(1).and_then(|m| m.parse().ok()),
+                },
                 _ => PgType::Other { name: type_name },
             }
         }
@@ -444,13 +760,24 @@ fn convert_data_type(dt: &DataType) -> PgType {
     }
 }
 
+/// Parse a type name as written in a cast, e.g. `numeric(10,2)` or `public.status`.
+pub fn parse_type_name(name: &str) -> PgType {
+    Parser::new(&PostgreSqlDialect {})
+        .try_with_sql(name)
+        .and_then(|mut parser| parser.parse_data_type())
+        .map(|data_type| convert_data_type(&data_type))
+        .unwrap_or_else(|_| PgType::Other {
+            name: name.to_string(),
+        })
+}
+
 /// Convert sqlparser Expr to our Expr.
 fn convert_sql_expr(expr: &SqlExpr) -> Expr {
     match expr {
         SqlExpr::Value(val) => convert_value_with_span(val),
-        SqlExpr::Identifier(ident) => Expr::ColumnRef(ident.value.clone()),
+        SqlExpr::Identifier(ident) => Expr::ColumnRef(convert_ident(ident).normalized),
         SqlExpr::CompoundIdentifier(idents) => {
-            let name: Vec<&str> = idents.iter().map(|i| i.value.as_str()).collect();
+            let name: Vec<String> = idents.iter().map(|i| convert_ident(i).normalized).collect();
             Expr::ColumnRef(name.join("."))
         }
         SqlExpr::Function(func) => {
@@ -496,6 +823,16 @@ fn convert_sql_expr(expr: &SqlExpr) -> Expr {
             op: op.to_string(),
             expr: Box::new(convert_sql_expr(expr)),
         },
+        SqlExpr::IsDistinctFrom(left, right) => Expr::binary(
+            convert_sql_expr(left),
+            "IS DISTINCT FROM",
+            convert_sql_expr(right),
+        ),
+        SqlExpr::IsNotDistinctFrom(left, right) => Expr::binary(
+            convert_sql_expr(left),
+            "IS NOT DISTINCT FROM",
+            convert_sql_expr(right),
+        ),
         SqlExpr::IsNull(expr) => Expr::IsNull {
             expr: Box::new(convert_sql_expr(expr)),
             negated: false,
@@ -524,7 +861,121 @@ fn convert_sql_expr(expr: &SqlExpr) -> Expr {
             high: Box::new(convert_sql_expr(high)),
             negated: *negated,
         },
+        SqlExpr::Like {
+            negated,
+            any: false,
+            expr: inner,
+            pattern,
+            escape_char,
+        } => convert_like(inner, pattern, LikeKind::Like, *negated, escape_char)
+            .unwrap_or_else(|| Expr::Raw(expr.to_string())),
+        SqlExpr::ILike {
+            negated,
+            any: false,
+            expr: inner,
+            pattern,
+            escape_char,
+        } => convert_like(inner, pattern, LikeKind::ILike, *negated, escape_char)
+            .unwrap_or_else(|| Expr::Raw(expr.to_string())),
+        SqlExpr::SimilarTo {
+            negated,
+            expr: inner,
+            pattern,
+            escape_char,
+        } => convert_like(inner, pattern, LikeKind::SimilarTo, *negated, escape_char)
+            .unwrap_or_else(|| Expr::Raw(expr.to_string())),
+        SqlExpr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => Expr::Case {
+            operand: operand.as_ref().map(|e| Box::new(convert_sql_expr(e))),
+            branches: conditions
+                .iter()
+                .map(|w| (convert_sql_expr(&w.condition), convert_sql_expr(&w.result)))
+                .collect(),
+            else_result: else_result.as_ref().map(|e| Box::new(convert_sql_expr(e))),
+        },
+        SqlExpr::Extract { field, expr, .. } => Expr::Extract {
+            field: field.to_string().to_lowercase(),
+            expr: Box::new(convert_sql_expr(expr)),
+        },
+        SqlExpr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+            ..
+        } => Expr::Substring {
+            expr: Box::new(convert_sql_expr(expr)),
+            from: substring_from
+                .as_ref()
+                .map(|e| Box::new(convert_sql_expr(e))),
+            length: substring_for
+                .as_ref()
+                .map(|e| Box::new(convert_sql_expr(e))),
+        },
+        SqlExpr::Position { expr, r#in } => Expr::Position {
+            needle: Box::new(convert_sql_expr(expr)),
+            haystack: Box::new(convert_sql_expr(r#in)),
+        },
+        SqlExpr::AtTimeZone {
+            timestamp,
+            time_zone,
+        } => Expr::AtTimeZone {
+            expr: Box::new(convert_sql_expr(timestamp)),
+            zone: Box::new(convert_sql_expr(time_zone)),
+        },
+        // DATE '2020-01-01', TIMESTAMP '…'
+        SqlExpr::TypedString(typed) => match &typed.value.value {
+            ast::Value::SingleQuotedString(value) => Expr::TypedLiteral {
+                type_name: typed.data_type.to_string(),
+                value: value.clone(),
+            },
+            _ => Expr::Raw(expr.to_string()),
+        },
         SqlExpr::Nested(inner) => Expr::Nested(Box::new(convert_sql_expr(inner))),
+        // ('a', 'b') → row('a', 'b')
+        SqlExpr::Tuple(items) => Expr::FunctionCall {
+            name: "row".to_string(),
+            args: items.iter().map(convert_sql_expr).collect(),
+        },
+        // (address).zip → FieldAccess
+        SqlExpr::CompoundFieldAccess { root, access_chain } => {
+            let mut converted = convert_sql_expr(root);
+            for access in access_chain {
+                match access {
+                    ast::AccessExpr::Dot(SqlExpr::Identifier(field)) => {
+                        converted = Expr::FieldAccess {
+                            expr: Box::new(converted),
+                            field: convert_ident(field).normalized,
+                        };
+                    }
+                    _ => return Expr::Raw(expr.to_string()),
+                }
+            }
+            converted
+        }
+        SqlExpr::Collate { expr, collation } => Expr::Collate {
+            expr: Box::new(convert_sql_expr(expr)),
+            collation: collation_name(collation),
+        },
+        // INTERVAL '1 day' / INTERVAL '1' DAY → INTERVAL '1 day'
+        SqlExpr::Interval(interval) if interval.last_field.is_none() => {
+            match convert_sql_expr(&interval.value) {
+                Expr::StringLiteral(text) => {
+                    let value = match &interval.leading_field {
+                        Some(field) => format!("{text} {}", field.to_string().to_lowercase()),
+                        None => text,
+                    };
+                    Expr::TypedLiteral {
+                        type_name: "INTERVAL".to_string(),
+                        value,
+                    }
+                }
+                _ => Expr::Raw(expr.to_string()),
+            }
+        }
         // col = ANY(ARRAY['a', 'b']) → col IN ('a', 'b')
         // Only convert when the right-hand side is an ARRAY literal.
         // Non-array forms (e.g., subqueries) fall through to Raw to avoid
@@ -556,6 +1007,29 @@ fn convert_sql_expr(expr: &SqlExpr) -> Expr {
     }
 }
 
+/// Convert a LIKE-style match. Returns `None` for an escape that is not a
+/// string literal.
+fn convert_like(
+    expr: &SqlExpr,
+    pattern: &SqlExpr,
+    kind: LikeKind,
+    negated: bool,
+    escape_char: &Option<ast::Value>,
+) -> Option<Expr> {
+    let escape = match escape_char {
+        None => None,
+        Some(ast::Value::SingleQuotedString(escape)) => Some(escape.clone()),
+        Some(_) => return None,
+    };
+    Some(Expr::Like {
+        expr: Box::new(convert_sql_expr(expr)),
+        pattern: Box::new(convert_sql_expr(pattern)),
+        kind,
+        negated,
+        escape,
+    })
+}
+
 /// Extract elements from an ARRAY literal expression.
 /// Returns `None` for non-array expressions (e.g., subqueries) so callers
 /// can fall back to `Expr::Raw` instead of producing incorrect results.
@@ -616,6 +1090,8 @@ fn extract_char_length(len: &Option<ast::CharacterLength>) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sqlparser::dialect::{Dialect, Precedence};
+    use sqlparser::parser::ParserError;
 
     #[test]
     fn test_parse_simple_table() {
@@ -639,6 +1115,34 @@ mod tests {
         assert_eq!(table.name.name.normalized, "users");
     }
 
+    #[test]
+    fn test_parse_quoted_identifiers_keep_case() {
+        let sql = r#"
+            CREATE TABLE app."UserProfile" (
+                "userId" INTEGER,
+                DisplayName TEXT CHECK (length("DisplayName") > 0 AND DisplayName <> ''),
+                PRIMARY KEY ("userId")
+            );
+            CREATE INDEX "Idx_Profile" ON app."UserProfile" ("userId");
+        "#;
+        let (model, _) = parse(sql);
+        let table = &model.tables[0];
+        assert_eq!(table.name.schema.as_ref().unwrap().normalized, "app");
+        assert_eq!(table.name.name.normalized, "UserProfile");
+        assert_eq!(table.columns[0].name.normalized, "userId");
+        assert_eq!(table.columns[1].name.normalized, "displayname");
+        assert_eq!(
+            table.columns[1].check.as_ref().unwrap().to_sql(),
+            "length(\"DisplayName\") > 0 AND displayname <> ''"
+        );
+        assert!(matches!(
+            &table.constraints[0],
+            TableConstraint::PrimaryKey { columns, .. } if columns[0].normalized == "userId"
+        ));
+        assert_eq!(model.indexes[0].name.normalized, "Idx_Profile");
+        assert_eq!(model.indexes[0].table.name.normalized, "UserProfile");
+    }
+
     #[test]
     fn test_parse_create_index() {
         let sql = "CREATE INDEX idx_name ON users (name);";
@@ -655,6 +1159,180 @@ mod tests {
         assert!(model.indexes[0].unique);
     }
 
+    #[test]
+    fn test_parse_extension_types() {
+        let sql = r#"
+            CREATE EXTENSION IF NOT EXISTS citext WITH SCHEMA public;
+            CREATE EXTENSION postgis;
+            CREATE TABLE places (
+                name CITEXT,
+                tags HSTORE,
+                path LTREE,
+                geom geometry(Point,4326),
+                area geography
+            );
+        "#;
+        let (model, _) = parse(sql);
+        assert_eq!(model.extensions, vec!["citext", "postgis"]);
+        let types: Vec<&PgType> = model.tables[0].columns.iter().map(|c| &c.pg_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                &PgType::Citext,
+                &PgType::Hstore,
+                &PgType::Ltree,
+                &PgType::Geometry {
+                    geography: false,
+                    subtype: Some("Point".to_string()),
+                    srid: Some(4326),
+                },
+                &PgType::Geometry {
+                    geography: true,
+                    subtype: None,
+                    srid: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_interval_literal() {
+        let sql = "CREATE TABLE t (a TIMESTAMP DEFAULT now() + INTERVAL '1 day', b INTERVAL DEFAULT INTERVAL '2' HOUR);";
+        let (model, _) = parse(sql);
+        let cols = &model.tables[0].columns;
+        assert_eq!(
+            cols[0].default,
+            Some(Expr::binary(
+                Expr::func("now", vec![]),
+                "+",
+                Expr::TypedLiteral {
+                    type_name: "INTERVAL".to_string(),
+                    value: "1 day".to_string(),
+                },
+            ))
+        );
+        assert_eq!(
+            cols[1].default,
+            Some(Expr::TypedLiteral {
+                type_name: "INTERVAL".to_string(),
+                value: "2 hour".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_structured_expressions() {
+        let sql = r#"CREATE TABLE t (
+            a TEXT CHECK (a NOT ILIKE 'x\_%' ESCAPE '\'),
+            b TEXT CHECK (CASE WHEN b = 'x' THEN 1 ELSE 0 END = 1),
+            c DATE DEFAULT DATE '2020-01-01' CHECK (EXTRACT(YEAR FROM c) > 2000),
+            d TEXT CHECK (SUBSTRING(d FROM 2 FOR 3) <> '' AND POSITION('@' IN d) > 0),
+            e TIMESTAMP CHECK (e AT TIME ZONE 'UTC' > '2020-01-01'),
+            f INT CHECK ((f IS DISTINCT FROM 0) AND f IS NOT DISTINCT FROM 1)
+        );"#;
+        let (model, _) = parse(sql);
+        let cols = &model.tables[0].columns;
+        let checks: Vec<String> = cols
+            .iter()
+            .map(|c| c.check.as_ref().unwrap().to_sql())
+            .collect();
+        assert_eq!(
+            checks,
+            [
+                r"a NOT ILIKE 'x\_%' ESCAPE '\'",
+                "CASE WHEN b = 'x' THEN 1 ELSE 0 END = 1",
+                "EXTRACT(YEAR FROM c) > 2000",
+                "SUBSTRING(d FROM 2 FOR 3) <> '' AND POSITION('@' IN d) > 0",
+                "e AT TIME ZONE 'UTC' > '2020-01-01'",
+                "(f IS DISTINCT FROM 0) AND f IS NOT DISTINCT FROM 1",
+            ]
+        );
+        assert!(matches!(
+            cols[0].check,
+            Some(Expr::Like {
+                kind: LikeKind::ILike,
+                negated: true,
+                ..
+            })
+        ));
+        assert_eq!(
+            cols[2].default,
+            Some(Expr::TypedLiteral {
+                type_name: "DATE".to_string(),
+                value: "2020-01-01".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_expression_index() {
+        let sql = "CREATE INDEX idx_lower_email ON users (lower(email), id);";
+        let (model, _) = parse(sql);
+        let columns = &model.indexes[0].columns;
+        assert!(matches!(
+            &columns[0],
+            IndexColumn::Expression(Expr::FunctionCall { name, args })
+                if name == "lower" && args == &vec![Expr::ColumnRef("email".to_string())]
+        ));
+        assert!(matches!(&columns[1], IndexColumn::Column(c) if c.normalized == "id"));
+    }
+
+    #[test]
+    fn test_parse_composite_type() {
+        let sql = r#"
+            CREATE TYPE public.address AS (street text, zip varchar(10));
+            CREATE TABLE shops (addr address DEFAULT ('a', 'b'), CHECK ((addr).zip <> ''));
+        "#;
+        let (model, _) = parse(sql);
+        assert_eq!(model.composites.len(), 1);
+        let attrs = &model.composites[0].attributes;
+        assert_eq!(attrs[0].name.normalized, "street");
+        assert_eq!(attrs[1].pg_type, PgType::Varchar { length: Some(10) });
+        assert!(matches!(
+            &model.tables[0].columns[0].default,
+            Some(Expr::FunctionCall { name, args }) if name == "row" && args.len() == 2
+        ));
+        let TableConstraint::Check { expr, .. } = &model.tables[0].constraints[0] else {
+            panic!("expected CHECK");
+        };
+        assert!(matches!(
+            expr,
+            Expr::BinaryOp { left, .. } if matches!(&**left, Expr::FieldAccess { field, .. } if field == "zip")
+        ));
+    }
+
+    #[test]
+    fn test_parse_collations() {
+        let sql = r#"
+            CREATE COLLATION public.case_insensitive (provider = icu, deterministic = false, locale = 'und-u-ks-level2');
+            CREATE COLLATION IF NOT EXISTS bytes FROM "C";
+            CREATE TABLE users (
+                email text COLLATE public.case_insensitive NOT NULL,
+                code char(2) COLLATE "C"
+            );
+            CREATE INDEX idx_email ON users (email COLLATE "default");
+        "#;
+        let (model, warnings) = parse(sql);
+        assert!(warnings.is_empty());
+        assert_eq!(model.collations.len(), 2);
+        assert_eq!(model.collations[0].name.name.normalized, "case_insensitive");
+        assert_eq!(
+            model.collations[0].locale.as_deref(),
+            Some("und-u-ks-level2")
+        );
+        assert!(!model.collations[0].deterministic);
+        assert_eq!(model.collations[1].locale.as_deref(), Some("C"));
+
+        let cols = &model.tables[0].columns;
+        assert_eq!(cols[0].collation.as_deref(), Some("case_insensitive"));
+        assert!(cols[0].not_null);
+        assert_eq!(cols[1].collation.as_deref(), Some("C"));
+        assert!(matches!(
+            &model.indexes[0].columns[0],
+            IndexColumn::Expression(Expr::Collate { collation, .. }) if collation == "default"
+        ));
+    }
+
     #[test]
     fn test_parse_alter_table_add_constraint() {
         let sql = r#"
@@ -671,7 +1349,7 @@ mod tests {
         let sql = "CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy');";
         let (model, _) = parse(sql);
         assert_eq!(model.enums.len(), 1);
-        assert_eq!(model.enums[0].values.len(), 3);
+        assert_eq!(model.enums[0].values, ["sad", "ok", "happy"]);
     }
 
     #[test]
@@ -682,6 +1360,46 @@ mod tests {
         assert!(col.default.is_some());
     }
 
+    #[test]
+    fn test_parse_time_zone_types() {
+        let sql =
+            "CREATE TABLE t (a TIMESTAMPTZ, b timestamp with time zone, c TIMETZ, d TIMESTAMP);";
+        let (model, _) = parse(sql);
+        let types: Vec<&PgType> = model.tables[0].columns.iter().map(|c| &c.pg_type).collect();
+        assert_eq!(
+            types,
+            [
+                &PgType::Timestamp { with_tz: true },
+                &PgType::Timestamp { with_tz: true },
+                &PgType::Time { with_tz: true },
+                &PgType::Timestamp { with_tz: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_domains() {
+        let sql = r#"
+            CREATE DOMAIN public.email AS varchar(255) NOT NULL CHECK (VALUE LIKE '%@%');
+            CREATE DOMAIN score AS integer DEFAULT 0 NULL CONSTRAINT score_check CHECK (VALUE IS NOT NULL);
+            CREATE TABLE t (e email, s score);
+        "#;
+        let (model, warnings) = parse(sql);
+        assert!(warnings.is_empty());
+        assert_eq!(model.tables.len(), 1);
+
+        let email = &model.domains[0];
+        assert_eq!(email.name.name.normalized, "email");
+        assert_eq!(email.base_type, PgType::Varchar { length: Some(255) });
+        assert!(email.not_null);
+        assert!(email.check.is_some());
+
+        let score = &model.domains[1];
+        assert!(!score.not_null);
+        assert!(matches!(score.default, Some(Expr::IntegerLiteral(0))));
+        assert!(score.check.is_some());
+    }
+
     #[test]
     fn test_non_ddl_ignored() {
         let sql = "SELECT 1; CREATE TABLE t (id INTEGER);";
@@ -795,4 +1513,232 @@ mod tests {
         assert_eq!(model.identity_columns[0].table.name.normalized, "seed");
         assert_eq!(model.identity_columns[0].column.normalized, "id");
     }
+
+    /// PostgreSQL syntax with SQLite's operator precedence, to read rendered
+    /// expressions back the way SQLite would.
+    #[derive(Debug)]
+    struct SqlitePrecedence;
+
+    impl Dialect for SqlitePrecedence {
+        fn is_identifier_start(&self, ch: char) -> bool {
+            PostgreSqlDialect {}.is_identifier_start(ch)
+        }
+
+        fn is_identifier_part(&self, ch: char) -> bool {
+            PostgreSqlDialect {}.is_identifier_part(ch)
+        }
+
+        fn get_next_precedence(&self, parser: &Parser) -> Option<Result<u8, ParserError>> {
+            match parser.peek_token().token {
+                Token::StringConcat => Some(Ok(90)),
+                Token::Lt | Token::LtEq | Token::Gt | Token::GtEq => Some(Ok(50)),
+                _ => None,
+            }
+        }
+
+        fn prec_value(&self, prec: Precedence) -> u8 {
+            match prec {
+                Precedence::Period => 200,
+                Precedence::DoubleColon => 140,
+                Precedence::AtTz => 120,
+                Precedence::MulDivModOp => 80,
+                Precedence::PlusMinus => 70,
+                Precedence::Xor
+                | Precedence::Ampersand
+                | Precedence::Caret
+                | Precedence::Pipe
+                | Precedence::Colon => 60,
+                Precedence::Between
+                | Precedence::Eq
+                | Precedence::Like
+                | Precedence::Is
+                | Precedence::PgOther => 40,
+                Precedence::UnaryNot => 30,
+                Precedence::And => 20,
+                Precedence::Or => 10,
+            }
+        }
+    }
+
+    /// Parse with SQLite precedence, dropping the parentheses from the tree.
+    fn reparse(sql: &str) -> Expr {
+        let parsed = Parser::new(&SqlitePrecedence)
+            .try_with_sql(sql)
+            .and_then(|mut p| p.parse_expr())
+            .unwrap_or_else(|e| panic!("{sql}: {e}"));
+        let mut expr = convert_sql_expr(&parsed);
+        // sqlparser attaches COLLATE to the innermost operand, where SQLite's
+        // prefix operators bind tighter: - x COLLATE c is (- x) COLLATE c
+        let mut hoisted = true;
+        while hoisted {
+            hoisted = false;
+            expr.walk_mut(&mut |node| {
+                let Expr::UnaryOp { op, expr: operand } = node else {
+                    return;
+                };
+                let Expr::Collate {
+                    expr: inner,
+                    collation,
+                } = operand.as_mut()
+                else {
+                    return;
+                };
+                if !matches!(op.as_str(), "-" | "+" | "~") {
+                    return;
+                }
+                let unary = Expr::UnaryOp {
+                    op: op.clone(),
+                    expr: Box::new(std::mem::replace(inner.as_mut(), Expr::Null)),
+                };
+                *node = Expr::Collate {
+                    expr: Box::new(unary),
+                    collation: collation.clone(),
+                };
+                hoisted = true;
+            });
+        }
+        expr.walk_mut(&mut |node| {
+            while let Expr::Nested(inner) = node {
+                *node = std::mem::replace(&mut **inner, Expr::Null);
+            }
+        });
+        expr
+    }
+
+    #[test]
+    fn test_render_round_trips_precedence() {
+        let col = |name: &str| Expr::ColumnRef(name.to_string());
+        let unary = |op: &str, expr| Expr::UnaryOp {
+            op: op.to_string(),
+            expr: Box::new(expr),
+        };
+        let cases = [
+            (
+                Expr::binary(Expr::binary(col("a"), "||", col("b")), "=", col("c")),
+                "a || b = c",
+            ),
+            (
+                Expr::binary(col("a"), "||", Expr::binary(col("b"), "=", col("c"))),
+                "a || (b = c)",
+            ),
+            (
+                unary("-", Expr::binary(col("a"), "+", col("b"))),
+                "- (a + b)",
+            ),
+            (
+                Expr::binary(col("a"), "-", Expr::binary(col("b"), "-", col("c"))),
+                "a - (b - c)",
+            ),
+            (
+                Expr::binary(Expr::binary(col("a"), "+", col("b")), "*", col("c")),
+                "(a + b) * c",
+            ),
+            (
+                Expr::binary(Expr::binary(col("a"), "<", col("b")), "=", col("c")),
+                "a < b = c",
+            ),
+            (
+                Expr::binary(col("a"), "<", Expr::binary(col("b"), "=", col("c"))),
+                "a < (b = c)",
+            ),
+            (
+                Expr::binary(Expr::binary(col("a"), "&", col("b")), "+", col("c")),
+                "(a & b) + c",
+            ),
+            (
+                unary("NOT", Expr::binary(col("a"), "=", col("b"))),
+                "NOT a = b",
+            ),
+            (
+                Expr::binary(unary("NOT", col("a")), "=", col("b")),
+                "(NOT a) = b",
+            ),
+            (
+                Expr::binary(col("a"), "OR", Expr::binary(col("b"), "AND", col("c"))),
+                "a OR b AND c",
+            ),
+            (
+                Expr::binary(Expr::binary(col("a"), "OR", col("b")), "AND", col("c")),
+                "(a OR b) AND c",
+            ),
+            (
+                Expr::Collate {
+                    expr: Box::new(Expr::binary(col("a"), "||", col("b"))),
+                    collation: "NOCASE".to_string(),
+                },
+                "(a || b) COLLATE NOCASE",
+            ),
+            (
+                Expr::Collate {
+                    expr: Box::new(unary("-", col("x"))),
+                    collation: "NOCASE".to_string(),
+                },
+                "- x COLLATE NOCASE",
+            ),
+            (
+                unary(
+                    "-",
+                    Expr::Collate {
+                        expr: Box::new(col("x")),
+                        collation: "NOCASE".to_string(),
+                    },
+                ),
+                "- (x COLLATE NOCASE)",
+            ),
+            (
+                Expr::IsNull {
+                    expr: Box::new(unary("NOT", col("a"))),
+                    negated: true,
+                },
+                "(NOT a) IS NOT NULL",
+            ),
+            (
+                Expr::Between {
+                    expr: Box::new(Expr::binary(col("a"), "+", col("b"))),
+                    low: Box::new(Expr::binary(col("x"), "OR", col("y"))),
+                    high: Box::new(Expr::binary(col("c"), "*", Expr::IntegerLiteral(2))),
+                    negated: false,
+                },
+                "a + b BETWEEN (x OR y) AND c * 2",
+            ),
+            (
+                Expr::Like {
+                    expr: Box::new(Expr::binary(col("a"), "||", col("b"))),
+                    pattern: Box::new(Expr::binary(
+                        col("c"),
+                        "||",
+                        Expr::StringLiteral("%".to_string()),
+                    )),
+                    kind: LikeKind::Like,
+                    negated: true,
+                    escape: None,
+                },
+                "a || b NOT LIKE c || '%'",
+            ),
+            (
+                Expr::InList {
+                    expr: Box::new(Expr::binary(col("a"), "AND", col("b"))),
+                    list: vec![Expr::IntegerLiteral(0), Expr::IntegerLiteral(1)],
+                    negated: false,
+                },
+                "(a AND b) IN (0, 1)",
+            ),
+            (
+                Expr::binary(Expr::binary(col("a"), "~", col("b")), "AND", col("c")),
+                "(a ~ b) AND c",
+            ),
+            (
+                Expr::binary(col("a"), "-", unary("-", Expr::IntegerLiteral(1))),
+                "a - - 1",
+            ),
+        ];
+        for (tree, sql) in cases {
+            assert_eq!(tree.to_sql(), sql);
+            assert_eq!(reparse(sql), tree, "{sql}");
+        }
+
+        // AND and OR chains are not re-grouped
+        let chain = Expr::binary(col("a"), "AND", Expr::binary(col("b"), "AND", col("c")));
+        assert_eq!(chain.to_sql(), "a AND b AND c");
+    }
 }
@@ -0,0 +1,236 @@
+/// `search_path` tracking: assign schemas to unqualified names as PostgreSQL would.
+use sqlparser::ast::{self, Expr as SqlExpr, FunctionArg, FunctionArgExpr, FunctionArguments};
+
+use crate::ir::{Ident, QualifiedName};
+
+/// The `search_path` in effect at a point of the statement stream.
+///
+/// Until a `SET search_path` is seen the path is unknown, and unqualified
+/// names are left for [`normalize`](crate::pg::normalize::normalize) to
+/// assign to the target schema.
+#[derive(Debug, Clone, Default)]
+pub struct SearchPath {
+    schemas: Option<Vec<Ident>>,
+}
+
+impl SearchPath {
+    /// PostgreSQL's default path, `"$user", public`.
+    fn postgres_default() -> Self {
+        Self {
+            schemas: Some(vec![Ident::quoted("$user"), Ident::new("public")]),
+        }
+    }
+
+    /// Update the path from a `SET search_path`, `RESET search_path` or
+    /// `SELECT set_config('search_path', …)` statement; other statements
+    /// are ignored.
+    pub fn apply(&mut self, stmt: &ast::Statement) {
+        match stmt {
+            ast::Statement::Set(ast::Set::SingleAssignment {
+                variable, values, ..
+            }) if is_search_path(variable) => {
+                *self = match values.as_slice() {
+                    [SqlExpr::Identifier(id)]
+                        if id.quote_style.is_none() && is_default(&id.value) =>
+                    {
+                        Self::postgres_default()
+                    }
+                    values => Self {
+                        schemas: Some(values.iter().flat_map(path_entries).collect()),
+                    },
+                };
+            }
+            ast::Statement::Reset(reset) => match &reset.reset {
+                ast::Reset::ALL => *self = Self::postgres_default(),
+                ast::Reset::ConfigurationParameter(name) if is_search_path(name) => {
+                    *self = Self::postgres_default();
+                }
+                _ => {}
+            },
+            ast::Statement::Query(query) => {
+                if let Some(value) = set_config_search_path(query) {
+                    *self = Self {
+                        schemas: Some(split_path(&value)),
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Schemas searched for existing objects, skipping `$user`, whose name
+    /// depends on the role running the script, and the system schemas.
+    fn searched(&self) -> impl Iterator<Item = &Ident> {
+        self.schemas
+            .iter()
+            .flatten()
+            .filter(|s| !matches!(s.normalized.as_str(), "$user" | "pg_catalog" | "pg_temp"))
+    }
+
+    /// Schema new objects are created in: the first searchable path entry.
+    fn creation_schema(&self) -> Option<&Ident> {
+        self.searched().next()
+    }
+
+    /// Qualify the name of an object being created.
+    pub fn qualify_new(&self, name: &mut QualifiedName) {
+        if name.schema.is_none() {
+            name.schema = self.creation_schema().cloned();
+        }
+    }
+
+    /// Qualify a reference to a table, using the first schema on the path
+    /// where `exists` finds it, or the creation schema otherwise.
+    pub fn qualify_ref(&self, name: &mut QualifiedName, exists: impl Fn(&QualifiedName) -> bool) {
+        if name.schema.is_some() {
+            return;
+        }
+        let found = self.searched().find(|schema| {
+            exists(&QualifiedName::with_schema(
+                (*schema).clone(),
+                name.name.clone(),
+            ))
+        });
+        name.schema = found.or_else(|| self.creation_schema()).cloned();
+    }
+}
+
+fn is_search_path(name: &ast::ObjectName) -> bool {
+    name.to_string().eq_ignore_ascii_case("search_path")
+}
+
+fn is_default(value: &str) -> bool {
+    value.eq_ignore_ascii_case("default")
+}
+
+/// Schemas named by one value of `SET search_path TO a, "B", 'c, d'`.
+fn path_entries(value: &SqlExpr) -> Vec<Ident> {
+    match value {
+        SqlExpr::Identifier(id) if id.quote_style.is_some() => vec![Ident::quoted(&id.value)],
+        SqlExpr::Identifier(id) => vec![Ident::new(&id.value)],
+        SqlExpr::Value(v) => match &v.value {
+            ast::Value::SingleQuotedString(s) => split_path(s),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Split a path string like `billing, "Audit", public`.
+fn split_path(value: &str) -> Vec<Ident> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(
+            |s| match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                Some(quoted) => Ident::quoted(&quoted.replace("\"\"", "\"")),
+                None => Ident::new(s),
+            },
+        )
+        .collect()
+}
+
+/// The value of `SELECT [pg_catalog.]set_config('search_path', value, is_local)`.
+fn set_config_search_path(query: &ast::Query) -> Option<String> {
+    let ast::SetExpr::Select(select) = query.body.as_ref() else {
+        return None;
+    };
+    let [ast::SelectItem::UnnamedExpr(SqlExpr::Function(func))] = select.projection.as_slice()
+    else {
+        return None;
+    };
+    let is_set_config = func
+        .name
+        .0
+        .last()
+        .and_then(|part| part.as_ident())
+        .is_some_and(|id| id.value.eq_ignore_ascii_case("set_config"));
+    let FunctionArguments::List(list) = &func.args else {
+        return None;
+    };
+    let strings: Vec<&str> = list
+        .args
+        .iter()
+        .filter_map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(SqlExpr::Value(v))) => match &v.value {
+                ast::Value::SingleQuotedString(s) => Some(s.as_str()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    match strings.as_slice() {
+        [name, value, ..] if is_set_config && name.eq_ignore_ascii_case("search_path") => {
+            Some(value.to_string())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::dialect::PostgreSqlDialect;
+    use sqlparser::parser::Parser;
+
+    fn path_after(sql: &str) -> SearchPath {
+        let mut path = SearchPath::default();
+        for stmt in Parser::parse_sql(&PostgreSqlDialect {}, sql).unwrap() {
+            path.apply(&stmt);
+        }
+        path
+    }
+
+    fn schemas(path: &SearchPath) -> Option<Vec<String>> {
+        path.schemas
+            .as_ref()
+            .map(|s| s.iter().map(|i| i.normalized.clone()).collect())
+    }
+
+    #[test]
+    fn test_search_path_statements() {
+        assert_eq!(schemas(&path_after("SELECT 1;")), None);
+        assert_eq!(
+            schemas(&path_after(
+                "SET search_path TO Billing, \"Audit\", public;"
+            )),
+            Some(vec!["billing".into(), "Audit".into(), "public".into()])
+        );
+        assert_eq!(
+            schemas(&path_after("SET LOCAL search_path = 'app, \"$user\"';")),
+            Some(vec!["app".into(), "$user".into()])
+        );
+        assert_eq!(
+            schemas(&path_after(
+                "SELECT pg_catalog.set_config('search_path', '', false);"
+            )),
+            Some(vec![])
+        );
+        assert_eq!(
+            schemas(&path_after("SET search_path TO app; RESET search_path;")),
+            Some(vec!["$user".into(), "public".into()])
+        );
+    }
+
+    #[test]
+    fn test_qualify_names() {
+        let path = path_after("SET search_path TO \"$user\", billing, public;");
+        let mut name = QualifiedName::new(Ident::new("invoices"));
+        path.qualify_new(&mut name);
+        assert_eq!(name.to_string(), "billing.invoices");
+
+        let mut target = QualifiedName::new(Ident::new("users"));
+        path.qualify_ref(&mut target, |n| n.to_string() == "public.users");
+        assert_eq!(target.to_string(), "public.users");
+
+        let mut missing = QualifiedName::new(Ident::new("ledger"));
+        path.qualify_ref(&mut missing, |_| false);
+        assert_eq!(missing.to_string(), "billing.ledger");
+
+        let unknown = SearchPath::default();
+        let mut name = QualifiedName::new(Ident::new("invoices"));
+        unknown.qualify_new(&mut name);
+        assert!(name.schema.is_none());
+    }
+}
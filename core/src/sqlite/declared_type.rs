@@ -0,0 +1,370 @@
+/// Declared column types that keep the PostgreSQL type name.
+///
+/// SQLite derives a column's affinity from substrings of its declared type
+/// (<https://www.sqlite.org/datatype3.html#determination_of_column_affinity>),
+/// so a name is only emitted when it stores values as the mapped [`SqliteType`]
+/// does. Names that store them differently get the affinity keyword appended
+/// (`TIMESTAMPTZ TEXT`); names that still do, such as `POINT` containing `INT`,
+/// fall back to the plain SQLite type.
+use crate::ir::{Column, PgType, SqliteType};
+
+/// Words that would end the type name inside a column definition.
+const RESERVED: &[&str] = &[
+    "AS",
+    "CHECK",
+    "COLLATE",
+    "CONSTRAINT",
+    "DEFAULT",
+    "GENERATED",
+    "KEY",
+    "NOT",
+    "NULL",
+    "PRIMARY",
+    "REFERENCES",
+    "UNIQUE",
+];
+
+/// The affinity SQLite assigns to a declared type name.
+pub fn affinity(declared: &str) -> SqliteType {
+    let declared = declared.to_ascii_uppercase();
+    if declared.contains("INT") {
+        SqliteType::Integer
+    } else if ["CHAR", "CLOB", "TEXT"]
+        .iter()
+        .any(|k| declared.contains(k))
+    {
+        SqliteType::Text
+    } else if declared.is_empty() || declared.contains("BLOB") {
+        SqliteType::Blob
+    } else if ["REAL", "FLOA", "DOUB"]
+        .iter()
+        .any(|k| declared.contains(k))
+    {
+        SqliteType::Real
+    } else {
+        SqliteType::Numeric
+    }
+}
+
+/// Whether a column declared as `declared` stores values as `sqlite_type` does.
+///
+/// INTEGER and NUMERIC affinity only differ in `CAST` expressions, and array
+/// text (`{…}` or `[…]`) never looks numeric, so no affinity converts it.
+fn stores_like(declared: &str, sqlite_type: SqliteType, pg_type: &PgType) -> bool {
+    let declared = affinity(declared);
+    declared == sqlite_type
+        || matches!(
+            (declared, sqlite_type),
+            (SqliteType::Integer, SqliteType::Numeric) | (SqliteType::Numeric, SqliteType::Integer)
+        )
+        || (matches!(pg_type, PgType::Array { .. }) && sqlite_type == SqliteType::Text)
+}
+
+/// The declared type for a column: the PostgreSQL name when it stores values
+/// as the column's SQLite type, otherwise the SQLite type itself.
+pub fn declared_type(col: &Column) -> Option<String> {
+    let sqlite_type = col.sqlite_type?;
+    if sqlite_type == SqliteType::Any {
+        return Some(sqlite_type.to_string());
+    }
+    let Some((name, params)) = pg_type_name(&col.pg_type) else {
+        return Some(sqlite_type.to_string());
+    };
+
+    let candidates = [name.clone(), format!("{name} {sqlite_type}")];
+    let declared = candidates
+        .into_iter()
+        .find(|c| stores_like(c, sqlite_type, &col.pg_type))
+        .map(|c| format!("{c}{params}"))
+        .unwrap_or_else(|| sqlite_type.to_string());
+    Some(declared)
+}
+
+/// Upper-case type name and parenthesized modifiers for a PostgreSQL type.
+fn pg_type_name(pg_type: &PgType) -> Option<(String, String)> {
+    let length = |n: &Option<u32>| n.map(|n| format!("({n})")).unwrap_or_default();
+    let (name, params) = match pg_type {
+        PgType::SmallInt => ("SMALLINT", String::new()),
+        PgType::Integer => ("INTEGER", String::new()),
+        PgType::BigInt => ("BIGINT", String::new()),
+        PgType::SmallSerial => ("SMALLSERIAL", String::new()),
+        PgType::Serial => ("SERIAL", String::new()),
+        PgType::BigSerial => ("BIGSERIAL", String::new()),
+        PgType::Numeric { precision, scale } => {
+            let params = match (precision, scale) {
+                (Some(p), Some(s)) => format!("({p},{s})"),
+                (Some(p), None) => format!("({p})"),
+                _ => String::new(),
+            };
+            ("NUMERIC", params)
+        }
+        PgType::Real => ("REAL", String::new()),
+        PgType::DoublePrecision => ("DOUBLE PRECISION", String::new()),
+        PgType::Text => ("TEXT", String::new()),
+        PgType::Varchar { length: n } => ("VARCHAR", length(n)),
+        PgType::Char { length: n } => ("CHAR", length(n)),
+        PgType::Boolean => ("BOOLEAN", String::new()),
+        PgType::Date => ("DATE", String::new()),
+        PgType::Time { with_tz: false } => ("TIME", String::new()),
+        PgType::Time { with_tz: true } => ("TIMETZ", String::new()),
+        PgType::Timestamp { with_tz: false } => ("TIMESTAMP", String::new()),
+        PgType::Timestamp { with_tz: true } => ("TIMESTAMPTZ", String::new()),
+        PgType::Interval => ("INTERVAL", String::new()),
+        PgType::Bytea => ("BYTEA", String::new()),
+        PgType::Uuid => ("UUID", String::new()),
+        PgType::Json => ("JSON", String::new()),
+        PgType::Jsonb => ("JSONB", String::new()),
+        PgType::Inet => ("INET", String::new()),
+        PgType::Cidr => ("CIDR", String::new()),
+        PgType::MacAddr => ("MACADDR", String::new()),
+        PgType::Point => ("POINT", String::new()),
+        PgType::Line => ("LINE", String::new()),
+        PgType::Lseg => ("LSEG", String::new()),
+        PgType::Box => ("BOX", String::new()),
+        PgType::Path => ("PATH", String::new()),
+        PgType::Polygon => ("POLYGON", String::new()),
+        PgType::Circle => ("CIRCLE", String::new()),
+        PgType::Money => ("MONEY", String::new()),
+        PgType::Bit { length: n } => ("BIT", length(n)),
+        PgType::VarBit { length: n } => ("VARBIT", length(n)),
+        PgType::Xml => ("XML", String::new()),
+        PgType::Int4Range => ("INT4RANGE", String::new()),
+        PgType::Int8Range => ("INT8RANGE", String::new()),
+        PgType::NumRange => ("NUMRANGE", String::new()),
+        PgType::TsRange => ("TSRANGE", String::new()),
+        PgType::TsTzRange => ("TSTZRANGE", String::new()),
+        PgType::DateRange => ("DATERANGE", String::new()),
+        PgType::Citext => ("CITEXT", String::new()),
+        PgType::Hstore => ("HSTORE", String::new()),
+        PgType::Ltree => ("LTREE", String::new()),
+        PgType::Geometry {
+            geography: false, ..
+        } => ("GEOMETRY", String::new()),
+        PgType::Geometry {
+            geography: true, ..
+        } => ("GEOGRAPHY", String::new()),
+        PgType::Enum { name }
+        | PgType::Composite { name }
+        | PgType::Domain { name }
+        | PgType::Other { name } => return user_type_name(name).map(|n| (n, String::new())),
+        // Element modifiers are dropped: SQLite rejects `VARCHAR(20)[]`
+        PgType::Array { element } => {
+            let (name, _) = pg_type_name(element)?;
+            return Some((format!("{name}[]"), String::new()));
+        }
+    };
+    Some((name.to_string(), params))
+}
+
+/// Upper-case name of a user-defined type, when it is a plain identifier
+/// that SQLite reads as a type name.
+fn user_type_name(name: &str) -> Option<String> {
+    let name = name.rsplit('.').next().unwrap_or(name).to_ascii_uppercase();
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    (valid && !RESERVED.contains(&name.as_str())).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::Ident;
+
+    fn column(pg_type: PgType, sqlite_type: SqliteType) -> Column {
+        Column {
+            name: Ident::new("c"),
+            pg_type,
+            sqlite_type: Some(sqlite_type),
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn declared(pg_type: PgType, sqlite_type: SqliteType) -> String {
+        declared_type(&column(pg_type, sqlite_type)).unwrap()
+    }
+
+    #[test]
+    fn test_affinity_rules() {
+        // Examples from the SQLite documentation
+        for (name, expected) in [
+            ("INT", SqliteType::Integer),
+            ("UNSIGNED BIG INT", SqliteType::Integer),
+            ("CHARACTER(20)", SqliteType::Text),
+            ("NVARCHAR(100)", SqliteType::Text),
+            ("CLOB", SqliteType::Text),
+            ("BLOB", SqliteType::Blob),
+            ("", SqliteType::Blob),
+            ("DOUBLE PRECISION", SqliteType::Real),
+            ("FLOAT", SqliteType::Real),
+            ("DECIMAL(10,5)", SqliteType::Numeric),
+            ("DATETIME", SqliteType::Numeric),
+            ("FLOATING POINT", SqliteType::Integer),
+            ("STRING", SqliteType::Numeric),
+        ] {
+            assert_eq!(affinity(name), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_matching_names_kept() {
+        assert_eq!(
+            declared(PgType::Varchar { length: Some(255) }, SqliteType::Text),
+            "VARCHAR(255)"
+        );
+        assert_eq!(
+            declared(
+                PgType::Numeric {
+                    precision: Some(10),
+                    scale: Some(2)
+                },
+                SqliteType::Numeric
+            ),
+            "NUMERIC(10,2)"
+        );
+        assert_eq!(
+            declared(PgType::DoublePrecision, SqliteType::Real),
+            "DOUBLE PRECISION"
+        );
+        assert_eq!(declared(PgType::BigInt, SqliteType::Integer), "BIGINT");
+        assert_eq!(declared(PgType::Citext, SqliteType::Text), "CITEXT");
+    }
+
+    #[test]
+    fn test_integer_and_numeric_interchangeable() {
+        assert_eq!(declared(PgType::Boolean, SqliteType::Integer), "BOOLEAN");
+        assert_eq!(
+            declared(
+                PgType::Numeric {
+                    precision: Some(10),
+                    scale: Some(2)
+                },
+                SqliteType::Integer
+            ),
+            "NUMERIC(10,2)"
+        );
+        assert_eq!(declared(PgType::Integer, SqliteType::Numeric), "INTEGER");
+    }
+
+    #[test]
+    fn test_affinity_keyword_appended() {
+        assert_eq!(
+            declared(PgType::Timestamp { with_tz: true }, SqliteType::Text),
+            "TIMESTAMPTZ TEXT"
+        );
+        assert_eq!(declared(PgType::Uuid, SqliteType::Blob), "UUID BLOB");
+        assert_eq!(declared(PgType::Jsonb, SqliteType::Text), "JSONB TEXT");
+        assert_eq!(declared(PgType::Uuid, SqliteType::Text), "UUID TEXT");
+        assert_eq!(
+            declared(PgType::Bit { length: Some(8) }, SqliteType::Text),
+            "BIT TEXT(8)"
+        );
+    }
+
+    #[test]
+    fn test_array_names() {
+        let array = |element: PgType| PgType::Array {
+            element: Box::new(element),
+        };
+        assert_eq!(
+            declared(array(PgType::Integer), SqliteType::Text),
+            "INTEGER[]"
+        );
+        assert_eq!(declared(array(PgType::Text), SqliteType::Text), "TEXT[]");
+        assert_eq!(
+            declared(
+                array(PgType::Varchar { length: Some(20) }),
+                SqliteType::Text
+            ),
+            "VARCHAR[]"
+        );
+        assert_eq!(
+            declared(array(array(PgType::Point)), SqliteType::Text),
+            "POINT[][]"
+        );
+        assert_eq!(declared(array(PgType::Integer), SqliteType::Blob), "BLOB");
+    }
+
+    #[test]
+    fn test_colliding_names_fall_back() {
+        assert_eq!(declared(PgType::Point, SqliteType::Text), "TEXT");
+        assert_eq!(declared(PgType::Interval, SqliteType::Text), "TEXT");
+        assert_eq!(declared(PgType::Interval, SqliteType::Integer), "INTERVAL");
+        assert_eq!(declared(PgType::Citext, SqliteType::Blob), "BLOB");
+    }
+
+    #[test]
+    fn test_user_type_names() {
+        let other = |name: &str| PgType::Enum {
+            name: name.to_string(),
+        };
+        assert_eq!(declared(other("mood"), SqliteType::Text), "MOOD TEXT");
+        assert_eq!(
+            declared(other("app.status"), SqliteType::Text),
+            "STATUS TEXT"
+        );
+        assert_eq!(declared(other("primary"), SqliteType::Text), "TEXT");
+        assert_eq!(declared(other("my-type"), SqliteType::Text), "TEXT");
+    }
+
+    #[test]
+    fn test_every_name_stores_like_mapped_type() {
+        let types = [
+            PgType::SmallInt,
+            PgType::Serial,
+            PgType::Real,
+            PgType::Text,
+            PgType::Char { length: Some(3) },
+            PgType::Date,
+            PgType::Time { with_tz: true },
+            PgType::Bytea,
+            PgType::Json,
+            PgType::Inet,
+            PgType::Circle,
+            PgType::Money,
+            PgType::Bit { length: Some(8) },
+            PgType::Xml,
+            PgType::Int8Range,
+            PgType::TsTzRange,
+            PgType::Hstore,
+            PgType::Ltree,
+            PgType::Array {
+                element: Box::new(PgType::Integer),
+            },
+        ];
+        let affinities = [
+            SqliteType::Integer,
+            SqliteType::Text,
+            SqliteType::Real,
+            SqliteType::Numeric,
+            SqliteType::Blob,
+        ];
+        for pg_type in types {
+            for sqlite_type in affinities {
+                let name = declared(pg_type.clone(), sqlite_type);
+                assert!(
+                    stores_like(&name, sqlite_type, &pg_type),
+                    "{pg_type} as {name}"
+                );
+                if !matches!(pg_type, PgType::Array { .. }) {
+                    let integer_like = [SqliteType::Integer, SqliteType::Numeric];
+                    assert!(
+                        affinity(&name) == sqlite_type
+                            || (integer_like.contains(&affinity(&name))
+                                && integer_like.contains(&sqlite_type)),
+                        "{pg_type} as {name}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1 +1,2 @@
+pub mod declared_type;
 pub mod render;
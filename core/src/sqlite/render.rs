@@ -1,8 +1,22 @@
 /// SQLite DDL rendering from IR.
-use crate::ir::{Column, Index, IndexColumn, SchemaModel, Table, TableConstraint};
-
-/// Render the schema model as SQLite DDL text.
-pub fn render(model: &SchemaModel, enable_foreign_keys: bool) -> String {
+use super::declared_type::declared_type;
+use crate::ir::{
+    Column, ColumnMetadata, Expr, Index, IndexColumn, SchemaModel, SqliteType, Table,
+    TableConstraint,
+};
+use crate::transform::extension::json_string;
+
+/// Render the schema model as SQLite DDL text, optionally declaring every table `STRICT`.
+///
+/// With `preserve_type_names`, columns are declared with their PostgreSQL
+/// type names; STRICT tables only accept the plain SQLite types, so the
+/// option has no effect there.
+pub fn render(
+    model: &SchemaModel,
+    enable_foreign_keys: bool,
+    strict_tables: bool,
+    preserve_type_names: bool,
+) -> String {
     let mut output = String::new();
 
     // Code generation header
@@ -15,7 +29,12 @@ pub fn render(model: &SchemaModel, enable_foreign_keys: bool) -> String {
 
     // CREATE TABLE statements
     for table in &model.tables {
-        render_table(table, &mut output);
+        render_table(
+            table,
+            strict_tables,
+            preserve_type_names && !strict_tables,
+            &mut output,
+        );
         output.push('\n');
     }
 
@@ -34,6 +53,12 @@ pub fn render(model: &SchemaModel, enable_foreign_keys: bool) -> String {
         output.push('\n');
     }
 
+    // Column metadata table
+    if !model.column_metadata.is_empty() {
+        render_column_metadata(&model.column_metadata, strict_tables, &mut output);
+        output.push('\n');
+    }
+
     // Remove trailing newline
     while output.ends_with("\n\n") {
         output.pop();
@@ -45,14 +70,22 @@ pub fn render(model: &SchemaModel, enable_foreign_keys: bool) -> String {
     output
 }
 
-fn render_table(table: &Table, out: &mut String) {
+fn render_table(table: &Table, strict: bool, preserve_type_names: bool, out: &mut String) {
+    if let Some(note) = &table.note {
+        out.push_str(&format!("-- {note}\n"));
+    }
     out.push_str(&format!("CREATE TABLE {} (\n", table.name.to_sql()));
 
     let mut parts: Vec<String> = Vec::new();
 
     // Columns
+    let rowid_key = single_primary_key(table);
     for col in &table.columns {
-        parts.push(render_column(col));
+        // Only a column declared exactly `INTEGER PRIMARY KEY` aliases the rowid
+        let preserve = preserve_type_names
+            && !(rowid_key == Some(col.name.normalized.as_str())
+                && col.sqlite_type == Some(SqliteType::Integer));
+        parts.push(render_column(col, preserve));
     }
 
     // Table-level constraints (PK → UNIQUE → CHECK → FK)
@@ -69,19 +102,104 @@ fn render_table(table: &Table, out: &mut String) {
     }
 
     out.push_str(&parts.join(",\n"));
-    out.push_str("\n);\n");
+
+    // Table options
+    let mut options = Vec::new();
+    if table.without_rowid {
+        options.push("WITHOUT ROWID");
+    }
+    if strict {
+        options.push("STRICT");
+    }
+    if options.is_empty() {
+        out.push_str("\n);\n");
+    } else {
+        out.push_str(&format!("\n) {};\n", options.join(", ")));
+    }
+}
+
+fn render_column_metadata(rows: &[ColumnMetadata], strict: bool, out: &mut String) {
+    out.push_str(&format!(
+        "CREATE TABLE _pg2sqlite_columns (
+  table_name TEXT NOT NULL,
+  column_name TEXT NOT NULL,
+  pg_type TEXT NOT NULL,
+  sqlite_type TEXT NOT NULL,
+  strategy TEXT NOT NULL,
+  domain_name TEXT,
+  enum_labels TEXT,
+  PRIMARY KEY (table_name, column_name)
+){};\n\n",
+        if strict { " STRICT" } else { "" }
+    ));
+
+    let text = |s: &str| Expr::StringLiteral(s.to_string()).to_sql();
+    let values: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let domain = row.domain.as_deref().map_or("NULL".to_string(), text);
+            let labels = row
+                .enum_labels
+                .as_ref()
+                .map_or("NULL".to_string(), |labels| {
+                    let items: Vec<String> = labels.iter().map(|l| json_string(l)).collect();
+                    text(&format!("[{}]", items.join(",")))
+                });
+            format!(
+                "  ({}, {}, {}, {}, {}, {domain}, {labels})",
+                text(&row.table),
+                text(&row.column),
+                text(&row.pg_type),
+                text(&row.sqlite_type),
+                text(&row.strategy),
+            )
+        })
+        .collect();
+    out.push_str(&format!(
+        "INSERT INTO _pg2sqlite_columns (table_name, column_name, pg_type, sqlite_type, strategy, domain_name, enum_labels) VALUES\n{};\n",
+        values.join(",\n")
+    ));
+}
+
+/// The primary key column of a table keyed by a single column.
+fn single_primary_key(table: &Table) -> Option<&str> {
+    for constraint in &table.constraints {
+        if let TableConstraint::PrimaryKey { columns, .. } = constraint {
+            return match columns.as_slice() {
+                [column] => Some(column.normalized.as_str()),
+                _ => None,
+            };
+        }
+    }
+    match table
+        .columns
+        .iter()
+        .filter(|c| c.is_primary_key)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [column] => Some(column.name.normalized.as_str()),
+        _ => None,
+    }
 }
 
-fn render_column(col: &Column) -> String {
+fn render_column(col: &Column, preserve_type_names: bool) -> String {
     let mut parts = Vec::new();
 
     parts.push(format!("  {}", col.name.to_sql()));
 
     // Type
-    if let Some(sqlite_type) = &col.sqlite_type {
+    if preserve_type_names {
+        parts.extend(declared_type(col));
+    } else if let Some(sqlite_type) = &col.sqlite_type {
         parts.push(sqlite_type.to_string());
     }
 
+    // COLLATE
+    if let Some(collation) = &col.collation {
+        parts.push(format!("COLLATE {collation}"));
+    }
+
     // PRIMARY KEY (with optional AUTOINCREMENT)
     if col.is_primary_key {
         if col.autoincrement {
@@ -207,7 +325,7 @@ fn render_index(index: &Index, out: &mut String) {
 }
 
 fn needs_default_parens(sql: &str) -> bool {
-    sql.contains('(') || sql == "CURRENT_TIMESTAMP"
+    sql.contains('(') || matches!(sql, "CURRENT_TIMESTAMP" | "CURRENT_DATE" | "CURRENT_TIME")
 }
 
 #[cfg(test)]
@@ -227,6 +345,7 @@ mod tests {
             autoincrement: false,
             references: None,
             check: None,
+            collation: None,
         }
     }
 
@@ -248,11 +367,13 @@ mod tests {
                     },
                 ],
                 constraints: vec![],
+                without_rowid: false,
+                note: None,
             }],
             ..Default::default()
         };
 
-        let sql = render(&model, false);
+        let sql = render(&model, false, false, false);
         assert!(sql.contains("CREATE TABLE users"));
         assert!(sql.contains("id INTEGER PRIMARY KEY"));
         assert!(sql.contains("name TEXT NOT NULL"));
@@ -261,7 +382,7 @@ mod tests {
     #[test]
     fn test_render_with_pragma() {
         let model = SchemaModel::default();
-        let sql = render(&model, true);
+        let sql = render(&model, true, false, false);
         assert!(sql.starts_with(
             "-- Code generated by `pg2sqlite`. DO NOT EDIT.\n\nPRAGMA foreign_keys = ON;"
         ));
@@ -280,11 +401,13 @@ mod tests {
                     name: None,
                     columns: vec![Ident::new("user_id"), Ident::new("role_id")],
                 }],
+                without_rowid: false,
+                note: None,
             }],
             ..Default::default()
         };
 
-        let sql = render(&model, false);
+        let sql = render(&model, false, false, false);
         assert!(sql.contains("PRIMARY KEY (user_id, role_id)"));
     }
 
@@ -303,11 +426,13 @@ mod tests {
                     on_update: None,
                     deferrable: false,
                 }],
+                without_rowid: false,
+                note: None,
             }],
             ..Default::default()
         };
 
-        let sql = render(&model, true);
+        let sql = render(&model, true, false, false);
         assert!(sql.contains("FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE"));
     }
 
@@ -325,7 +450,7 @@ mod tests {
             ..Default::default()
         };
 
-        let sql = render(&model, false);
+        let sql = render(&model, false, false, false);
         assert!(sql.contains("CREATE UNIQUE INDEX idx_email ON users (email);"));
     }
 
@@ -340,11 +465,105 @@ mod tests {
                     c
                 }],
                 constraints: vec![],
+                without_rowid: false,
+                note: None,
             }],
             ..Default::default()
         };
 
-        let sql = render(&model, false);
+        let sql = render(&model, false, false, false);
         assert!(sql.contains("DEFAULT (CURRENT_TIMESTAMP)"));
     }
+
+    #[test]
+    fn test_render_column_collation() {
+        let model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("users")),
+                columns: vec![{
+                    let mut c = make_column("email", SqliteType::Text);
+                    c.collation = Some("NOCASE".to_string());
+                    c.not_null = true;
+                    c
+                }],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        };
+
+        let sql = render(&model, false, false, false);
+        assert!(sql.contains("email TEXT COLLATE NOCASE NOT NULL"));
+    }
+
+    #[test]
+    fn test_render_strict_table() {
+        let model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("items")),
+                columns: vec![make_column("data", SqliteType::Any)],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        };
+
+        let sql = render(&model, false, true, false);
+        assert!(sql.contains("CREATE TABLE items (\n  data ANY\n) STRICT;"));
+    }
+
+    #[test]
+    fn test_render_without_rowid_table() {
+        let mut table = Table {
+            name: QualifiedName::new(Ident::new("tags")),
+            columns: vec![{
+                let mut c = make_column("slug", SqliteType::Text);
+                c.is_primary_key = true;
+                c
+            }],
+            constraints: vec![],
+            without_rowid: true,
+            note: None,
+        };
+        let model = SchemaModel {
+            tables: vec![table.clone()],
+            ..Default::default()
+        };
+        assert!(render(&model, false, false, false).contains("\n) WITHOUT ROWID;"));
+        assert!(render(&model, false, true, false).contains("\n) WITHOUT ROWID, STRICT;"));
+
+        table.without_rowid = false;
+        let model = SchemaModel {
+            tables: vec![table],
+            ..Default::default()
+        };
+        assert!(render(&model, false, false, false).contains("\n);"));
+    }
+
+    #[test]
+    fn test_render_preserved_type_names() {
+        let mut created_at = make_column("created_at", SqliteType::Text);
+        created_at.pg_type = PgType::Timestamp { with_tz: true };
+        let mut email = make_column("email", SqliteType::Text);
+        email.pg_type = PgType::Varchar { length: Some(255) };
+        let model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("users")),
+                columns: vec![created_at, email],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        };
+
+        let sql = render(&model, false, false, true);
+        assert!(sql.contains("created_at TIMESTAMPTZ TEXT,"));
+        assert!(sql.contains("email VARCHAR(255)\n"));
+
+        let sql = render(&model, false, true, true);
+        assert!(sql.contains("created_at TEXT,"));
+    }
 }
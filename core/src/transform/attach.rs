@@ -0,0 +1,651 @@
+/// Attach layout: one SQLite database per PostgreSQL schema, tied together
+/// by an `ATTACH DATABASE` script.
+use std::collections::{BTreeMap, HashMap};
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{FkAction, Ident, QualifiedName, SchemaModel, Table, TableConstraint};
+use crate::transform::catalog::Catalog;
+use crate::transform::metadata::ColumnOrigin;
+use crate::transform::name_resolve;
+
+/// How tables from several schemas are laid out in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchemaLayout {
+    /// One database; colliding tables are prefixed `schema__table` (default).
+    #[default]
+    Prefix,
+    /// One database per schema, attached by a bootstrap script.
+    Attach,
+}
+
+impl std::str::FromStr for SchemaLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "prefix" => Ok(SchemaLayout::Prefix),
+            "attach" => Ok(SchemaLayout::Attach),
+            other => Err(format!(
+                "unknown schema layout '{other}' (expected prefix or attach)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaLayout::Prefix => write!(f, "prefix"),
+            SchemaLayout::Attach => write!(f, "attach"),
+        }
+    }
+}
+
+/// The tables of one schema, with their column origins.
+pub struct SchemaPart {
+    pub schema: String,
+    pub model: SchemaModel,
+    pub origins: Vec<HashMap<String, ColumnOrigin>>,
+}
+
+/// Qualify every table, foreign key and index with its schema, assuming
+/// `default_schema` for names that are still unqualified. Stands in for
+/// [`resolve_names`](name_resolve::resolve_names): table names are kept.
+pub fn qualify_names(
+    model: &mut SchemaModel,
+    catalog: &Catalog,
+    default_schema: &str,
+    warnings: &mut Vec<Warning>,
+) {
+    name_resolve::qualify_references(model, catalog, warnings);
+
+    let qualify = |name: &mut QualifiedName| {
+        if name.schema.is_none() {
+            name.schema = Some(Ident::new(default_schema));
+        }
+    };
+    for table in &mut model.tables {
+        qualify(&mut table.name);
+        for constraint in &mut table.constraints {
+            if let TableConstraint::ForeignKey { ref_table, .. } = constraint {
+                qualify(ref_table);
+            }
+        }
+        for col in &mut table.columns {
+            if let Some(fk) = &mut col.references {
+                qualify(&mut fk.table);
+            }
+        }
+    }
+    for index in &mut model.indexes {
+        qualify(&mut index.table);
+    }
+}
+
+/// A foreign key whose tables live in different schemas.
+struct CrossSchemaFk {
+    object: String,
+    child: QualifiedName,
+    columns: Vec<Ident>,
+    parent: QualifiedName,
+    ref_columns: Vec<Ident>,
+    on_delete: Option<FkAction>,
+    on_update: Option<FkAction>,
+}
+
+/// Remove foreign keys between schemas, which SQLite cannot enforce across
+/// attached databases, and return TEMP triggers emulating them.
+///
+/// Must run after renames, as the triggers name the final tables and
+/// columns. Nothing is emulated when foreign keys are disabled. Keys with
+/// `SET DEFAULT` actions, keys whose referenced columns cannot be determined,
+/// and `CASCADE` or `SET NULL` keys on a table whose name is not unique across
+/// schemas (trigger bodies can only modify unqualified tables) are dropped.
+pub fn extract_cross_schema_fks(
+    model: &mut SchemaModel,
+    enable_foreign_keys: bool,
+    warnings: &mut Vec<Warning>,
+) -> Vec<String> {
+    let mut found = Vec::new();
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        for col in &mut table.columns {
+            let Some(fk) = &col.references else {
+                continue;
+            };
+            if !same_schema(&table.name, &fk.table) {
+                let fk = col.references.take().unwrap();
+                found.push(CrossSchemaFk {
+                    object: format!("{table_name}.{}", col.name.normalized),
+                    child: table.name.clone(),
+                    columns: vec![col.name.clone()],
+                    parent: fk.table,
+                    ref_columns: fk.column.into_iter().collect(),
+                    on_delete: fk.on_delete,
+                    on_update: fk.on_update,
+                });
+            }
+        }
+        let child = table.name.clone();
+        table.constraints.retain(|c| match c {
+            TableConstraint::ForeignKey {
+                name,
+                columns,
+                ref_table,
+                ref_columns,
+                on_delete,
+                on_update,
+                ..
+            } if !same_schema(&child, ref_table) => {
+                found.push(CrossSchemaFk {
+                    object: match name {
+                        Some(name) => format!("{table_name}.{}", name.normalized),
+                        None => table_name.clone(),
+                    },
+                    child: child.clone(),
+                    columns: columns.clone(),
+                    parent: ref_table.clone(),
+                    ref_columns: ref_columns.clone(),
+                    on_delete: *on_delete,
+                    on_update: *on_update,
+                });
+                false
+            }
+            _ => true,
+        });
+    }
+    if !enable_foreign_keys {
+        return Vec::new();
+    }
+
+    let mut triggers = Vec::new();
+    for (n, mut fk) in found.into_iter().enumerate() {
+        if fk.ref_columns.is_empty() {
+            fk.ref_columns = primary_key(model, &fk.parent).unwrap_or_default();
+        }
+        let actions = [fk.on_delete, fk.on_update];
+        let set_default = actions.contains(&Some(FkAction::SetDefault));
+        let modifies_child = actions
+            .iter()
+            .any(|a| matches!(a, Some(FkAction::Cascade | FkAction::SetNull)));
+        let child_unique = model
+            .tables
+            .iter()
+            .filter(|t| {
+                t.name
+                    .name
+                    .normalized
+                    .eq_ignore_ascii_case(&fk.child.name.normalized)
+            })
+            .count()
+            == 1;
+        if fk.ref_columns.len() != fk.columns.len()
+            || set_default
+            || (modifies_child && !child_unique)
+        {
+            warnings.push(
+                Warning::new(
+                    warning::CROSS_SCHEMA_FK_DROPPED,
+                    Severity::Unsupported,
+                    format!(
+                        "foreign key to {} spans attached databases and cannot be emulated; dropped",
+                        fk.parent
+                    ),
+                )
+                .with_object(&fk.object),
+            );
+            continue;
+        }
+        warnings.push(
+            Warning::new(
+                warning::CROSS_SCHEMA_FK_EMULATED,
+                Severity::Lossy,
+                format!(
+                    "foreign key to {} spans attached databases; enforced by TEMP triggers in the attach script",
+                    fk.parent
+                ),
+            )
+            .with_object(&fk.object),
+        );
+        triggers.extend(fk_triggers(&fk, n + 1));
+    }
+    triggers
+}
+
+/// Split a model into one part per schema, ordered by schema name.
+///
+/// `origins` must be aligned with `model.tables`.
+pub fn split_by_schema(
+    model: SchemaModel,
+    origins: Vec<HashMap<String, ColumnOrigin>>,
+) -> Vec<SchemaPart> {
+    let mut parts: BTreeMap<String, SchemaPart> = BTreeMap::new();
+    let schema_of = |name: &QualifiedName| {
+        name.schema
+            .as_ref()
+            .map(|s| s.normalized.clone())
+            .unwrap_or_default()
+    };
+    for (table, origins) in model.tables.into_iter().zip(origins) {
+        let schema = schema_of(&table.name);
+        let part = parts.entry(schema.clone()).or_insert_with(|| SchemaPart {
+            schema,
+            model: SchemaModel::default(),
+            origins: Vec::new(),
+        });
+        part.model.tables.push(table);
+        part.origins.push(origins);
+    }
+    for index in model.indexes {
+        if let Some(part) = parts.get_mut(&schema_of(&index.table)) {
+            part.model.indexes.push(index);
+        }
+    }
+    parts.into_values().collect()
+}
+
+/// The script attaching each schema's database, followed by the triggers
+/// emulating foreign keys between them.
+pub fn render_attach_script(
+    schemas: &[String],
+    triggers: &[String],
+    enable_foreign_keys: bool,
+) -> String {
+    let mut output = String::from("-- Code generated by `pg2sqlite`. DO NOT EDIT.\n\n");
+    for schema in schemas {
+        output.push_str(&format!(
+            "ATTACH DATABASE '{}.db' AS {};\n",
+            schema.replace('\'', "''"),
+            Ident::quoted(schema).to_sql()
+        ));
+    }
+    if enable_foreign_keys {
+        output.push_str("\nPRAGMA foreign_keys = ON;\n");
+    }
+    for trigger in triggers {
+        output.push('\n');
+        output.push_str(trigger);
+        output.push('\n');
+    }
+    output
+}
+
+fn same_schema(a: &QualifiedName, b: &QualifiedName) -> bool {
+    a.schema.as_ref().map(|s| &s.normalized) == b.schema.as_ref().map(|s| &s.normalized)
+}
+
+/// Primary key columns of a table in the model.
+fn primary_key(model: &SchemaModel, name: &QualifiedName) -> Option<Vec<Ident>> {
+    let table: &Table = model
+        .tables
+        .iter()
+        .find(|t| t.name.name.normalized == name.name.normalized && same_schema(&t.name, name))?;
+    if let Some(col) = table.columns.iter().find(|c| c.is_primary_key) {
+        return Some(vec![col.name.clone()]);
+    }
+    table.constraints.iter().find_map(|c| match c {
+        TableConstraint::PrimaryKey { columns, .. } => Some(columns.clone()),
+        _ => None,
+    })
+}
+
+fn qualified_sql(name: &QualifiedName) -> String {
+    match &name.schema {
+        Some(schema) => format!("{}.{}", schema.to_sql(), name.name.to_sql()),
+        None => name.name.to_sql(),
+    }
+}
+
+/// `a = NEW.x AND b = NEW.y` style column pairings.
+fn pairs(left: &[Ident], row: &str, right: &[Ident], sep: &str) -> String {
+    left.iter()
+        .zip(right)
+        .map(|(l, r)| format!("{} = {row}.{}", l.to_sql(), r.to_sql()))
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+/// TEMP triggers enforcing one foreign key: child inserts and updates must
+/// find the parent row; parent deletes and key updates are restricted,
+/// cascaded or set the child columns to NULL.
+fn fk_triggers(fk: &CrossSchemaFk, n: usize) -> Vec<String> {
+    let child = qualified_sql(&fk.child);
+    // Tables modified in a trigger body cannot be qualified
+    let child_target = fk.child.name.to_sql();
+    let parent = qualified_sql(&fk.parent);
+    let prefix = format!(
+        "fk_{}_{}_{n}",
+        fk.child
+            .schema
+            .as_ref()
+            .map(|s| s.normalized.as_str())
+            .unwrap_or_default(),
+        fk.child.name.normalized
+    );
+    let trigger = |suffix: &str| Ident::quoted(&format!("{prefix}_{suffix}")).to_sql();
+    let raise = "SELECT RAISE(ABORT, 'FOREIGN KEY constraint failed');";
+
+    let not_null = fk
+        .columns
+        .iter()
+        .map(|c| format!("NEW.{} IS NOT NULL", c.to_sql()))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let parent_missing = format!(
+        "{not_null} AND NOT EXISTS (SELECT 1 FROM {parent} WHERE {})",
+        pairs(&fk.ref_columns, "NEW", &fk.columns, " AND ")
+    );
+    let child_rows = pairs(&fk.columns, "OLD", &fk.ref_columns, " AND ");
+    let child_exists = format!("EXISTS (SELECT 1 FROM {child} WHERE {child_rows})");
+    let key_changed = fk
+        .ref_columns
+        .iter()
+        .map(|c| format!("OLD.{0} IS NOT NEW.{0}", c.to_sql()))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let child_columns = fk
+        .columns
+        .iter()
+        .map(|c| c.to_sql())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let ref_columns = fk
+        .ref_columns
+        .iter()
+        .map(|c| c.to_sql())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let set_null = fk
+        .columns
+        .iter()
+        .map(|c| format!("{} = NULL", c.to_sql()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut triggers = vec![
+        format!(
+            "CREATE TEMP TRIGGER {} BEFORE INSERT ON {child}\nWHEN {parent_missing}\nBEGIN\n  {raise}\nEND;",
+            trigger("insert")
+        ),
+        format!(
+            "CREATE TEMP TRIGGER {} BEFORE UPDATE OF {child_columns} ON {child}\nWHEN {parent_missing}\nBEGIN\n  {raise}\nEND;",
+            trigger("update")
+        ),
+    ];
+    triggers.push(match fk.on_delete {
+        Some(FkAction::Cascade) => format!(
+            "CREATE TEMP TRIGGER {} AFTER DELETE ON {parent}\nBEGIN\n  DELETE FROM {child_target} WHERE {child_rows};\nEND;",
+            trigger("parent_delete")
+        ),
+        Some(FkAction::SetNull) => format!(
+            "CREATE TEMP TRIGGER {} AFTER DELETE ON {parent}\nBEGIN\n  UPDATE {child_target} SET {set_null} WHERE {child_rows};\nEND;",
+            trigger("parent_delete")
+        ),
+        _ => format!(
+            "CREATE TEMP TRIGGER {} BEFORE DELETE ON {parent}\nWHEN {child_exists}\nBEGIN\n  {raise}\nEND;",
+            trigger("parent_delete")
+        ),
+    });
+    triggers.push(match fk.on_update {
+        Some(FkAction::Cascade) => format!(
+            "CREATE TEMP TRIGGER {} AFTER UPDATE OF {ref_columns} ON {parent}\nWHEN {key_changed}\nBEGIN\n  UPDATE {child_target} SET {} WHERE {child_rows};\nEND;",
+            trigger("parent_update"),
+            pairs(&fk.columns, "NEW", &fk.ref_columns, ", ")
+        ),
+        Some(FkAction::SetNull) => format!(
+            "CREATE TEMP TRIGGER {} AFTER UPDATE OF {ref_columns} ON {parent}\nWHEN {key_changed}\nBEGIN\n  UPDATE {child_target} SET {set_null} WHERE {child_rows};\nEND;",
+            trigger("parent_update")
+        ),
+        _ => format!(
+            "CREATE TEMP TRIGGER {} BEFORE UPDATE OF {ref_columns} ON {parent}\nWHEN ({key_changed}) AND {child_exists}\nBEGIN\n  {raise}\nEND;",
+            trigger("parent_update")
+        ),
+    });
+    triggers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, PgType};
+
+    fn column(name: &str, is_primary_key: bool) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type: PgType::Integer,
+            sqlite_type: None,
+            not_null: false,
+            default: None,
+            is_primary_key,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn table(schema: &str, name: &str, fks: Vec<(&str, Option<FkAction>)>) -> Table {
+        Table {
+            name: QualifiedName::with_schema(Ident::new(schema), Ident::new(name)),
+            columns: vec![column("id", true), column("ref_id", false)],
+            constraints: fks
+                .into_iter()
+                .map(|(target, on_delete)| {
+                    let (schema, table) = target.split_once('.').unwrap();
+                    TableConstraint::ForeignKey {
+                        name: None,
+                        columns: vec![Ident::new("ref_id")],
+                        ref_table: QualifiedName::with_schema(
+                            Ident::new(schema),
+                            Ident::new(table),
+                        ),
+                        ref_columns: vec![],
+                        on_delete,
+                        on_update: None,
+                        deferrable: false,
+                    }
+                })
+                .collect(),
+            without_rowid: false,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_cross_schema_fks() {
+        let mut model = SchemaModel {
+            tables: vec![
+                table("public", "users", vec![]),
+                table(
+                    "billing",
+                    "invoices",
+                    vec![("public.users", None), ("billing.invoices", None)],
+                ),
+                table(
+                    "billing",
+                    "notes",
+                    vec![("public.users", Some(FkAction::Cascade))],
+                ),
+                table(
+                    "audit",
+                    "notes",
+                    vec![("public.users", Some(FkAction::SetDefault))],
+                ),
+            ],
+            ..Default::default()
+        };
+        let mut warnings = Vec::new();
+        let triggers = extract_cross_schema_fks(&mut model, true, &mut warnings);
+
+        // The same-schema key stays; every cross-schema key is removed
+        assert_eq!(model.tables[1].constraints.len(), 1);
+        assert!(model.tables[2].constraints.is_empty());
+        assert!(model.tables[3].constraints.is_empty());
+        assert_eq!(triggers.len(), 4);
+        assert!(triggers[0].starts_with(
+            "CREATE TEMP TRIGGER fk_billing_invoices_1_insert BEFORE INSERT ON billing.invoices\n\
+             WHEN NEW.ref_id IS NOT NULL AND NOT EXISTS (SELECT 1 FROM public.users WHERE id = NEW.ref_id)"
+        ));
+        let codes: Vec<&str> = warnings.iter().map(|w| w.code).collect();
+        assert_eq!(
+            codes,
+            [
+                warning::CROSS_SCHEMA_FK_EMULATED,
+                warning::CROSS_SCHEMA_FK_DROPPED,
+                warning::CROSS_SCHEMA_FK_DROPPED,
+            ]
+        );
+        assert_eq!(
+            warnings[1].object.as_deref(),
+            Some("notes"),
+            "CASCADE onto a table name shared by two schemas"
+        );
+    }
+
+    #[test]
+    fn test_split_and_attach_script() {
+        let model = SchemaModel {
+            tables: vec![
+                table("public", "users", vec![]),
+                table("billing", "invoices", vec![]),
+                table("public", "orders", vec![]),
+            ],
+            ..Default::default()
+        };
+        let origins = vec![HashMap::new(); 3];
+        let parts = split_by_schema(model, origins);
+        let schemas: Vec<String> = parts.iter().map(|p| p.schema.clone()).collect();
+        assert_eq!(schemas, ["billing", "public"]);
+        assert_eq!(parts[1].model.tables.len(), 2);
+        assert_eq!(
+            render_attach_script(&schemas, &[], false),
+            "-- Code generated by `pg2sqlite`. DO NOT EDIT.\n\n\
+             ATTACH DATABASE 'billing.db' AS billing;\n\
+             ATTACH DATABASE 'public.db' AS public;\n"
+        );
+    }
+
+    #[test]
+    fn test_qualify_names() {
+        let sql = r#"
+            CREATE TABLE users (id INTEGER PRIMARY KEY);
+            CREATE TABLE billing.invoices (
+                id INTEGER PRIMARY KEY,
+                user_id INTEGER REFERENCES public.users(id)
+            );
+            CREATE TABLE orders (
+                id INTEGER,
+                user_id INTEGER REFERENCES users(id),
+                invoice_id INTEGER,
+                FOREIGN KEY (invoice_id) REFERENCES billing.invoices(id)
+            );
+            CREATE INDEX idx_orders_user ON orders (user_id);
+            CREATE INDEX idx_invoices_user ON billing.invoices (user_id);
+        "#;
+        let (mut model, _) = crate::pg::parser::parse(sql);
+        let catalog = Catalog::build(&model);
+        let mut warnings = Vec::new();
+        qualify_names(&mut model, &catalog, "public", &mut warnings);
+
+        let tables: Vec<String> = model.tables.iter().map(|t| t.name.to_string()).collect();
+        assert_eq!(
+            tables,
+            ["public.users", "billing.invoices", "public.orders"]
+        );
+        let orders = &model.tables[2];
+        assert_eq!(
+            orders.columns[1]
+                .references
+                .as_ref()
+                .unwrap()
+                .table
+                .to_string(),
+            "public.users"
+        );
+        assert!(matches!(
+            &orders.constraints[0],
+            TableConstraint::ForeignKey { ref_table, .. } if ref_table.to_string() == "billing.invoices"
+        ));
+        let indexes: Vec<String> = model.indexes.iter().map(|i| i.table.to_string()).collect();
+        assert_eq!(indexes, ["public.orders", "billing.invoices"]);
+
+        // Each index goes to its table's database
+        let origins = vec![HashMap::new(); model.tables.len()];
+        let parts = split_by_schema(model, origins);
+        let placed: Vec<(&str, Vec<&str>)> = parts
+            .iter()
+            .map(|p| {
+                let names = p
+                    .model
+                    .indexes
+                    .iter()
+                    .map(|i| i.name.normalized.as_str())
+                    .collect();
+                (p.schema.as_str(), names)
+            })
+            .collect();
+        assert_eq!(
+            placed,
+            [
+                ("billing", vec!["idx_invoices_user"]),
+                ("public", vec!["idx_orders_user"]),
+            ]
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_cross_schema_fks_dropped() {
+        let mut model = SchemaModel {
+            tables: vec![
+                table("billing", "invoices", vec![("public.users", None)]),
+                table("billing", "notes", vec![("public.users", None)]),
+            ],
+            ..Default::default()
+        };
+        // The parent table is not converted, so its key is unknown
+        let mut warnings = Vec::new();
+        let triggers = extract_cross_schema_fks(&mut model, true, &mut warnings);
+        assert!(triggers.is_empty());
+        assert!(model.tables.iter().all(|t| t.constraints.is_empty()));
+        let diagnostics: Vec<(&str, Severity, &str)> = warnings
+            .iter()
+            .map(|w| (w.code, w.severity, w.object.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    warning::CROSS_SCHEMA_FK_DROPPED,
+                    Severity::Unsupported,
+                    "invoices"
+                ),
+                (
+                    warning::CROSS_SCHEMA_FK_DROPPED,
+                    Severity::Unsupported,
+                    "notes"
+                ),
+            ]
+        );
+        assert!(
+            warnings[0]
+                .message
+                .contains("public.users spans attached databases")
+        );
+
+        // Without foreign keys nothing is enforced, so nothing is reported
+        let mut model = SchemaModel {
+            tables: vec![
+                table("public", "users", vec![]),
+                table("billing", "invoices", vec![("public.users", None)]),
+            ],
+            ..Default::default()
+        };
+        let mut warnings = Vec::new();
+        let triggers = extract_cross_schema_fks(&mut model, false, &mut warnings);
+        assert!(triggers.is_empty());
+        assert!(model.tables[1].constraints.is_empty());
+        assert!(warnings.is_empty());
+    }
+}
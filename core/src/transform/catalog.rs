@@ -0,0 +1,275 @@
+/// Symbol table of tables and user-defined types, keyed by qualified name.
+use std::collections::HashMap;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Ident, QualifiedName, SchemaModel, Table};
+
+/// A user-defined type, as an index into the model's list of its kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeRef {
+    Enum(usize),
+    Composite(usize),
+    Domain(usize),
+}
+
+/// Outcome of a catalog lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lookup<T> {
+    Found(T),
+    Missing,
+    /// Several schemas define the name and none of them is preferred;
+    /// holds their qualified names.
+    Ambiguous(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+struct Entry<T> {
+    schema: Option<String>,
+    qualified: String,
+    target: T,
+}
+
+/// Tables and user-defined types of a model, grouped by unqualified name.
+///
+/// Tables are recorded by qualified name, so lookups stay valid while
+/// tables are filtered or reordered; a renamed table is no longer found.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    tables: HashMap<String, Vec<Entry<QualifiedName>>>,
+    types: HashMap<String, Vec<Entry<TypeRef>>>,
+}
+
+impl Catalog {
+    /// Catalog the tables, enums, composites and domains of a model.
+    pub fn build(model: &SchemaModel) -> Self {
+        let mut catalog = Self::from_tables(&model.tables);
+        let types = model
+            .enums
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (&e.name, TypeRef::Enum(i)))
+            .chain(
+                model
+                    .composites
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| (&c.name, TypeRef::Composite(i))),
+            )
+            .chain(
+                model
+                    .domains
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| (&d.name, TypeRef::Domain(i))),
+            );
+        for (name, target) in types {
+            insert(&mut catalog.types, name, target);
+        }
+        catalog
+    }
+
+    /// Catalog tables only.
+    pub fn from_tables(tables: &[Table]) -> Self {
+        let mut catalog = Self::default();
+        for table in tables {
+            insert(&mut catalog.tables, &table.name, table.name.clone());
+        }
+        catalog
+    }
+
+    /// Find the table a reference names.
+    ///
+    /// A qualified reference only matches that schema, or a table whose
+    /// schema is unknown. An unqualified reference matches the table of
+    /// that name when there is just one, preferring one whose schema is
+    /// unknown too.
+    pub fn table(&self, name: &QualifiedName) -> Lookup<QualifiedName> {
+        let Some(entries) = self.tables.get(&name.name.normalized) else {
+            return Lookup::Missing;
+        };
+        let schema = name.schema.as_ref().map(|s| s.normalized.as_str());
+        if let Some(entry) = entries.iter().find(|e| e.schema.as_deref() == schema) {
+            return Lookup::Found(entry.target.clone());
+        }
+        match schema {
+            Some(_) => entries
+                .iter()
+                .find(|e| e.schema.is_none())
+                .map_or(Lookup::Missing, |e| Lookup::Found(e.target.clone())),
+            None => pick(entries),
+        }
+    }
+
+    /// Find the table a reference names, as its position in `tables`.
+    /// A table removed or renamed since cataloging is `Missing`.
+    pub fn table_index(&self, tables: &[Table], name: &QualifiedName) -> Lookup<usize> {
+        match self.table(name) {
+            Lookup::Found(found) => tables
+                .iter()
+                .position(|t| t.name == found)
+                .map_or(Lookup::Missing, Lookup::Found),
+            Lookup::Missing => Lookup::Missing,
+            Lookup::Ambiguous(candidates) => Lookup::Ambiguous(candidates),
+        }
+    }
+
+    /// Find the user-defined type named by a column of a table in `schema`.
+    ///
+    /// Type names are unqualified in the IR, so a type in the column's own
+    /// schema wins, then the only type of that name, then one whose schema
+    /// is unknown.
+    pub fn user_type(&self, name: &str, schema: Option<&Ident>) -> Lookup<TypeRef> {
+        let Some(entries) = self.types.get(name) else {
+            return Lookup::Missing;
+        };
+        let schema = schema.map(|s| s.normalized.as_str());
+        if let Some(entry) = entries.iter().find(|e| e.schema.as_deref() == schema) {
+            return Lookup::Found(entry.target);
+        }
+        pick(entries)
+    }
+}
+
+fn insert<T>(map: &mut HashMap<String, Vec<Entry<T>>>, name: &QualifiedName, target: T) {
+    map.entry(name.name.normalized.clone())
+        .or_default()
+        .push(Entry {
+            schema: name.schema.as_ref().map(|s| s.normalized.clone()),
+            qualified: name.to_string(),
+            target,
+        });
+}
+
+/// The sole entry, or the sole one without a schema.
+fn pick<T: Clone>(entries: &[Entry<T>]) -> Lookup<T> {
+    if let [entry] = entries {
+        return Lookup::Found(entry.target.clone());
+    }
+    let mut unqualified = entries.iter().filter(|e| e.schema.is_none());
+    match (unqualified.next(), unqualified.next()) {
+        (Some(entry), None) => Lookup::Found(entry.target.clone()),
+        _ => Lookup::Ambiguous(entries.iter().map(|e| e.qualified.clone()).collect()),
+    }
+}
+
+/// Warning for a reference the catalog could not resolve to a single object.
+pub fn ambiguous_reference(
+    name: &str,
+    candidates: &[String],
+    consequence: &str,
+    object: impl Into<String>,
+) -> Warning {
+    Warning::new(
+        warning::AMBIGUOUS_REFERENCE,
+        Severity::Unsupported,
+        format!(
+            "'{name}' is ambiguous between {}; {consequence}",
+            candidates.join(", ")
+        ),
+    )
+    .with_object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{EnumDef, Table};
+
+    fn name(schema: Option<&str>, name: &str) -> QualifiedName {
+        match schema {
+            Some(s) => QualifiedName::with_schema(Ident::new(s), Ident::new(name)),
+            None => QualifiedName::new(Ident::new(name)),
+        }
+    }
+
+    fn table(schema: Option<&str>, table_name: &str) -> Table {
+        Table {
+            name: name(schema, table_name),
+            columns: vec![],
+            constraints: vec![],
+            without_rowid: false,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_table_lookup() {
+        let tables = [
+            table(Some("public"), "accounts"),
+            table(Some("billing"), "accounts"),
+            table(Some("billing"), "invoices"),
+            table(None, "users"),
+        ];
+        let catalog = Catalog::from_tables(&tables);
+        let index = |n: QualifiedName| catalog.table_index(&tables, &n);
+        assert_eq!(index(name(Some("billing"), "accounts")), Lookup::Found(1));
+        assert_eq!(index(name(Some("public"), "accounts")), Lookup::Found(0));
+        assert_eq!(index(name(Some("audit"), "accounts")), Lookup::Missing);
+        assert_eq!(
+            index(name(None, "accounts")),
+            Lookup::Ambiguous(vec!["public.accounts".into(), "billing.accounts".into()])
+        );
+        assert_eq!(index(name(None, "invoices")), Lookup::Found(2));
+        assert_eq!(index(name(Some("public"), "invoices")), Lookup::Missing);
+        assert_eq!(index(name(Some("public"), "users")), Lookup::Found(3));
+        assert_eq!(
+            catalog.table(&name(Some("public"), "users")),
+            Lookup::Found(name(None, "users"))
+        );
+        assert_eq!(index(name(None, "orders")), Lookup::Missing);
+    }
+
+    #[test]
+    fn test_table_lookup_after_tables_change() {
+        let mut tables = vec![
+            table(Some("public"), "accounts"),
+            table(Some("billing"), "invoices"),
+            table(None, "users"),
+        ];
+        let catalog = Catalog::from_tables(&tables);
+        tables.remove(0);
+        tables.reverse();
+        tables[0].name = name(None, "members");
+
+        let index = |n: QualifiedName| catalog.table_index(&tables, &n);
+        assert_eq!(index(name(None, "invoices")), Lookup::Found(1));
+        assert_eq!(index(name(None, "accounts")), Lookup::Missing);
+        assert_eq!(index(name(None, "users")), Lookup::Missing);
+    }
+
+    #[test]
+    fn test_type_lookup() {
+        let model = SchemaModel {
+            enums: vec![
+                EnumDef {
+                    name: name(Some("public"), "mood"),
+                    values: vec!["ok".into()],
+                },
+                EnumDef {
+                    name: name(Some("billing"), "mood"),
+                    values: vec!["paid".into()],
+                },
+                EnumDef {
+                    name: name(Some("billing"), "status"),
+                    values: vec!["open".into()],
+                },
+            ],
+            ..Default::default()
+        };
+        let catalog = Catalog::build(&model);
+        let billing = Ident::new("billing");
+        assert_eq!(
+            catalog.user_type("mood", Some(&billing)),
+            Lookup::Found(TypeRef::Enum(1))
+        );
+        assert_eq!(
+            catalog.user_type("status", Some(&Ident::new("public"))),
+            Lookup::Found(TypeRef::Enum(2))
+        );
+        assert!(matches!(
+            catalog.user_type("mood", None),
+            Lookup::Ambiguous(_)
+        ));
+        assert_eq!(catalog.user_type("color", None), Lookup::Missing);
+    }
+}
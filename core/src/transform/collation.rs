@@ -0,0 +1,367 @@
+/// Collation mapping: PG column, index and expression collations → SQLite BINARY, NOCASE or RTRIM.
+use std::collections::{HashMap, HashSet};
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{CollationDef, Expr, Ident, IndexColumn, PgType, SchemaModel, TableConstraint};
+
+/// How a PG collation behaves, as far as SQLite is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Behavior {
+    /// The database default collation; nothing to emit.
+    Default,
+    /// Byte-wise ordering (`C`, `POSIX`, `ucs_basic`).
+    Binary,
+    /// Nondeterministic ICU collation at secondary strength (case-insensitive).
+    CaseInsensitive,
+    /// Locale-aware ordering SQLite cannot reproduce.
+    Unsupported,
+}
+
+/// Classify a collation name, consulting CREATE COLLATION definitions for user-defined ones.
+fn classify(name: &str, defs: &[CollationDef]) -> Behavior {
+    match name.to_lowercase().as_str() {
+        "default" => return Behavior::Default,
+        "c" | "posix" | "ucs_basic" | "pg_c_utf8" => return Behavior::Binary,
+        _ => {}
+    }
+
+    let Some(def) = defs
+        .iter()
+        .find(|d| d.name.name.raw.eq_ignore_ascii_case(name))
+    else {
+        return Behavior::Unsupported;
+    };
+    let locale = def.locale.as_deref().unwrap_or_default().to_lowercase();
+    if def.deterministic && matches!(locale.as_str(), "c" | "posix" | "c.utf8" | "c.utf-8") {
+        Behavior::Binary
+    } else if !def.deterministic
+        && (locale.contains("-ks-level2") || locale.contains("colstrength=secondary"))
+    {
+        Behavior::CaseInsensitive
+    } else {
+        Behavior::Unsupported
+    }
+}
+
+/// Map a PG collation to a SQLite collation name, or `None` to drop it.
+///
+/// Byte-wise collations on `char(n)` become RTRIM, since PG ignores the
+/// padding spaces when comparing `char(n)` values.
+fn sqlite_collation(
+    name: &str,
+    is_char: bool,
+    defs: &[CollationDef],
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<String> {
+    match classify(name, defs) {
+        Behavior::Default => None,
+        Behavior::Binary if is_char => Some("RTRIM".to_string()),
+        Behavior::Binary => Some("BINARY".to_string()),
+        Behavior::CaseInsensitive => {
+            warnings.push(
+                Warning::new(
+                    warning::COLLATION_AS_NOCASE,
+                    Severity::Lossy,
+                    format!(
+                        "case-insensitive collation '{name}' mapped to NOCASE, which only folds ASCII letters"
+                    ),
+                )
+                .with_object(object),
+            );
+            Some("NOCASE".to_string())
+        }
+        Behavior::Unsupported => {
+            warnings.push(
+                Warning::new(
+                    warning::COLLATION_UNSUPPORTED,
+                    Severity::Lossy,
+                    format!("collation '{name}' has no SQLite equivalent; using BINARY"),
+                )
+                .with_object(object),
+            );
+            None
+        }
+    }
+}
+
+/// Map the collations on columns, CHECK expressions and indexes to SQLite
+/// collations, optionally rewriting unique `lower(col)` indexes as
+/// `col COLLATE NOCASE`.
+pub fn apply_collations(
+    model: &mut SchemaModel,
+    lower_index_nocase: bool,
+    warnings: &mut Vec<Warning>,
+) {
+    let defs = &model.collations;
+    let char_columns: HashMap<String, HashSet<String>> = model
+        .tables
+        .iter()
+        .map(|t| {
+            let columns = t
+                .columns
+                .iter()
+                .filter(|c| matches!(c.pg_type, PgType::Char { .. }))
+                .map(|c| c.name.normalized.clone())
+                .collect();
+            (t.name.name.normalized.clone(), columns)
+        })
+        .collect();
+    let no_columns = HashSet::new();
+
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        let chars = &char_columns[&table_name];
+        for col in &mut table.columns {
+            let obj = format!("{}.{}", table_name, col.name.normalized);
+            if let Some(name) = col.collation.take() {
+                let is_char = matches!(col.pg_type, PgType::Char { .. });
+                col.collation = sqlite_collation(&name, is_char, defs, &obj, warnings);
+            }
+            if let Some(check) = &mut col.check {
+                map_expr_collations(check, chars, defs, &obj, warnings);
+            }
+        }
+        for constraint in &mut table.constraints {
+            if let TableConstraint::Check { expr, .. } = constraint {
+                map_expr_collations(expr, chars, defs, &table_name, warnings);
+            }
+        }
+    }
+
+    for index in &mut model.indexes {
+        let obj = index.name.normalized.clone();
+        let chars = char_columns
+            .get(&index.table.name.normalized)
+            .unwrap_or(&no_columns);
+        for column in &mut index.columns {
+            if let IndexColumn::Expression(expr) = column {
+                map_expr_collations(expr, chars, defs, &obj, warnings);
+                if let Expr::ColumnRef(name) = expr {
+                    *column = IndexColumn::Column(Ident::quoted(name));
+                }
+            }
+        }
+        if let Some(where_clause) = &mut index.where_clause {
+            map_expr_collations(where_clause, chars, defs, &obj, warnings);
+        }
+
+        if lower_index_nocase && index.unique {
+            for column in &mut index.columns {
+                let IndexColumn::Expression(Expr::FunctionCall { name, args }) = column else {
+                    continue;
+                };
+                let [Expr::ColumnRef(col)] = args.as_slice() else {
+                    continue;
+                };
+                if name != "lower" {
+                    continue;
+                }
+                warnings.push(
+                    Warning::new(
+                        warning::COLLATION_AS_NOCASE,
+                        Severity::Lossy,
+                        format!(
+                            "unique index on lower({col}) rewritten as {col} COLLATE NOCASE, which only folds ASCII letters"
+                        ),
+                    )
+                    .with_object(&obj),
+                );
+                *column = IndexColumn::Expression(Expr::Collate {
+                    expr: Box::new(Expr::ColumnRef(col.clone())),
+                    collation: "NOCASE".to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Map every COLLATE in an expression, unwrapping the ones SQLite does not need.
+fn map_expr_collations(
+    expr: &mut Expr,
+    char_columns: &HashSet<String>,
+    defs: &[CollationDef],
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) {
+    expr.walk_mut(&mut |e| {
+        let Expr::Collate {
+            expr: inner,
+            collation,
+        } = e
+        else {
+            return;
+        };
+        let is_char = matches!(&**inner, Expr::ColumnRef(c) if char_columns.contains(c));
+        match sqlite_collation(collation, is_char, defs, object, warnings) {
+            Some(mapped) => *collation = mapped,
+            None => {
+                let inner = std::mem::replace(inner.as_mut(), Expr::Null);
+                *e = inner;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, Index, QualifiedName, Table};
+
+    fn column(name: &str, pg_type: PgType, collation: Option<&str>) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type: None,
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: collation.map(str::to_string),
+        }
+    }
+
+    fn model(columns: Vec<Column>, indexes: Vec<Index>) -> SchemaModel {
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("users")),
+                columns,
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            indexes,
+            ..Default::default()
+        }
+    }
+
+    fn index(columns: Vec<IndexColumn>, unique: bool) -> Index {
+        Index {
+            name: Ident::new("idx"),
+            table: QualifiedName::new(Ident::new("users")),
+            columns,
+            unique,
+            method: None,
+            where_clause: None,
+        }
+    }
+
+    #[test]
+    fn test_builtin_collations() {
+        let mut m = model(
+            vec![
+                column("a", PgType::Text, Some("C")),
+                column("b", PgType::Char { length: Some(3) }, Some("POSIX")),
+                column("c", PgType::Text, Some("default")),
+            ],
+            vec![],
+        );
+        let mut w = Vec::new();
+        apply_collations(&mut m, false, &mut w);
+        let cols = &m.tables[0].columns;
+        assert_eq!(cols[0].collation.as_deref(), Some("BINARY"));
+        assert_eq!(cols[1].collation.as_deref(), Some("RTRIM"));
+        assert_eq!(cols[2].collation, None);
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_icu_case_insensitive_collation() {
+        let mut m = model(
+            vec![
+                column("email", PgType::Text, Some("case_insensitive")),
+                column("name", PgType::Text, Some("en-x-icu")),
+            ],
+            vec![],
+        );
+        m.collations.push(CollationDef {
+            name: QualifiedName::with_schema(Ident::new("public"), Ident::new("case_insensitive")),
+            locale: Some("und-u-ks-level2".to_string()),
+            deterministic: false,
+        });
+        let mut w = Vec::new();
+        apply_collations(&mut m, false, &mut w);
+        let cols = &m.tables[0].columns;
+        assert_eq!(cols[0].collation.as_deref(), Some("NOCASE"));
+        assert_eq!(cols[1].collation, None);
+        assert!(w.iter().any(|w| w.code == warning::COLLATION_AS_NOCASE));
+        assert!(w.iter().any(|w| w.code == warning::COLLATION_UNSUPPORTED
+            && w.object.as_deref() == Some("users.name")));
+    }
+
+    #[test]
+    fn test_deterministic_level2_collation_unsupported() {
+        let mut m = model(vec![column("a", PgType::Text, Some("ci"))], vec![]);
+        m.collations.push(CollationDef {
+            name: QualifiedName::new(Ident::new("ci")),
+            locale: Some("und-u-ks-level2".to_string()),
+            deterministic: true,
+        });
+        let mut w = Vec::new();
+        apply_collations(&mut m, false, &mut w);
+        assert_eq!(m.tables[0].columns[0].collation, None);
+        assert_eq!(w[0].code, warning::COLLATION_UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_index_expression_collations() {
+        let collate = |name: &str, collation: &str| {
+            IndexColumn::Expression(Expr::Collate {
+                expr: Box::new(Expr::ColumnRef(name.to_string())),
+                collation: collation.to_string(),
+            })
+        };
+        let mut m = model(
+            vec![
+                column("code", PgType::Char { length: Some(2) }, None),
+                column("name", PgType::Text, None),
+            ],
+            vec![index(
+                vec![collate("code", "C"), collate("name", "de_DE")],
+                false,
+            )],
+        );
+        let mut w = Vec::new();
+        apply_collations(&mut m, false, &mut w);
+        let cols = &m.indexes[0].columns;
+        assert!(
+            matches!(&cols[0], IndexColumn::Expression(e) if e.to_sql() == "code COLLATE RTRIM")
+        );
+        assert!(matches!(&cols[1], IndexColumn::Column(c) if c.normalized == "name"));
+        assert_eq!(w.len(), 1);
+    }
+
+    #[test]
+    fn test_lower_unique_index_rewrite() {
+        let lower = || {
+            IndexColumn::Expression(Expr::func(
+                "lower",
+                vec![Expr::ColumnRef("email".to_string())],
+            ))
+        };
+        let mut m = model(
+            vec![column("email", PgType::Text, None)],
+            vec![index(vec![lower()], true), index(vec![lower()], false)],
+        );
+        let mut w = Vec::new();
+        apply_collations(&mut m, true, &mut w);
+        assert!(
+            matches!(&m.indexes[0].columns[0], IndexColumn::Expression(e) if e.to_sql() == "email COLLATE NOCASE")
+        );
+        assert!(
+            matches!(&m.indexes[1].columns[0], IndexColumn::Expression(e) if e.to_sql() == "lower(email)")
+        );
+
+        let mut m = model(
+            vec![column("email", PgType::Text, None)],
+            vec![index(vec![lower()], true)],
+        );
+        apply_collations(&mut m, false, &mut Vec::new());
+        assert!(
+            matches!(&m.indexes[0].columns[0], IndexColumn::Expression(e) if e.to_sql() == "lower(email)")
+        );
+    }
+}
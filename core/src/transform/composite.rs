@@ -0,0 +1,827 @@
+/// Composite types: store as JSON objects or expand into prefixed columns.
+use std::collections::HashMap;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{
+    Column, CompositeDef, Expr, Ident, IndexColumn, PgType, SchemaModel, TableConstraint,
+};
+use crate::transform::catalog::{Catalog, Lookup, TypeRef};
+use crate::transform::extension::{json_object_check, json_string};
+
+/// Storage representation for composite-typed columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompositeMode {
+    /// JSON object TEXT with a `json_valid` CHECK (default).
+    #[default]
+    Json,
+    /// One `<col>_<attr>` column per attribute, recursing into nested composites.
+    Columns,
+}
+
+impl std::str::FromStr for CompositeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(CompositeMode::Json),
+            "columns" => Ok(CompositeMode::Columns),
+            other => Err(format!(
+                "unknown composite mode '{other}' (expected json or columns)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for CompositeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompositeMode::Json => write!(f, "json"),
+            CompositeMode::Columns => write!(f, "columns"),
+        }
+    }
+}
+
+type Composites = HashMap<String, CompositeDef>;
+
+/// A composite column and, in columns mode, the leaf columns it expands into.
+struct CompositeColumn {
+    type_name: String,
+    /// Attribute path (normalized names) → expanded column.
+    leaves: Vec<(Vec<String>, Ident)>,
+}
+
+/// A default value decomposed along a composite type.
+enum FieldValue {
+    Null,
+    Scalar(Expr),
+    Record(Vec<FieldValue>),
+}
+
+/// Store composite columns as JSON objects or expand them into attribute columns,
+/// rewriting field accesses in CHECKs and indexes.
+pub fn apply_composite_mode(
+    model: &mut SchemaModel,
+    catalog: &Catalog,
+    mode: CompositeMode,
+    warnings: &mut Vec<Warning>,
+) {
+    let composites: Composites = model
+        .composites
+        .iter()
+        .map(|c| (c.name.name.normalized.clone(), c.clone()))
+        .collect();
+    let mut by_table: HashMap<String, HashMap<String, CompositeColumn>> = HashMap::new();
+
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        let mut found: HashMap<String, CompositeColumn> = HashMap::new();
+        let mut columns = Vec::with_capacity(table.columns.len());
+        let mut extra_constraints = Vec::new();
+
+        for mut col in std::mem::take(&mut table.columns) {
+            let PgType::Composite { name } = &col.pg_type else {
+                columns.push(col);
+                continue;
+            };
+            let Lookup::Found(TypeRef::Composite(idx)) =
+                catalog.user_type(name, table.name.schema.as_ref())
+            else {
+                columns.push(col);
+                continue;
+            };
+            let def = &model.composites[idx];
+            let obj = format!("{}.{}", table_name, col.name.normalized);
+            let type_name = name.clone();
+            let values = col.default.as_ref().map(|d| decompose(d, def, &composites));
+            if let Some(None) = values {
+                warnings.push(unsupported_default(&type_name, &obj));
+            }
+            let values = values.flatten();
+
+            if mode == CompositeMode::Json {
+                col.default =
+                    values.map(|v| Expr::StringLiteral(record_json(&v, def, &composites)));
+                col.add_check(Expr::native(json_object_check(Expr::ColumnRef(
+                    col.name.normalized.clone(),
+                ))));
+                found.insert(
+                    col.name.normalized.clone(),
+                    CompositeColumn {
+                        type_name,
+                        leaves: Vec::new(),
+                    },
+                );
+                columns.push(col);
+                continue;
+            }
+
+            let mut leaves = Vec::new();
+            collect_leaves(&col.name, &[], def, &composites, &mut leaves);
+            let mut defaults = Vec::new();
+            if let Some(values) = &values {
+                flatten_values(values, &mut defaults);
+            }
+            warnings.push(
+                Warning::new(
+                    warning::COMPOSITE_EXPANDED,
+                    Severity::Info,
+                    format!(
+                        "composite type '{type_name}' expanded into {} columns{}",
+                        leaves
+                            .iter()
+                            .map(|(_, ident, _)| ident.normalized.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        if col.not_null {
+                            "; NOT NULL not enforced"
+                        } else {
+                            ""
+                        }
+                    ),
+                )
+                .with_object(&obj),
+            );
+            for (i, (_, ident, pg_type)) in leaves.iter().enumerate() {
+                columns.push(Column {
+                    name: ident.clone(),
+                    pg_type: pg_type.clone(),
+                    sqlite_type: None,
+                    not_null: false,
+                    default: defaults.get(i).cloned().flatten(),
+                    is_primary_key: false,
+                    is_unique: false,
+                    autoincrement: false,
+                    references: None,
+                    check: None,
+                    collation: None,
+                });
+            }
+
+            // Column-level constraints move to the table level
+            let leaf_idents: Vec<Ident> = leaves.iter().map(|(_, i, _)| i.clone()).collect();
+            if col.is_primary_key {
+                extra_constraints.push(TableConstraint::PrimaryKey {
+                    name: None,
+                    columns: leaf_idents.clone(),
+                });
+            }
+            if col.is_unique {
+                extra_constraints.push(TableConstraint::Unique {
+                    name: None,
+                    columns: leaf_idents,
+                });
+            }
+            if let Some(check) = col.check {
+                extra_constraints.push(TableConstraint::Check {
+                    name: None,
+                    expr: check,
+                });
+            }
+
+            found.insert(
+                col.name.normalized.clone(),
+                CompositeColumn {
+                    type_name,
+                    leaves: leaves
+                        .into_iter()
+                        .map(|(path, ident, _)| (path, ident))
+                        .collect(),
+                },
+            );
+        }
+        table.columns = columns;
+
+        if found.is_empty() {
+            continue;
+        }
+
+        let mut kept = Vec::new();
+        for constraint in std::mem::take(&mut table.constraints)
+            .into_iter()
+            .chain(extra_constraints)
+        {
+            match constraint {
+                TableConstraint::PrimaryKey { name, columns } => {
+                    kept.push(TableConstraint::PrimaryKey {
+                        name,
+                        columns: expand_columns(&columns, &found),
+                    });
+                }
+                TableConstraint::Unique { name, columns } => {
+                    kept.push(TableConstraint::Unique {
+                        name,
+                        columns: expand_columns(&columns, &found),
+                    });
+                }
+                TableConstraint::Check { name, mut expr } => {
+                    match rewrite_expr(&mut expr, &found, &composites, mode) {
+                        Ok(()) => kept.push(TableConstraint::Check { name, expr }),
+                        Err(column) => {
+                            warnings.push(check_dropped(&column, &found, &table_name));
+                        }
+                    }
+                }
+                TableConstraint::ForeignKey {
+                    name,
+                    columns,
+                    ref_table,
+                    ref_columns,
+                    on_delete,
+                    on_update,
+                    deferrable,
+                } => kept.push(TableConstraint::ForeignKey {
+                    name,
+                    columns: expand_columns(&columns, &found),
+                    ref_table,
+                    ref_columns,
+                    on_delete,
+                    on_update,
+                    deferrable,
+                }),
+            }
+        }
+        table.constraints = kept;
+
+        for col in &mut table.columns {
+            if let Some(check) = &mut col.check
+                && let Err(column) = rewrite_expr(check, &found, &composites, mode)
+            {
+                let obj = format!("{}.{}", table_name, col.name.normalized);
+                warnings.push(check_dropped(&column, &found, &obj));
+                col.check = None;
+            }
+        }
+
+        by_table.insert(table_name, found);
+    }
+
+    // Indexes: expand plain composite columns, rewrite field accesses
+    model.indexes.retain_mut(|index| {
+        let Some(found) = by_table.get(&index.table.name.normalized) else {
+            return true;
+        };
+        let mut columns = Vec::new();
+        for col in std::mem::take(&mut index.columns) {
+            match col {
+                IndexColumn::Column(ident) => match found.get(&ident.normalized) {
+                    Some(composite) if mode == CompositeMode::Columns => columns.extend(
+                        composite
+                            .leaves
+                            .iter()
+                            .map(|(_, leaf)| IndexColumn::Column(leaf.clone())),
+                    ),
+                    _ => columns.push(IndexColumn::Column(ident)),
+                },
+                IndexColumn::Expression(mut expr) => {
+                    if let Err(column) = rewrite_expr(&mut expr, found, &composites, mode) {
+                        warnings.push(index_dropped(&index.name, &column, found));
+                        return false;
+                    }
+                    while let Expr::Nested(inner) = expr {
+                        expr = *inner;
+                    }
+                    columns.push(match expr {
+                        Expr::ColumnRef(name) => IndexColumn::Column(Ident {
+                            raw: name.clone(),
+                            normalized: name,
+                        }),
+                        expr => IndexColumn::Expression(expr),
+                    });
+                }
+            }
+        }
+        index.columns = columns;
+
+        if let Some(where_clause) = &mut index.where_clause
+            && let Err(column) = rewrite_expr(where_clause, found, &composites, mode)
+        {
+            warnings.push(index_dropped(&index.name, &column, found));
+            return false;
+        }
+        true
+    });
+}
+
+fn unsupported_default(type_name: &str, object: &str) -> Warning {
+    Warning::new(
+        warning::COMPOSITE_EXPR_UNSUPPORTED,
+        Severity::Unsupported,
+        format!("default for composite type '{type_name}' is not a row literal; dropped"),
+    )
+    .with_object(object)
+}
+
+fn check_dropped(column: &str, found: &HashMap<String, CompositeColumn>, object: &str) -> Warning {
+    Warning::new(
+        warning::COMPOSITE_EXPR_UNSUPPORTED,
+        Severity::Unsupported,
+        format!(
+            "CHECK uses composite column '{column}' ({}) as a whole; dropped",
+            found[column].type_name
+        ),
+    )
+    .with_object(object)
+}
+
+fn index_dropped(index: &Ident, column: &str, found: &HashMap<String, CompositeColumn>) -> Warning {
+    Warning::new(
+        warning::COMPOSITE_EXPR_UNSUPPORTED,
+        Severity::Unsupported,
+        format!(
+            "index uses composite column '{column}' ({}) as a whole; index skipped",
+            found[column].type_name
+        ),
+    )
+    .with_object(&index.normalized)
+}
+
+/// Collect the leaf columns of a composite, prefixing names with the column name.
+fn collect_leaves(
+    column: &Ident,
+    path: &[String],
+    def: &CompositeDef,
+    composites: &Composites,
+    leaves: &mut Vec<(Vec<String>, Ident, PgType)>,
+) {
+    for attr in &def.attributes {
+        let mut attr_path = path.to_vec();
+        attr_path.push(attr.name.normalized.clone());
+        match &attr.pg_type {
+            PgType::Composite { name } if composites.contains_key(name) => {
+                collect_leaves(column, &attr_path, &composites[name], composites, leaves);
+            }
+            pg_type => {
+                let ident = Ident {
+                    raw: format!("{}_{}", column.raw, attr_path.join("_")),
+                    normalized: format!("{}_{}", column.normalized, attr_path.join("_")),
+                };
+                leaves.push((attr_path, ident, pg_type.clone()));
+            }
+        }
+    }
+}
+
+/// Replace each composite column in a column list with its leaf columns.
+fn expand_columns(columns: &[Ident], found: &HashMap<String, CompositeColumn>) -> Vec<Ident> {
+    columns
+        .iter()
+        .flat_map(|c| match found.get(&c.normalized) {
+            Some(composite) if !composite.leaves.is_empty() => composite
+                .leaves
+                .iter()
+                .map(|(_, leaf)| leaf.clone())
+                .collect(),
+            _ => vec![c.clone()],
+        })
+        .collect()
+}
+
+/// Rewrite `(col).a.b` field accesses: `json_extract(col, '$.a.b')` in JSON mode,
+/// the expanded `col_a_b` column in columns mode. Returns the name of a composite
+/// column that is used in a way the mode cannot express.
+fn rewrite_expr(
+    expr: &mut Expr,
+    found: &HashMap<String, CompositeColumn>,
+    composites: &Composites,
+    mode: CompositeMode,
+) -> Result<(), String> {
+    let mut unsupported = None;
+    expr.walk_mut(&mut |node| match node {
+        Expr::FieldAccess { .. } => {
+            let mut fields = Vec::new();
+            let mut root = &*node;
+            loop {
+                match root {
+                    Expr::FieldAccess { expr, field } => {
+                        fields.push(field.clone());
+                        root = expr;
+                    }
+                    Expr::Nested(expr) => root = expr,
+                    _ => break,
+                }
+            }
+            fields.reverse();
+            let Expr::ColumnRef(column) = root else {
+                return;
+            };
+            let column = column.clone();
+            let Some(composite) = found.get(&column) else {
+                return;
+            };
+            let replacement = match mode {
+                CompositeMode::Json => {
+                    valid_path(&composite.type_name, &fields, composites).then(|| {
+                        Expr::native(Expr::func(
+                            "json_extract",
+                            vec![
+                                Expr::ColumnRef(column.clone()),
+                                Expr::StringLiteral(format!("$.{}", fields.join("."))),
+                            ],
+                        ))
+                    })
+                }
+                CompositeMode::Columns => composite
+                    .leaves
+                    .iter()
+                    .find(|(path, _)| *path == fields)
+                    .map(|(_, leaf)| Expr::ColumnRef(leaf.normalized.clone())),
+            };
+            match replacement {
+                Some(replacement) => *node = replacement,
+                None => {
+                    unsupported.get_or_insert(column);
+                }
+            }
+        }
+        Expr::ColumnRef(column)
+            if mode == CompositeMode::Columns && found.contains_key(column.as_str()) =>
+        {
+            unsupported.get_or_insert_with(|| column.clone());
+        }
+        _ => {}
+    });
+    match unsupported {
+        Some(column) => Err(column),
+        None => Ok(()),
+    }
+}
+
+/// True if `fields` names a chain of attributes starting at composite `type_name`.
+fn valid_path(type_name: &str, fields: &[String], composites: &Composites) -> bool {
+    let mut current = type_name;
+    for field in fields {
+        let Some(attr) = composites
+            .get(current)
+            .and_then(|def| def.attributes.iter().find(|a| a.name.normalized == *field))
+        else {
+            return false;
+        };
+        if let PgType::Composite { name } = &attr.pg_type {
+            current = name;
+        } else {
+            current = "";
+        }
+    }
+    true
+}
+
+/// Decompose a composite default — `ROW(...)`, `(...)` or a `'(...)'` record
+/// literal — into one value per attribute.
+fn decompose(expr: &Expr, def: &CompositeDef, composites: &Composites) -> Option<Vec<FieldValue>> {
+    let fields: Vec<Expr> = match expr {
+        Expr::Cast { expr, .. } | Expr::Nested(expr) => return decompose(expr, def, composites),
+        Expr::FunctionCall { name, args } if name == "row" => args.clone(),
+        Expr::StringLiteral(text) => parse_record(text)?
+            .into_iter()
+            .map(|field| field.map_or(Expr::Null, Expr::StringLiteral))
+            .collect(),
+        _ => return None,
+    };
+    if fields.len() != def.attributes.len() {
+        return None;
+    }
+    fields
+        .iter()
+        .zip(&def.attributes)
+        .map(|(field, attr)| field_value(field, &attr.pg_type, composites))
+        .collect()
+}
+
+fn field_value(expr: &Expr, pg_type: &PgType, composites: &Composites) -> Option<FieldValue> {
+    match expr {
+        Expr::Null => return Some(FieldValue::Null),
+        Expr::Cast { expr, .. } | Expr::Nested(expr) => {
+            return field_value(expr, pg_type, composites);
+        }
+        _ => {}
+    }
+    if let PgType::Composite { name } = pg_type {
+        let def = composites.get(name)?;
+        return decompose(expr, def, composites).map(FieldValue::Record);
+    }
+    let scalar = match expr {
+        Expr::StringLiteral(s) => typed_literal(s, pg_type),
+        Expr::IntegerLiteral(_) | Expr::FloatLiteral(_) | Expr::BooleanLiteral(_) => expr.clone(),
+        Expr::UnaryOp { op, expr: inner } if op == "-" => match &**inner {
+            Expr::IntegerLiteral(n) => Expr::IntegerLiteral(-n),
+            Expr::FloatLiteral(n) => Expr::FloatLiteral(-n),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(FieldValue::Scalar(scalar))
+}
+
+/// Interpret a record field's text according to the attribute type.
+fn typed_literal(text: &str, pg_type: &PgType) -> Expr {
+    let numeric = matches!(
+        pg_type,
+        PgType::SmallInt
+            | PgType::Integer
+            | PgType::BigInt
+            | PgType::Real
+            | PgType::DoublePrecision
+            | PgType::Numeric { .. }
+    );
+    if numeric {
+        if let Ok(n) = text.trim().parse::<i64>() {
+            return Expr::IntegerLiteral(n);
+        }
+        if let Ok(n) = text.trim().parse::<f64>() {
+            return Expr::FloatLiteral(n);
+        }
+    }
+    if *pg_type == PgType::Boolean {
+        match text.trim().to_lowercase().as_str() {
+            "t" | "true" | "yes" | "on" | "1" => return Expr::BooleanLiteral(true),
+            "f" | "false" | "no" | "off" | "0" => return Expr::BooleanLiteral(false),
+            _ => {}
+        }
+    }
+    Expr::StringLiteral(text.to_string())
+}
+
+/// Render decomposed values as a JSON object keyed by attribute name.
+fn record_json(values: &[FieldValue], def: &CompositeDef, composites: &Composites) -> String {
+    let body: Vec<String> = values
+        .iter()
+        .zip(&def.attributes)
+        .map(|(value, attr)| {
+            let value = match (value, &attr.pg_type) {
+                (FieldValue::Null, _) => "null".to_string(),
+                (FieldValue::Scalar(Expr::StringLiteral(s)), _) => json_string(s),
+                (FieldValue::Scalar(expr), _) => match expr {
+                    Expr::BooleanLiteral(b) => b.to_string(),
+                    expr => expr.to_sql(),
+                },
+                (FieldValue::Record(fields), PgType::Composite { name }) => {
+                    record_json(fields, &composites[name], composites)
+                }
+                (FieldValue::Record(_), _) => "null".to_string(),
+            };
+            format!("{}:{value}", json_string(&attr.name.normalized))
+        })
+        .collect();
+    format!("{{{}}}", body.join(","))
+}
+
+/// Flatten decomposed values in leaf-column order; NULL fields have no default.
+fn flatten_values(values: &[FieldValue], out: &mut Vec<Option<Expr>>) {
+    for value in values {
+        match value {
+            FieldValue::Null => out.push(None),
+            FieldValue::Scalar(expr) => out.push(Some(expr.clone())),
+            FieldValue::Record(fields) => flatten_values(fields, out),
+        }
+    }
+}
+
+/// Split a PG record literal `(a,"b c",)` into its fields; unquoted empty fields are NULL.
+fn parse_record(text: &str) -> Option<Vec<Option<String>>> {
+    let inner = text.trim().strip_prefix('(')?.strip_suffix(')')?;
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = inner.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                current.push('"');
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            '\\' => current.push(chars.next()?),
+            ',' if !in_quotes => {
+                fields.push((quoted || !current.is_empty()).then(|| std::mem::take(&mut current)));
+                quoted = false;
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    fields.push((quoted || !current.is_empty()).then_some(current));
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{CompositeAttribute, Index, QualifiedName, Table};
+
+    fn composite(name: &str, attrs: Vec<(&str, PgType)>) -> CompositeDef {
+        CompositeDef {
+            name: QualifiedName::new(Ident::new(name)),
+            attributes: attrs
+                .into_iter()
+                .map(|(n, t)| CompositeAttribute {
+                    name: Ident::new(n),
+                    pg_type: t,
+                })
+                .collect(),
+        }
+    }
+
+    fn column(name: &str, pg_type: PgType, default: Option<Expr>) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type: None,
+            not_null: false,
+            default,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn address_model(default: Option<Expr>) -> SchemaModel {
+        let address = PgType::Composite {
+            name: "address".to_string(),
+        };
+        let zip = Expr::FieldAccess {
+            expr: Box::new(Expr::Nested(Box::new(Expr::ColumnRef("addr".to_string())))),
+            field: "zip".to_string(),
+        };
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("shops")),
+                columns: vec![
+                    column("id", PgType::Integer, None),
+                    column("addr", address, default),
+                ],
+                constraints: vec![TableConstraint::Check {
+                    name: None,
+                    expr: Expr::binary(zip.clone(), "<>", Expr::StringLiteral(String::new())),
+                }],
+                without_rowid: false,
+                note: None,
+            }],
+            indexes: vec![Index {
+                name: Ident::new("shops_zip"),
+                table: QualifiedName::new(Ident::new("shops")),
+                columns: vec![IndexColumn::Expression(zip)],
+                unique: false,
+                method: None,
+                where_clause: None,
+            }],
+            composites: vec![
+                composite(
+                    "address",
+                    vec![
+                        ("street", PgType::Text),
+                        ("zip", PgType::Text),
+                        (
+                            "location",
+                            PgType::Composite {
+                                name: "geo".to_string(),
+                            },
+                        ),
+                    ],
+                ),
+                composite(
+                    "geo",
+                    vec![
+                        ("lat", PgType::DoublePrecision),
+                        ("lng", PgType::DoublePrecision),
+                    ],
+                ),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_composite_mode_from_str() {
+        assert_eq!("json".parse(), Ok(CompositeMode::Json));
+        assert_eq!("Columns".parse(), Ok(CompositeMode::Columns));
+        assert!("flat".parse::<CompositeMode>().is_err());
+    }
+
+    #[test]
+    fn test_json_mode() {
+        let default = Expr::func(
+            "row",
+            vec![
+                Expr::StringLiteral("Main St".to_string()),
+                Expr::Null,
+                Expr::func(
+                    "row",
+                    vec![Expr::FloatLiteral(1.5), Expr::IntegerLiteral(2)],
+                ),
+            ],
+        );
+        let mut model = address_model(Some(default));
+        let mut w = Vec::new();
+        let catalog = Catalog::build(&model);
+        apply_composite_mode(&mut model, &catalog, CompositeMode::Json, &mut w);
+        let addr = &model.tables[0].columns[1];
+        assert_eq!(
+            addr.default,
+            Some(Expr::StringLiteral(
+                r#"{"street":"Main St","zip":null,"location":{"lat":1.5,"lng":2}}"#.to_string()
+            ))
+        );
+        assert!(
+            addr.check
+                .as_ref()
+                .unwrap()
+                .to_sql()
+                .contains("json_valid(addr)")
+        );
+        let TableConstraint::Check { expr, .. } = &model.tables[0].constraints[0] else {
+            panic!("expected CHECK");
+        };
+        assert_eq!(expr.to_sql(), "json_extract(addr, '$.zip') <> ''");
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_columns_mode() {
+        let default = Expr::Cast {
+            expr: Box::new(Expr::StringLiteral(
+                r#"("1 ""A"" Rd",,"(3,4)")"#.to_string(),
+            )),
+            type_name: "address".to_string(),
+        };
+        let mut model = address_model(Some(default));
+        let mut w = Vec::new();
+        let catalog = Catalog::build(&model);
+        apply_composite_mode(&mut model, &catalog, CompositeMode::Columns, &mut w);
+        let names: Vec<&str> = model.tables[0]
+            .columns
+            .iter()
+            .map(|c| c.name.normalized.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "id",
+                "addr_street",
+                "addr_zip",
+                "addr_location_lat",
+                "addr_location_lng"
+            ]
+        );
+        let cols = &model.tables[0].columns;
+        assert_eq!(
+            cols[1].default,
+            Some(Expr::StringLiteral("1 \"A\" Rd".to_string()))
+        );
+        assert_eq!(cols[2].default, None);
+        assert_eq!(cols[3].default, Some(Expr::IntegerLiteral(3)));
+        assert_eq!(cols[4].pg_type, PgType::DoublePrecision);
+
+        let TableConstraint::Check { expr, .. } = &model.tables[0].constraints[0] else {
+            panic!("expected CHECK");
+        };
+        assert_eq!(expr.to_sql(), "addr_zip <> ''");
+        assert!(
+            matches!(&model.indexes[0].columns[..], [IndexColumn::Column(c)] if c.normalized == "addr_zip")
+        );
+        assert_eq!(w[0].code, warning::COMPOSITE_EXPANDED);
+    }
+
+    #[test]
+    fn test_columns_mode_whole_value_unsupported() {
+        let mut model = address_model(Some(Expr::func("make_address", vec![])));
+        model.indexes[0].columns = vec![IndexColumn::Expression(Expr::func(
+            "md5",
+            vec![Expr::ColumnRef("addr".to_string())],
+        ))];
+        let mut w = Vec::new();
+        let catalog = Catalog::build(&model);
+        apply_composite_mode(&mut model, &catalog, CompositeMode::Columns, &mut w);
+        assert!(model.indexes.is_empty());
+        assert!(model.tables[0].columns.iter().all(|c| c.default.is_none()));
+        let unsupported = w
+            .iter()
+            .filter(|w| w.code == warning::COMPOSITE_EXPR_UNSUPPORTED)
+            .count();
+        assert_eq!(unsupported, 2);
+    }
+
+    #[test]
+    fn test_parse_record() {
+        assert_eq!(
+            parse_record(r#"(a,"b,c",,"")"#),
+            Some(vec![
+                Some("a".to_string()),
+                Some("b,c".to_string()),
+                None,
+                Some(String::new())
+            ])
+        );
+        assert_eq!(parse_record("(\"open)"), None);
+        assert_eq!(parse_record("a,b"), None);
+    }
+}
@@ -153,6 +153,7 @@ mod tests {
             autoincrement: false,
             references: None,
             check: None,
+            collation: None,
         }
     }
 
@@ -161,6 +162,8 @@ mod tests {
             name: QualifiedName::new(Ident::new(name)),
             columns,
             constraints,
+            without_rowid: false,
+            note: None,
         }
     }
 
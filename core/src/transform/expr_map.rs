@@ -1,6 +1,12 @@
 /// PostgreSQL expression → SQLite expression conversion.
+use std::collections::{HashMap, HashSet};
+
 use crate::diagnostics::warning::{self, Severity, Warning};
-use crate::ir::Expr;
+use crate::ir::{Expr, IndexColumn, LikeKind, PgType, SchemaModel, SqliteType, TableConstraint};
+use crate::pg::parser::parse_type_name;
+use crate::transform::functions::{self, Translation};
+use crate::transform::type_map::UuidMode;
+use crate::transform::{interval, regex, uuid};
 
 /// Convert a PG expression to a SQLite-compatible expression.
 /// Returns None if the expression should be dropped entirely.
@@ -36,40 +42,43 @@ pub fn map_expr(expr: &Expr, object: &str, warnings: &mut Vec<Warning>) -> Optio
         // Function calls
         Expr::FunctionCall { name, args } => map_function_call(name, args, object, warnings),
 
-        // Cast → strip the cast, keep the inner expression
+        // Cast → SQLite CAST, or removed when it doesn't change the value
         Expr::Cast {
             expr: inner,
             type_name,
         } => {
-            warnings.push(
-                Warning::new(
-                    warning::CAST_REMOVED,
-                    Severity::Info,
-                    format!("cast to {type_name} removed"),
-                )
-                .with_object(object),
-            );
-            map_expr(inner, object, warnings)
+            let mapped = map_expr(inner, object, warnings)?;
+            Some(map_cast(mapped, type_name, object, warnings))
         }
 
-        // Binary operations — recursively convert both sides
+        // Binary operations — translate the operator, converting both sides;
+        // now() ± interval '…' becomes datetime('now', '±N unit')
         Expr::BinaryOp { left, op, right } => {
-            let left = map_expr(left, object, warnings)?;
-            let right = map_expr(right, object, warnings)?;
-            Some(Expr::BinaryOp {
-                left: std::boxed::Box::new(left),
-                op: op.clone(),
-                right: std::boxed::Box::new(right),
-            })
+            if let Some(mapped) = interval::datetime_arithmetic(left, op, right) {
+                Some(mapped)
+            } else {
+                map_binary_op(left, op, right, object, warnings)
+            }
         }
 
         // Unary operations
         Expr::UnaryOp { op, expr: inner } => {
             let mapped = map_expr(inner, object, warnings)?;
-            Some(Expr::UnaryOp {
-                op: op.clone(),
-                expr: std::boxed::Box::new(mapped),
-            })
+            match op.as_str() {
+                "-" | "+" | "~" | "NOT" => Some(Expr::UnaryOp {
+                    op: op.clone(),
+                    expr: std::boxed::Box::new(mapped),
+                }),
+                // @x → abs(x)
+                "@" => Some(Expr::func("abs", vec![mapped])),
+                _ => {
+                    warnings.push(unsupported(
+                        format!("operator '{op}' has no SQLite equivalent"),
+                        object,
+                    ));
+                    None
+                }
+            }
         }
 
         // IS NULL / IS NOT NULL
@@ -126,47 +135,791 @@ pub fn map_expr(expr: &Expr, object: &str, warnings: &mut Vec<Warning>) -> Optio
             Some(Expr::Nested(std::boxed::Box::new(mapped)))
         }
 
+        // Composite field access left over from the composite transform
+        Expr::FieldAccess { field, .. } => {
+            warnings.push(
+                Warning::new(
+                    warning::COMPOSITE_EXPR_UNSUPPORTED,
+                    Severity::Unsupported,
+                    format!("composite field access '.{field}' is not supported"),
+                )
+                .with_object(object),
+            );
+            None
+        }
+
+        // COLLATE — collation names are mapped by the collation transform
+        Expr::Collate {
+            expr: inner,
+            collation,
+        } => {
+            let mapped = map_expr(inner, object, warnings)?;
+            Some(Expr::Collate {
+                expr: std::boxed::Box::new(mapped),
+                collation: collation.clone(),
+            })
+        }
+
+        // CASE — SQLite has the same syntax
+        Expr::Case {
+            operand,
+            branches,
+            else_result,
+        } => {
+            let operand = match operand {
+                Some(operand) => Some(std::boxed::Box::new(map_expr(operand, object, warnings)?)),
+                None => None,
+            };
+            let branches = branches
+                .iter()
+                .map(|(condition, result)| {
+                    Some((
+                        map_expr(condition, object, warnings)?,
+                        map_expr(result, object, warnings)?,
+                    ))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let else_result = match else_result {
+                Some(e) => Some(std::boxed::Box::new(map_expr(e, object, warnings)?)),
+                None => None,
+            };
+            Some(Expr::Case {
+                operand,
+                branches,
+                else_result,
+            })
+        }
+
+        // LIKE / ILIKE → LIKE; SIMILAR TO has no equivalent
+        Expr::Like {
+            expr: inner,
+            pattern,
+            kind,
+            negated,
+            escape,
+        } => map_like(inner, pattern, *kind, *negated, escape, object, warnings),
+
+        // EXTRACT(field FROM x) → CAST(strftime('%…', x) AS INTEGER)
+        Expr::Extract { field, expr: inner } => {
+            let mapped = map_expr(inner, object, warnings)?;
+            let extracted = extract_field(field, mapped);
+            if extracted.is_none() {
+                warnings.push(unsupported(
+                    format!("EXTRACT({}) has no SQLite equivalent", field.to_uppercase()),
+                    object,
+                ));
+            }
+            extracted
+        }
+
+        // SUBSTRING(x FROM a FOR b) → substr(x, a, b)
+        Expr::Substring {
+            expr: inner,
+            from,
+            length,
+        } => {
+            // substring(x FROM 'pattern') is a regular expression match
+            if let Some(from) = from
+                && matches!(**from, Expr::StringLiteral(_))
+            {
+                warnings.push(unsupported(
+                    "SUBSTRING with a regular expression has no SQLite equivalent",
+                    object,
+                ));
+                return None;
+            }
+            let mut args = vec![
+                map_expr(inner, object, warnings)?,
+                match from {
+                    Some(from) => map_expr(from, object, warnings)?,
+                    None => Expr::IntegerLiteral(1),
+                },
+            ];
+            if let Some(length) = length {
+                args.push(map_expr(length, object, warnings)?);
+            }
+            Some(Expr::func("substr", args))
+        }
+
+        // POSITION(a IN b) → instr(b, a)
+        Expr::Position { needle, haystack } => {
+            let haystack = map_expr(haystack, object, warnings)?;
+            let needle = map_expr(needle, object, warnings)?;
+            Some(Expr::func("instr", vec![haystack, needle]))
+        }
+
+        // AT TIME ZONE — SQLite has no time zones; keep the timestamp
+        Expr::AtTimeZone { expr: inner, zone } => {
+            warnings.push(
+                Warning::new(
+                    warning::TIMEZONE_LOSS,
+                    Severity::Lossy,
+                    format!("AT TIME ZONE {} removed", zone.to_sql()),
+                )
+                .with_object(object),
+            );
+            map_expr(inner, object, warnings)
+        }
+
+        // DATE '…', INTERVAL '…' → '…'; date/time values are stored as text
+        Expr::TypedLiteral { type_name, value } => {
+            let upper = type_name.to_uppercase();
+            if !["DATE", "TIME", "INTERVAL"]
+                .iter()
+                .any(|prefix| upper.starts_with(prefix))
+            {
+                warnings.push(
+                    Warning::new(
+                        warning::CAST_REMOVED,
+                        Severity::Info,
+                        format!("type {type_name} removed from literal '{value}'"),
+                    )
+                    .with_object(object),
+                );
+            }
+            Some(Expr::StringLiteral(value.clone()))
+        }
+
         // Raw SQL — pass through (best effort)
         Expr::Raw(_) => Some(expr.clone()),
+
+        // Built in SQLite form by an earlier pass — nothing to translate
+        Expr::Native(inner) => Some((**inner).clone()),
+    }
+}
+
+/// Translate integer casts of column references by the column's type, which
+/// `map_expr` cannot see.
+///
+/// Fractional columns are rounded first, as PG does; integer columns are cast
+/// directly so values beyond 2^53 keep every digit.
+pub fn type_column_casts(model: &mut SchemaModel) {
+    let fractional: HashMap<String, HashSet<String>> = model
+        .tables
+        .iter()
+        .map(|t| {
+            let columns = t
+                .columns
+                .iter()
+                .filter(|c| match c.sqlite_type {
+                    Some(affinity) => matches!(affinity, SqliteType::Real | SqliteType::Numeric),
+                    None => matches!(
+                        c.pg_type,
+                        PgType::Real | PgType::DoublePrecision | PgType::Numeric { .. }
+                    ),
+                })
+                .map(|c| c.name.normalized.clone())
+                .collect();
+            (t.name.name.normalized.clone(), columns)
+        })
+        .collect();
+    let no_columns = HashSet::new();
+
+    for table in &mut model.tables {
+        let columns = &fractional[&table.name.name.normalized];
+        for col in &mut table.columns {
+            for expr in [&mut col.default, &mut col.check].into_iter().flatten() {
+                type_casts(expr, columns);
+            }
+        }
+        for constraint in &mut table.constraints {
+            if let TableConstraint::Check { expr, .. } = constraint {
+                type_casts(expr, columns);
+            }
+        }
+    }
+    for index in &mut model.indexes {
+        let columns = fractional
+            .get(&index.table.name.normalized)
+            .unwrap_or(&no_columns);
+        for column in &mut index.columns {
+            if let IndexColumn::Expression(expr) = column {
+                type_casts(expr, columns);
+            }
+        }
+        if let Some(where_clause) = &mut index.where_clause {
+            type_casts(where_clause, columns);
+        }
+    }
+}
+
+fn type_casts(expr: &mut Expr, fractional: &HashSet<String>) {
+    // The walk goes on into each replacement, whose cast is visited next
+    let mut replaced = false;
+    expr.walk_mut(&mut |e| {
+        if std::mem::take(&mut replaced) {
+            return;
+        }
+        let Expr::Cast {
+            expr: inner,
+            type_name,
+        } = e
+        else {
+            return;
+        };
+        let Expr::ColumnRef(column) = inner.as_ref() else {
+            return;
+        };
+        if cast_target(&parse_type_name(type_name)) != CastTarget::Affinity(SqliteType::Integer) {
+            return;
+        }
+        let operand = if fractional.contains(column) {
+            Expr::func("round", vec![Expr::ColumnRef(column.clone())])
+        } else {
+            Expr::ColumnRef(column.clone())
+        };
+        *e = Expr::native(Expr::Cast {
+            expr: std::boxed::Box::new(operand),
+            type_name: SqliteType::Integer.to_string(),
+        });
+        replaced = true;
+    });
+}
+
+/// The storage class an already mapped expression evaluates to, when it
+/// can be told from the expression alone.
+fn operand_affinity(expr: &Expr) -> Option<SqliteType> {
+    match expr {
+        Expr::IntegerLiteral(_) => Some(SqliteType::Integer),
+        Expr::FloatLiteral(_) => Some(SqliteType::Real),
+        Expr::StringLiteral(_) => Some(SqliteType::Text),
+        Expr::Cast { type_name, .. } => type_name.parse().ok(),
+        Expr::FunctionCall { name, .. } if name == "round" => Some(SqliteType::Real),
+        Expr::Nested(inner) | Expr::UnaryOp { expr: inner, .. } => operand_affinity(inner),
+        Expr::BinaryOp { left, op, right }
+            if matches!(op.as_str(), "+" | "-" | "*" | "/" | "%") =>
+        {
+            match (operand_affinity(left)?, operand_affinity(right)?) {
+                (SqliteType::Integer, SqliteType::Integer) => Some(SqliteType::Integer),
+                (
+                    SqliteType::Integer | SqliteType::Real | SqliteType::Numeric,
+                    SqliteType::Real,
+                )
+                | (SqliteType::Real | SqliteType::Numeric, SqliteType::Integer) => {
+                    Some(SqliteType::Real)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// What a cast to a PG type does to a value in SQLite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CastTarget {
+    /// Convert to this storage class.
+    Affinity(SqliteType),
+    /// Convert to an integer truth value.
+    Boolean,
+    /// Truncate timestamp text with `date()` or `time()`.
+    DateTime(&'static str),
+    /// Storage depends on the column's conversion options; keep the value.
+    Unchanged,
+}
+
+fn cast_target(pg_type: &PgType) -> CastTarget {
+    match pg_type {
+        PgType::SmallInt
+        | PgType::Integer
+        | PgType::BigInt
+        | PgType::SmallSerial
+        | PgType::Serial
+        | PgType::BigSerial => CastTarget::Affinity(SqliteType::Integer),
+        PgType::Numeric { .. } => CastTarget::Affinity(SqliteType::Numeric),
+        PgType::Real | PgType::DoublePrecision => CastTarget::Affinity(SqliteType::Real),
+        PgType::Text | PgType::Varchar { .. } | PgType::Char { .. } | PgType::Citext => {
+            CastTarget::Affinity(SqliteType::Text)
+        }
+        PgType::Bytea => CastTarget::Affinity(SqliteType::Blob),
+        PgType::Boolean => CastTarget::Boolean,
+        PgType::Date => CastTarget::DateTime("date"),
+        PgType::Time { with_tz: false } => CastTarget::DateTime("time"),
+        _ => CastTarget::Unchanged,
+    }
+}
+
+/// Translate a cast of an already mapped expression.
+///
+/// Literals are converted in place; other values get a SQLite `CAST` when the
+/// target has a storage class of its own.
+fn map_cast(expr: Expr, type_name: &str, object: &str, warnings: &mut Vec<Warning>) -> Expr {
+    let pg_type = parse_type_name(type_name);
+    let target = cast_target(&pg_type);
+    if let Some(literal) = cast_literal(&expr, target) {
+        warnings.push(
+            Warning::new(
+                warning::CAST_REMOVED,
+                Severity::Info,
+                format!("cast to {type_name} removed"),
+            )
+            .with_object(object),
+        );
+        return literal;
+    }
+
+    let cast = |expr, affinity: SqliteType| Expr::Cast {
+        expr: std::boxed::Box::new(expr),
+        type_name: affinity.to_string(),
+    };
+    match target {
+        CastTarget::Affinity(affinity) => {
+            let ignored = match pg_type {
+                PgType::Varchar { length: Some(_) } | PgType::Char { length: Some(_) } => {
+                    Some("does not truncate")
+                }
+                PgType::Numeric { scale: Some(_), .. } => Some("does not round"),
+                _ => None,
+            };
+            if let Some(ignored) = ignored {
+                warnings.push(
+                    Warning::new(
+                        warning::CAST_LOSSY,
+                        Severity::Lossy,
+                        format!("cast to {type_name} {ignored} in SQLite"),
+                    )
+                    .with_object(object),
+                );
+            }
+            match affinity {
+                // PG rounds fractions where SQLite's CAST truncates them; an
+                // integer goes through as is, since round() returns a REAL
+                SqliteType::Integer => match operand_affinity(&expr) {
+                    Some(SqliteType::Real | SqliteType::Numeric) => {
+                        cast(Expr::func("round", vec![expr]), affinity)
+                    }
+                    Some(_) => cast(expr, affinity),
+                    None => {
+                        warnings.push(
+                            Warning::new(
+                                warning::CAST_LOSSY,
+                                Severity::Lossy,
+                                format!("cast to {type_name} truncates fractions in SQLite"),
+                            )
+                            .with_object(object),
+                        );
+                        cast(expr, affinity)
+                    }
+                },
+                // A NUMERIC cast keeps integers integral, and SQLite divides
+                // them as integers where PG would not
+                SqliteType::Numeric => cast(expr, SqliteType::Real),
+                _ => cast(expr, affinity),
+            }
+        }
+        CastTarget::Boolean => cast(expr, SqliteType::Integer),
+        CastTarget::DateTime(function) => Expr::func(function, vec![expr]),
+        CastTarget::Unchanged => {
+            warnings.push(
+                Warning::new(
+                    warning::CAST_REMOVED,
+                    Severity::Info,
+                    format!("cast to {type_name} removed"),
+                )
+                .with_object(object),
+            );
+            expr
+        }
     }
 }
 
+/// The literal a cast evaluates to, when it can be computed up front.
+fn cast_literal(expr: &Expr, target: CastTarget) -> Option<Expr> {
+    use SqliteType::{Integer, Numeric, Real, Text};
+
+    let literal = match (expr, target) {
+        (Expr::Null, _) => Expr::Null,
+        (Expr::StringLiteral(_), CastTarget::Unchanged | CastTarget::DateTime(_))
+        | (Expr::StringLiteral(_), CastTarget::Affinity(Text))
+        | (Expr::IntegerLiteral(_), CastTarget::Affinity(Integer | Numeric))
+        | (Expr::FloatLiteral(_), CastTarget::Affinity(Real | Numeric)) => expr.clone(),
+        (Expr::StringLiteral(text), CastTarget::Affinity(Integer)) => {
+            Expr::IntegerLiteral(text.trim().parse().ok()?)
+        }
+        (Expr::StringLiteral(text), CastTarget::Affinity(Real)) => {
+            Expr::FloatLiteral(text.trim().parse().ok()?)
+        }
+        (Expr::StringLiteral(text), CastTarget::Affinity(Numeric)) => {
+            let text = text.trim();
+            match text.parse() {
+                Ok(n) => Expr::IntegerLiteral(n),
+                Err(_) => Expr::FloatLiteral(text.parse().ok()?),
+            }
+        }
+        (Expr::IntegerLiteral(n), CastTarget::Affinity(Real)) => Expr::FloatLiteral(*n as f64),
+        (Expr::IntegerLiteral(n), CastTarget::Affinity(Text)) => Expr::StringLiteral(n.to_string()),
+        (Expr::FloatLiteral(n), CastTarget::Affinity(Text)) => Expr::StringLiteral(n.to_string()),
+        (Expr::IntegerLiteral(n), CastTarget::Boolean) => Expr::IntegerLiteral((*n != 0) as i64),
+        (Expr::StringLiteral(text), CastTarget::Boolean) => {
+            // The spellings PG accepts, including unique prefixes
+            let text = text.trim().to_ascii_lowercase();
+            let value = match text.as_str() {
+                "1" | "on" => 1,
+                "0" | "of" | "off" => 0,
+                _ if !text.is_empty()
+                    && ("true".starts_with(&text) || "yes".starts_with(&text)) =>
+                {
+                    1
+                }
+                _ if !text.is_empty()
+                    && ("false".starts_with(&text) || "no".starts_with(&text)) =>
+                {
+                    0
+                }
+                _ => return None,
+            };
+            Expr::IntegerLiteral(value)
+        }
+        _ => return None,
+    };
+    Some(literal)
+}
+
+fn map_like(
+    expr: &Expr,
+    pattern: &Expr,
+    kind: LikeKind,
+    negated: bool,
+    escape: &Option<String>,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<Expr> {
+    match kind {
+        LikeKind::Like => {
+            // SQLite's LIKE ignores ASCII case where PG's does not; GLOB keeps it
+            let glob = match pattern {
+                Expr::StringLiteral(text) => like_to_glob(text, escape),
+                _ => None,
+            };
+            if let Some(glob) = glob {
+                let op = if negated { "NOT GLOB" } else { "GLOB" };
+                return Some(Expr::binary(
+                    map_expr(expr, object, warnings)?,
+                    op,
+                    Expr::StringLiteral(glob),
+                ));
+            }
+            warnings.push(
+                Warning::new(
+                    warning::LIKE_CASE_INSENSITIVE,
+                    Severity::Lossy,
+                    "LIKE kept as LIKE, which ignores case for ASCII letters in SQLite",
+                )
+                .with_object(object),
+            );
+        }
+        LikeKind::ILike => warnings.push(
+            Warning::new(
+                warning::ILIKE_AS_LIKE,
+                Severity::Info,
+                "ILIKE mapped to LIKE, which ignores case for ASCII letters only",
+            )
+            .with_object(object),
+        ),
+        LikeKind::SimilarTo => {
+            warnings.push(unsupported("SIMILAR TO has no SQLite equivalent", object));
+            return None;
+        }
+    }
+    // PG escapes with a backslash by default; SQLite has no default escape
+    let escape = match (escape, pattern) {
+        (None, Expr::StringLiteral(text)) if text.contains('\\') => Some("\\".to_string()),
+        _ => escape.clone(),
+    };
+    Some(Expr::Like {
+        expr: std::boxed::Box::new(map_expr(expr, object, warnings)?),
+        pattern: std::boxed::Box::new(map_expr(pattern, object, warnings)?),
+        kind: LikeKind::Like,
+        negated,
+        escape,
+    })
+}
+
+/// Translate a LIKE pattern to the equivalent GLOB pattern.
+///
+/// PG escapes with a backslash unless `ESCAPE` names another character (or
+/// none); returns None for an escape clause or pattern SQLite can't mirror.
+fn like_to_glob(pattern: &str, escape: &Option<String>) -> Option<String> {
+    let escape = match escape.as_deref() {
+        None => Some('\\'),
+        Some("") => None,
+        Some(text) => {
+            let mut chars = text.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            Some(c)
+        }
+    };
+    let mut glob = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        let literal = match c {
+            c if Some(c) == escape => chars.next()?,
+            '%' => {
+                glob.push('*');
+                continue;
+            }
+            '_' => {
+                glob.push('?');
+                continue;
+            }
+            c => c,
+        };
+        match literal {
+            '*' | '?' | '[' => {
+                glob.push('[');
+                glob.push(literal);
+                glob.push(']');
+            }
+            c => glob.push(c),
+        }
+    }
+    Some(glob)
+}
+
+/// Binary operators SQLite understands with PG's meaning, and SQLite-only
+/// operators that earlier passes build (e.g. the ltree path GLOB CHECK).
+const SQLITE_BINARY_OPERATORS: &[&str] = &[
+    "=",
+    "==",
+    "<>",
+    "!=",
+    "<",
+    "<=",
+    ">",
+    ">=",
+    "AND",
+    "OR",
+    "+",
+    "-",
+    "*",
+    "/",
+    "%",
+    "||",
+    "&",
+    "|",
+    "<<",
+    ">>",
+    "->",
+    "->>",
+    "IS",
+    "IS NOT",
+    "GLOB",
+    "NOT GLOB",
+    "REGEXP",
+    "NOT REGEXP",
+];
+
+fn map_binary_op(
+    left: &Expr,
+    op: &str,
+    right: &Expr,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<Expr> {
+    let like = |kind, negated, warnings: &mut Vec<Warning>| {
+        map_like(left, right, kind, negated, &None, object, warnings)
+    };
+    let sqlite_op = match op {
+        // a ~~ b is PG's spelling of a LIKE b
+        "~~" => return like(LikeKind::Like, false, warnings),
+        "!~~" => return like(LikeKind::Like, true, warnings),
+        "~~*" => return like(LikeKind::ILike, false, warnings),
+        "!~~*" => return like(LikeKind::ILike, true, warnings),
+        "~" | "!~" | "~*" | "!~*" => return map_regex_match(left, op, right, object, warnings),
+        "IS DISTINCT FROM" => "IS NOT",
+        "IS NOT DISTINCT FROM" => "IS",
+        // a ^ b → pow(a, b), from SQLite's math functions
+        "^" => {
+            let left = map_expr(left, object, warnings)?;
+            let right = map_expr(right, object, warnings)?;
+            warnings.push(
+                Warning::new(
+                    warning::FUNCTION_NOT_BUILTIN,
+                    Severity::Info,
+                    "operator '^' mapped to pow(), which needs SQLite's math functions (3.35+, SQLITE_ENABLE_MATH_FUNCTIONS)",
+                )
+                .with_object(object),
+            );
+            return Some(Expr::func("pow", vec![left, right]));
+        }
+        // a # b (bitwise XOR) → (a | b) - (a & b)
+        "#" => {
+            let left = map_expr(left, object, warnings)?;
+            let right = map_expr(right, object, warnings)?;
+            return Some(Expr::binary(
+                Expr::binary(left.clone(), "|", right.clone()),
+                "-",
+                Expr::binary(left, "&", right),
+            ));
+        }
+        _ if SQLITE_BINARY_OPERATORS.contains(&op) => op,
+        _ => {
+            warnings.push(unsupported(
+                format!("operator '{op}' has no SQLite equivalent"),
+                object,
+            ));
+            return None;
+        }
+    };
+    let left = map_expr(left, object, warnings)?;
+    let right = map_expr(right, object, warnings)?;
+    Some(Expr::binary(left, sqlite_op, right))
+}
+
+/// Regex match (`~`, `~*` and their `!` negations) → GLOB, LIKE or REGEXP.
+fn map_regex_match(
+    left: &Expr,
+    op: &str,
+    right: &Expr,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<Expr> {
+    let negated = op.starts_with('!');
+    let ignore_case = op.ends_with('*');
+    let regex = match right {
+        Expr::StringLiteral(regex) => Some(regex.as_str()),
+        _ => None,
+    };
+    let left = map_expr(left, object, warnings)?;
+
+    // Case-insensitive matches only have LIKE, which folds ASCII letters only
+    if ignore_case {
+        let Some((pattern, escaped)) = regex.and_then(regex::to_like) else {
+            warnings.push(unsupported(
+                format!("case-insensitive regex match '{op}' has no SQLite equivalent"),
+                object,
+            ));
+            return None;
+        };
+        warnings.push(
+            Warning::new(
+                warning::ILIKE_AS_LIKE,
+                Severity::Info,
+                format!(
+                    "regex match '{op}' mapped to LIKE, which ignores case for ASCII letters only"
+                ),
+            )
+            .with_object(object),
+        );
+        return Some(Expr::Like {
+            expr: std::boxed::Box::new(left),
+            pattern: std::boxed::Box::new(Expr::StringLiteral(pattern)),
+            kind: LikeKind::Like,
+            negated,
+            escape: escaped.then(|| "\\".to_string()),
+        });
+    }
+
+    if let Some(pattern) = regex.and_then(regex::to_glob) {
+        let op = if negated { "NOT GLOB" } else { "GLOB" };
+        return Some(Expr::binary(left, op, Expr::StringLiteral(pattern)));
+    }
+    warnings.push(
+        Warning::new(
+            warning::FUNCTION_NOT_BUILTIN,
+            Severity::Lossy,
+            format!(
+                "regex match '{op}' mapped to REGEXP, which needs a regexp() function registered by the application"
+            ),
+        )
+        .with_object(object),
+    );
+    let op = if negated { "NOT REGEXP" } else { "REGEXP" };
+    Some(Expr::binary(left, op, map_expr(right, object, warnings)?))
+}
+
+/// Compute an EXTRACT field with strftime(), or `None` if SQLite can't.
+fn extract_field(field: &str, expr: Expr) -> Option<Expr> {
+    let strftime = |format: &str, expr: Expr, type_name: &str| Expr::Cast {
+        expr: std::boxed::Box::new(Expr::func(
+            "strftime",
+            vec![Expr::StringLiteral(format.to_string()), expr],
+        )),
+        type_name: type_name.to_string(),
+    };
+    let format = match field {
+        "year" | "years" => "%Y",
+        "month" | "months" => "%m",
+        "day" | "days" => "%d",
+        "hour" | "hours" => "%H",
+        "minute" | "minutes" => "%M",
+        "dow" => "%w",
+        "doy" => "%j",
+        "epoch" => "%s",
+        // Seconds include the fractional part in PG
+        "second" | "seconds" => return Some(strftime("%f", expr, "REAL")),
+        "quarter" => {
+            let month = strftime("%m", expr, "INTEGER");
+            return Some(Expr::binary(
+                Expr::Nested(std::boxed::Box::new(Expr::binary(
+                    month,
+                    "+",
+                    Expr::IntegerLiteral(2),
+                ))),
+                "/",
+                Expr::IntegerLiteral(3),
+            ));
+        }
+        _ => return None,
+    };
+    Some(strftime(format, expr, "INTEGER"))
+}
+
+fn unsupported(message: impl Into<String>, object: &str) -> Warning {
+    Warning::new(warning::DEFAULT_UNSUPPORTED, Severity::Unsupported, message).with_object(object)
+}
+
 fn map_function_call(
     name: &str,
     args: &[Expr],
     object: &str,
     warnings: &mut Vec<Warning>,
 ) -> Option<Expr> {
-    match name {
-        // now() → CURRENT_TIMESTAMP
-        "now" => Some(Expr::CurrentTimestamp),
-
-        // lower(), upper(), length(), abs(), max(), min() — SQLite-compatible
-        "lower" | "upper" | "length" | "abs" | "max" | "min" | "coalesce" | "nullif" | "typeof"
-        | "trim" | "ltrim" | "rtrim" | "replace" | "substr" | "instr" | "hex" | "quote"
-        | "round" | "random" | "unicode" | "zeroblob" | "total" | "sum" | "avg" | "count"
-        | "group_concat" => {
-            let mapped_args: Vec<Expr> = args
-                .iter()
-                .filter_map(|a| map_expr(a, object, warnings))
-                .collect();
-            Some(Expr::FunctionCall {
-                name: name.to_string(),
-                args: mapped_args,
-            })
-        }
+    // gen_random_uuid() / uuid_generate_v4() → random v4 UUID text
+    if uuid::is_uuid_generator(name) {
+        return Some(uuid::uuid_v4_expr(UuidMode::Text));
+    }
 
-        // PG-specific functions → drop with warning
-        _ => {
+    // PG-specific functions → drop with warning
+    let Some(translation) = functions::lookup(name) else {
+        warnings.push(unsupported(
+            format!("unsupported function '{name}()' in expression"),
+            object,
+        ));
+        return None;
+    };
+
+    let args = args
+        .iter()
+        .map(|a| map_expr(a, object, warnings))
+        .collect::<Option<Vec<Expr>>>()?;
+    match translation {
+        Translation::Same => Some(Expr::func(name, args)),
+        Translation::Rename(sqlite_name) => Some(Expr::func(sqlite_name, args)),
+        Translation::Rewrite(rewrite) => {
+            let rendered = Expr::func(name, args.clone()).to_sql();
+            let mapped = rewrite(args);
+            if mapped.is_none() {
+                warnings.push(unsupported(
+                    format!("{rendered} has no SQLite equivalent"),
+                    object,
+                ));
+            }
+            mapped
+        }
+        Translation::Extension => {
             warnings.push(
                 Warning::new(
-                    warning::DEFAULT_UNSUPPORTED,
-                    Severity::Unsupported,
-                    format!("unsupported function '{name}()' in expression"),
+                    warning::FUNCTION_NOT_BUILTIN,
+                    Severity::Lossy,
+                    format!("{name}() is not built into SQLite and must be registered by the application"),
                 )
                 .with_object(object),
             );
-            None
+            Some(Expr::func(name, args))
         }
     }
 }
@@ -209,6 +962,24 @@ mod tests {
         assert_eq!(map_expr(&expr, "t.c", &mut w), Some(Expr::CurrentTimestamp));
     }
 
+    #[test]
+    fn test_now_plus_interval_to_datetime() {
+        let mut w = Vec::new();
+        let expr = Expr::binary(
+            Expr::func("now", vec![]),
+            "+",
+            Expr::Cast {
+                expr: std::boxed::Box::new(Expr::StringLiteral("1 day".to_string())),
+                type_name: "interval".to_string(),
+            },
+        );
+        assert_eq!(
+            map_expr(&expr, "t.c", &mut w).map(|e| e.to_sql()),
+            Some("datetime('now', '+1 days')".to_string())
+        );
+        assert!(w.is_empty());
+    }
+
     #[test]
     fn test_nextval_removed() {
         let mut w = Vec::new();
@@ -231,17 +1002,181 @@ mod tests {
         assert_eq!(w[0].code, warning::CAST_REMOVED);
     }
 
+    #[test]
+    fn test_cast_translated() {
+        let cast = |expr: Expr, type_name: &str| Expr::Cast {
+            expr: std::boxed::Box::new(expr),
+            type_name: type_name.to_string(),
+        };
+        let col = |name: &str| Expr::ColumnRef(name.to_string());
+        let mut w = Vec::new();
+        let mut mapped = |expr: Expr| map_expr(&expr, "t.c", &mut w).map(|e| e.to_sql());
+
+        assert_eq!(
+            mapped(cast(cast(col("price"), "REAL"), "INTEGER")),
+            Some("CAST(round(CAST(price AS REAL)) AS INTEGER)".to_string())
+        );
+        assert_eq!(
+            mapped(cast(
+                Expr::Nested(std::boxed::Box::new(Expr::binary(
+                    Expr::IntegerLiteral(9007199254740993),
+                    "+",
+                    Expr::IntegerLiteral(0)
+                ))),
+                "BIGINT"
+            )),
+            Some("CAST((9007199254740993 + 0) AS INTEGER)".to_string())
+        );
+        assert_eq!(
+            mapped(cast(
+                Expr::Nested(std::boxed::Box::new(Expr::binary(col("a"), "/", col("b")))),
+                "NUMERIC"
+            )),
+            Some("CAST((a / b) AS REAL)".to_string())
+        );
+        assert_eq!(
+            mapped(cast(col("n"), "DOUBLE PRECISION")),
+            Some("CAST(n AS REAL)".to_string())
+        );
+        assert_eq!(
+            mapped(cast(col("n"), "TEXT")),
+            Some("CAST(n AS TEXT)".to_string())
+        );
+        assert_eq!(
+            mapped(cast(col("raw"), "BYTEA")),
+            Some("CAST(raw AS BLOB)".to_string())
+        );
+        assert_eq!(
+            mapped(cast(Expr::func("now", vec![]), "DATE")),
+            Some("date(CURRENT_TIMESTAMP)".to_string())
+        );
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_cast_literals_folded() {
+        let cast = |expr: Expr, type_name: &str| Expr::Cast {
+            expr: std::boxed::Box::new(expr),
+            type_name: type_name.to_string(),
+        };
+        let text = |s: &str| Expr::StringLiteral(s.to_string());
+        let mut w = Vec::new();
+        for (expr, expected) in [
+            (cast(text("123"), "INT"), Expr::IntegerLiteral(123)),
+            (cast(text("1.5"), "NUMERIC(4,2)"), Expr::FloatLiteral(1.5)),
+            (cast(Expr::IntegerLiteral(42), "TEXT"), text("42")),
+            (cast(text("yes"), "BOOLEAN"), Expr::IntegerLiteral(1)),
+            (cast(text("f"), "BOOLEAN"), Expr::IntegerLiteral(0)),
+            (cast(text("active"), "status"), text("active")),
+            (cast(text("2020-01-01"), "DATE"), text("2020-01-01")),
+            (cast(Expr::Null, "INTEGER"), Expr::Null),
+        ] {
+            assert_eq!(map_expr(&expr, "t.c", &mut w), Some(expected));
+        }
+        assert!(w.iter().all(|w| w.code == warning::CAST_REMOVED));
+
+        // Not a number: converted by SQLite at run time
+        w.clear();
+        assert_eq!(
+            map_expr(&cast(text("abc"), "INTEGER"), "t.c", &mut w).map(|e| e.to_sql()),
+            Some("CAST('abc' AS INTEGER)".to_string())
+        );
+        assert_eq!(
+            map_expr(
+                &cast(Expr::ColumnRef("code".to_string()), "VARCHAR(3)"),
+                "t.c",
+                &mut w
+            )
+            .map(|e| e.to_sql()),
+            Some("CAST(code AS TEXT)".to_string())
+        );
+        assert_eq!(w[0].code, warning::CAST_LOSSY);
+    }
+
     #[test]
     fn test_unsupported_function() {
         let mut w = Vec::new();
         let expr = Expr::FunctionCall {
-            name: "gen_random_uuid".to_string(),
+            name: "txid_current".to_string(),
             args: vec![],
         };
         assert_eq!(map_expr(&expr, "t.c", &mut w), None);
         assert_eq!(w[0].code, warning::DEFAULT_UNSUPPORTED);
     }
 
+    #[test]
+    fn test_function_translations() {
+        let mut w = Vec::new();
+        let call = |name: &str, args: Vec<Expr>| Expr::func(name, args);
+        assert_eq!(
+            map_expr(
+                &call("char_length", vec![Expr::ColumnRef("name".to_string())]),
+                "t.c",
+                &mut w
+            )
+            .map(|e| e.to_sql()),
+            Some("length(name)".to_string())
+        );
+        assert_eq!(
+            map_expr(&call("current_date", vec![]), "t.c", &mut w).map(|e| e.to_sql()),
+            Some("CURRENT_DATE".to_string())
+        );
+        assert!(w.is_empty());
+
+        // Arguments are mapped before the rewrite
+        assert_eq!(
+            map_expr(
+                &call(
+                    "greatest",
+                    vec![Expr::ColumnRef("a".to_string()), Expr::BooleanLiteral(true)]
+                ),
+                "t.c",
+                &mut w
+            )
+            .map(|e| e.to_sql()),
+            Some("max(coalesce(a, 1), 1)".to_string())
+        );
+
+        // Arguments the rewrite can't handle
+        let trunc = call(
+            "date_trunc",
+            vec![
+                Expr::StringLiteral("decade".to_string()),
+                Expr::ColumnRef("ts".to_string()),
+            ],
+        );
+        assert_eq!(map_expr(&trunc, "t.c", &mut w), None);
+        assert_eq!(w[0].code, warning::DEFAULT_UNSUPPORTED);
+        assert!(w[0].message.contains("date_trunc('decade', ts)"));
+    }
+
+    #[test]
+    fn test_md5_kept_with_warning() {
+        let mut w = Vec::new();
+        let expr = Expr::func("md5", vec![Expr::ColumnRef("email".to_string())]);
+        assert_eq!(
+            map_expr(&expr, "t.c", &mut w).map(|e| e.to_sql()),
+            Some("md5(email)".to_string())
+        );
+        assert_eq!(w[0].code, warning::FUNCTION_NOT_BUILTIN);
+    }
+
+    #[test]
+    fn test_uuid_generator_to_text_expr() {
+        let mut w = Vec::new();
+        for name in ["gen_random_uuid", "uuid_generate_v4"] {
+            let expr = Expr::FunctionCall {
+                name: name.to_string(),
+                args: vec![],
+            };
+            assert_eq!(
+                map_expr(&expr, "t.id", &mut w),
+                Some(uuid::uuid_v4_expr(UuidMode::Text))
+            );
+        }
+        assert!(w.is_empty());
+    }
+
     #[test]
     fn test_compatible_function_passthrough() {
         let mut w = Vec::new();
@@ -253,4 +1188,279 @@ mod tests {
         assert!(result.is_some());
         assert!(w.is_empty());
     }
+
+    #[test]
+    fn test_like_kinds() {
+        let like = |kind, pattern: &str| Expr::Like {
+            expr: std::boxed::Box::new(Expr::ColumnRef("name".to_string())),
+            pattern: std::boxed::Box::new(Expr::StringLiteral(pattern.to_string())),
+            kind,
+            negated: false,
+            escape: None,
+        };
+        let mut w = Vec::new();
+        assert_eq!(
+            map_expr(&like(LikeKind::ILike, "a%"), "t.c", &mut w).map(|e| e.to_sql()),
+            Some(r#"name LIKE 'a%'"#.to_string())
+        );
+        assert_eq!(w[0].code, warning::ILIKE_AS_LIKE);
+        assert_eq!(
+            map_expr(&like(LikeKind::ILike, r"a\_%"), "t.c", &mut w).map(|e| e.to_sql()),
+            Some(r#"name LIKE 'a\_%' ESCAPE '\'"#.to_string())
+        );
+        assert_eq!(
+            map_expr(&like(LikeKind::SimilarTo, "a|b"), "t.c", &mut w),
+            None
+        );
+        assert_eq!(w[2].code, warning::DEFAULT_UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_like_as_glob() {
+        let like = |pattern: Expr, escape: Option<&str>| Expr::Like {
+            expr: std::boxed::Box::new(Expr::ColumnRef("name".to_string())),
+            pattern: std::boxed::Box::new(pattern),
+            kind: LikeKind::Like,
+            negated: false,
+            escape: escape.map(str::to_string),
+        };
+        let text = |s: &str| Expr::StringLiteral(s.to_string());
+        let mut w = Vec::new();
+        for (pattern, escape, expected) in [
+            ("a%", None, "name GLOB 'a*'"),
+            (r"a\_%", None, "name GLOB 'a_*'"),
+            ("_*?[x]", None, "name GLOB '?[*][?][[]x]'"),
+            ("a!%b", Some("!"), "name GLOB 'a%b'"),
+            (r"a\%", Some(""), r"name GLOB 'a\*'"),
+        ] {
+            assert_eq!(
+                map_expr(&like(text(pattern), escape), "t.c", &mut w).map(|e| e.to_sql()),
+                Some(expected.to_string())
+            );
+        }
+        assert!(w.is_empty());
+
+        // GLOB keeps PG's case sensitivity
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let matches = |sql: &str| -> bool {
+            conn.query_row(&format!("SELECT {sql}"), [], |row| row.get(0))
+                .unwrap()
+        };
+        assert!(matches("'a_[1]' GLOB 'a?[[]1]'"));
+        assert!(!matches("'ABC' GLOB 'a*'"));
+        assert!(matches("'ABC' LIKE 'a%'"));
+
+        // A column pattern or a trailing escape can't be converted
+        assert_eq!(
+            map_expr(&like(Expr::ColumnRef("p".to_string()), None), "t.c", &mut w)
+                .map(|e| e.to_sql()),
+            Some("name LIKE p".to_string())
+        );
+        assert_eq!(w[0].code, warning::LIKE_CASE_INSENSITIVE);
+        assert_eq!(
+            map_expr(&like(text(r"a\"), None), "t.c", &mut w).map(|e| e.to_sql()),
+            Some(r#"name LIKE 'a\' ESCAPE '\'"#.to_string())
+        );
+        assert_eq!(w[1].code, warning::LIKE_CASE_INSENSITIVE);
+    }
+
+    #[test]
+    fn test_operator_translation() {
+        let col = |name: &str| Expr::ColumnRef(name.to_string());
+        let text = |s: &str| Expr::StringLiteral(s.to_string());
+        let mapped =
+            |expr: Expr, w: &mut Vec<Warning>| map_expr(&expr, "t.c", w).map(|e| e.to_sql());
+        let mut w = Vec::new();
+        assert_eq!(
+            mapped(Expr::binary(col("a"), "IS DISTINCT FROM", col("b")), &mut w),
+            Some("a IS NOT b".to_string())
+        );
+        assert_eq!(
+            mapped(
+                Expr::binary(col("a"), "IS NOT DISTINCT FROM", Expr::Null),
+                &mut w
+            ),
+            Some("a IS NULL".to_string())
+        );
+        assert_eq!(
+            mapped(Expr::binary(col("a"), "#", col("b")), &mut w),
+            Some("(a | b) - (a & b)".to_string())
+        );
+        assert_eq!(
+            mapped(Expr::binary(col("a"), "!~~", text("x%")), &mut w),
+            Some("a NOT GLOB 'x*'".to_string())
+        );
+        assert_eq!(
+            mapped(
+                Expr::UnaryOp {
+                    op: "@".to_string(),
+                    expr: std::boxed::Box::new(col("a")),
+                },
+                &mut w
+            ),
+            Some("abs(a)".to_string())
+        );
+        // SQLite-only operators built by earlier passes are kept
+        for op in ["==", "IS", "IS NOT", "GLOB", "NOT GLOB", "REGEXP"] {
+            assert_eq!(
+                mapped(Expr::binary(col("a"), op, col("b")), &mut w),
+                Some(format!("a {op} b"))
+            );
+        }
+        assert!(w.is_empty());
+
+        assert_eq!(
+            mapped(Expr::binary(col("a"), "^", Expr::IntegerLiteral(2)), &mut w),
+            Some("pow(a, 2)".to_string())
+        );
+        assert_eq!(w[0].code, warning::FUNCTION_NOT_BUILTIN);
+
+        for op in ["@>", "&&", "?"] {
+            let mut w = Vec::new();
+            assert_eq!(mapped(Expr::binary(col("a"), op, col("b")), &mut w), None);
+            assert_eq!(w[0].code, warning::DEFAULT_UNSUPPORTED);
+            assert!(w[0].message.contains(&format!("'{op}'")));
+        }
+        let mut w = Vec::new();
+        let sqrt = Expr::UnaryOp {
+            op: "|/".to_string(),
+            expr: std::boxed::Box::new(col("a")),
+        };
+        assert_eq!(mapped(sqrt, &mut w), None);
+        assert_eq!(w[0].code, warning::DEFAULT_UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_regex_match() {
+        let col = |name: &str| Expr::ColumnRef(name.to_string());
+        let regex = |op: &str, pattern: &str| {
+            Expr::binary(col("code"), op, Expr::StringLiteral(pattern.to_string()))
+        };
+        let mapped =
+            |expr: Expr, w: &mut Vec<Warning>| map_expr(&expr, "t.c", w).map(|e| e.to_sql());
+
+        let mut w = Vec::new();
+        assert_eq!(
+            mapped(regex("~", "^[A-Z]{2}$"), &mut w),
+            Some("code GLOB '[A-Z][A-Z]'".to_string())
+        );
+        assert_eq!(
+            mapped(regex("!~", "^tmp-"), &mut w),
+            Some("code NOT GLOB 'tmp-*'".to_string())
+        );
+        assert!(w.is_empty());
+
+        assert_eq!(
+            mapped(regex("~*", "^a_b"), &mut w),
+            Some(r"code LIKE 'a\_b%' ESCAPE '\'".to_string())
+        );
+        assert_eq!(w[0].code, warning::ILIKE_AS_LIKE);
+
+        let mut w = Vec::new();
+        assert_eq!(
+            mapped(regex("~", "^[a-z]+$"), &mut w),
+            Some("code REGEXP '^[a-z]+$'".to_string())
+        );
+        assert_eq!(
+            mapped(Expr::binary(col("code"), "!~", col("pattern")), &mut w),
+            Some("code NOT REGEXP pattern".to_string())
+        );
+        assert_eq!(w[0].code, warning::FUNCTION_NOT_BUILTIN);
+
+        let mut w = Vec::new();
+        assert_eq!(mapped(regex("~*", "^[a-z]+$"), &mut w), None);
+        assert_eq!(w[0].code, warning::DEFAULT_UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_extract_to_strftime() {
+        let extract = |field: &str| Expr::Extract {
+            field: field.to_string(),
+            expr: std::boxed::Box::new(Expr::ColumnRef("at".to_string())),
+        };
+        let mut w = Vec::new();
+        let mapped =
+            |field, w: &mut Vec<Warning>| map_expr(&extract(field), "t.c", w).map(|e| e.to_sql());
+        assert_eq!(
+            mapped("year", &mut w),
+            Some(r#"CAST(strftime('%Y', at) AS INTEGER)"#.to_string())
+        );
+        assert_eq!(
+            mapped("second", &mut w),
+            Some(r#"CAST(strftime('%f', at) AS REAL)"#.to_string())
+        );
+        assert_eq!(
+            mapped("quarter", &mut w),
+            Some(r#"(CAST(strftime('%m', at) AS INTEGER) + 2) / 3"#.to_string())
+        );
+        assert!(w.is_empty());
+        assert_eq!(mapped("timezone_hour", &mut w), None);
+        assert_eq!(w[0].code, warning::DEFAULT_UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_case_substring_position() {
+        let col = |name: &str| std::boxed::Box::new(Expr::ColumnRef(name.to_string()));
+        let mut w = Vec::new();
+        let case = Expr::Case {
+            operand: None,
+            branches: vec![(Expr::BooleanLiteral(true), Expr::func("now", vec![]))],
+            else_result: Some(std::boxed::Box::new(Expr::Null)),
+        };
+        assert_eq!(
+            map_expr(&case, "t.c", &mut w).map(|e| e.to_sql()),
+            Some("CASE WHEN 1 THEN CURRENT_TIMESTAMP ELSE NULL END".to_string())
+        );
+        let substring = Expr::Substring {
+            expr: col("code"),
+            from: None,
+            length: Some(std::boxed::Box::new(Expr::IntegerLiteral(3))),
+        };
+        assert_eq!(
+            map_expr(&substring, "t.c", &mut w).map(|e| e.to_sql()),
+            Some(r#"substr(code, 1, 3)"#.to_string())
+        );
+        let position = Expr::Position {
+            needle: std::boxed::Box::new(Expr::StringLiteral("@".to_string())),
+            haystack: col("email"),
+        };
+        assert_eq!(
+            map_expr(&position, "t.c", &mut w).map(|e| e.to_sql()),
+            Some(r#"instr(email, '@')"#.to_string())
+        );
+        assert!(w.is_empty());
+
+        let regex = Expr::Substring {
+            expr: col("code"),
+            from: Some(std::boxed::Box::new(Expr::StringLiteral(
+                "[0-9]+".to_string(),
+            ))),
+            length: None,
+        };
+        assert_eq!(map_expr(&regex, "t.c", &mut w), None);
+        assert_eq!(w[0].code, warning::DEFAULT_UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_typed_literal_and_time_zone() {
+        let mut w = Vec::new();
+        let date = Expr::TypedLiteral {
+            type_name: "DATE".to_string(),
+            value: "2020-01-01".to_string(),
+        };
+        assert_eq!(
+            map_expr(&date, "t.c", &mut w),
+            Some(Expr::StringLiteral("2020-01-01".to_string()))
+        );
+        assert!(w.is_empty());
+        let at_utc = Expr::AtTimeZone {
+            expr: std::boxed::Box::new(Expr::func("now", vec![])),
+            zone: std::boxed::Box::new(Expr::StringLiteral("UTC".to_string())),
+        };
+        assert_eq!(
+            map_expr(&at_utc, "t.c", &mut w),
+            Some(Expr::CurrentTimestamp)
+        );
+        assert_eq!(w[0].code, warning::TIMEZONE_LOSS);
+    }
 }
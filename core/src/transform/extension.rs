@@ -0,0 +1,476 @@
+/// Extension types: citext, hstore, ltree and PostGIS geometry/geography.
+use std::collections::HashSet;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Expr, IndexColumn, PgType, SchemaModel};
+
+/// Apply extension type semantics: NOCASE collation for citext, JSON for hstore
+/// and a label path CHECK for ltree.
+pub fn apply_extension_types(model: &mut SchemaModel, warnings: &mut Vec<Warning>) {
+    let mut reported: HashSet<&'static str> = HashSet::new();
+
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        for col in &mut table.columns {
+            let Some(extension) = col.pg_type.extension() else {
+                continue;
+            };
+            let obj = format!("{}.{}", table_name, col.name.normalized);
+            if !model.extensions.iter().any(|e| e == extension) && reported.insert(extension) {
+                warnings.push(
+                    Warning::new(
+                        warning::EXTENSION_NOT_DECLARED,
+                        Severity::Info,
+                        format!(
+                            "type '{}' assumed to come from extension '{extension}', which is not created in this dump",
+                            col.pg_type
+                        ),
+                    )
+                    .with_object(&obj),
+                );
+            }
+
+            let column = Expr::ColumnRef(col.name.normalized.clone());
+            match col.pg_type {
+                PgType::Citext => {
+                    col.collation = Some("NOCASE".to_string());
+                }
+                PgType::Hstore => {
+                    if let Some(default) = &col.default {
+                        col.default = hstore_default_to_json(default, &obj, warnings);
+                    }
+                    col.add_check(Expr::native(json_object_check(column)));
+                }
+                PgType::Ltree => {
+                    col.add_check(Expr::native(ltree_path_check(column)));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// CHECK that a column holds a JSON object.
+pub fn json_object_check(column: Expr) -> Expr {
+    Expr::and(
+        Expr::func("json_valid", vec![column.clone()]),
+        Expr::binary(
+            Expr::func("json_type", vec![column]),
+            "=",
+            Expr::StringLiteral("object".to_string()),
+        ),
+    )
+}
+
+/// CHECK that a value is a dot-separated ltree label path (possibly empty).
+///
+/// Labels are letters, digits, `_` and `-`; empty labels are rejected.
+fn ltree_path_check(column: Expr) -> Expr {
+    let glob = |pattern: &str| {
+        Expr::binary(
+            column.clone(),
+            "GLOB",
+            Expr::StringLiteral(pattern.to_string()),
+        )
+    };
+    let invalid = ["*[^A-Za-z0-9_.-]*", ".*", "*.", "*..*"]
+        .into_iter()
+        .map(glob)
+        .reduce(|acc, e| Expr::binary(acc, "OR", e))
+        .expect("non-empty pattern list");
+    Expr::UnaryOp {
+        op: "NOT".to_string(),
+        expr: Box::new(Expr::Nested(Box::new(invalid))),
+    }
+}
+
+/// Convert an hstore literal default (`'a=>1'::hstore`) into JSON object text.
+fn hstore_default_to_json(
+    default: &Expr,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<Expr> {
+    let text = match default {
+        Expr::StringLiteral(text) => text,
+        Expr::Cast { expr, type_name } if type_name.eq_ignore_ascii_case("hstore") => {
+            match expr.as_ref() {
+                Expr::StringLiteral(text) => text,
+                _ => return Some(default.clone()),
+            }
+        }
+        // NULL and non-literal defaults are left to expression mapping
+        _ => return Some(default.clone()),
+    };
+    match parse_hstore(text) {
+        Some(pairs) => Some(Expr::StringLiteral(hstore_to_json(&pairs))),
+        None => {
+            warnings.push(
+                Warning::new(
+                    warning::HSTORE_DEFAULT_UNSUPPORTED,
+                    Severity::Unsupported,
+                    format!("hstore default '{text}' could not be parsed; dropped"),
+                )
+                .with_object(object),
+            );
+            None
+        }
+    }
+}
+
+/// Parse hstore text (`"a"=>"1", b=>NULL`) into key/value pairs.
+///
+/// Duplicate keys keep the first value, as PG does.
+pub fn parse_hstore(text: &str) -> Option<Vec<(String, Option<String>)>> {
+    let mut chars = text.chars().peekable();
+    let mut pairs: Vec<(String, Option<String>)> = Vec::new();
+
+    let skip_ws = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+    };
+    // Returns the token and whether it was quoted
+    let read_token = |chars: &mut std::iter::Peekable<std::str::Chars>| -> Option<(String, bool)> {
+        let mut token = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next()? {
+                    '"' => return Some((token, true)),
+                    '\\' => token.push(chars.next()?),
+                    c => token.push(c),
+                }
+            }
+        }
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == ',' || c == '=' {
+                break;
+            }
+            token.push(c);
+            chars.next();
+        }
+        (!token.is_empty()).then_some((token, false))
+    };
+
+    loop {
+        skip_ws(&mut chars);
+        if chars.peek().is_none() {
+            break;
+        }
+        let (key, _) = read_token(&mut chars)?;
+        skip_ws(&mut chars);
+        if chars.next()? != '=' || chars.next()? != '>' {
+            return None;
+        }
+        skip_ws(&mut chars);
+        let (value, quoted) = read_token(&mut chars)?;
+        let value = (quoted || !value.eq_ignore_ascii_case("null")).then_some(value);
+        if !pairs.iter().any(|(k, _)| *k == key) {
+            pairs.push((key, value));
+        }
+        skip_ws(&mut chars);
+        match chars.next() {
+            Some(',') => {}
+            None => break,
+            Some(_) => return None,
+        }
+    }
+    Some(pairs)
+}
+
+/// Render hstore pairs as a JSON object with string (or null) values.
+pub fn hstore_to_json(pairs: &[(String, Option<String>)]) -> String {
+    let body: Vec<String> = pairs
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Some(v) => json_string(v),
+                None => "null".to_string(),
+            };
+            format!("{}:{value}", json_string(key))
+        })
+        .collect();
+    format!("{{{}}}", body.join(","))
+}
+
+/// Quote a string as a JSON string literal.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Move geometry columns into a SpatiaLite script.
+///
+/// Geometry columns are removed from the tables and re-created with
+/// `AddGeometryColumn`; single-column indexes on them become
+/// `CreateSpatialIndex`. Returns `None` when there are no geometry columns.
+pub fn extract_spatialite(model: &mut SchemaModel, warnings: &mut Vec<Warning>) -> Option<String> {
+    let mut statements = Vec::new();
+    let mut removed: HashSet<(String, String)> = HashSet::new();
+
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        table.columns.retain(|col| {
+            let PgType::Geometry {
+                geography,
+                subtype,
+                srid,
+            } = &col.pg_type
+            else {
+                return true;
+            };
+            let (geometry_type, dimension) = spatialite_geometry_type(subtype.as_deref());
+            let srid = srid.unwrap_or(if *geography { 4326 } else { 0 });
+            statements.push(format!(
+                "SELECT AddGeometryColumn({}, {}, {srid}, '{geometry_type}', '{dimension}', {});",
+                sql_string(&table_name),
+                sql_string(&col.name.normalized),
+                i32::from(col.not_null)
+            ));
+            removed.insert((table_name.clone(), col.name.normalized.clone()));
+            false
+        });
+    }
+
+    if statements.is_empty() {
+        return None;
+    }
+
+    model.indexes.retain(|index| {
+        let table_name = &index.table.name.normalized;
+        let uses_geometry = |c: &IndexColumn| match c {
+            IndexColumn::Column(ident) => {
+                removed.contains(&(table_name.clone(), ident.normalized.clone()))
+            }
+            IndexColumn::Expression(expr) => {
+                let mut found = false;
+                expr.clone().walk_mut(&mut |e| {
+                    if let Expr::ColumnRef(name) = e
+                        && removed.contains(&(table_name.clone(), name.clone()))
+                    {
+                        found = true;
+                    }
+                });
+                found
+            }
+        };
+        if !index.columns.iter().any(uses_geometry) {
+            return true;
+        }
+        match index.columns.as_slice() {
+            [IndexColumn::Column(column)] if index.where_clause.is_none() => {
+                statements.push(format!(
+                    "SELECT CreateSpatialIndex({}, {});",
+                    sql_string(table_name),
+                    sql_string(&column.normalized)
+                ));
+            }
+            _ => warnings.push(
+                Warning::new(
+                    warning::SPATIAL_INDEX_UNSUPPORTED,
+                    Severity::Unsupported,
+                    "index on a geometry column cannot be expressed as a SpatiaLite spatial index; index skipped",
+                )
+                .with_object(&index.name.normalized),
+            ),
+        }
+        false
+    });
+
+    let mut script = String::from("-- SpatiaLite geometry columns generated by `pg2sqlite`.\n");
+    script.push_str("-- Run after the schema script with the SpatiaLite extension loaded.\n");
+    script.push_str("SELECT InitSpatialMetadata(1);\n\n");
+    for statement in statements {
+        script.push_str(&statement);
+        script.push('\n');
+    }
+    Some(script)
+}
+
+/// SpatiaLite geometry type and dimension model for a PostGIS subtype (`pointz` → `POINT`, `XYZ`).
+fn spatialite_geometry_type(subtype: Option<&str>) -> (String, &'static str) {
+    let subtype = subtype.unwrap_or("geometry").to_ascii_uppercase();
+    for (suffix, dimension) in [("ZM", "XYZM"), ("Z", "XYZ"), ("M", "XYM")] {
+        if let Some(base) = subtype.strip_suffix(suffix)
+            && is_geometry_type(base)
+        {
+            return (base.to_string(), dimension);
+        }
+    }
+    (subtype, "XY")
+}
+
+fn is_geometry_type(name: &str) -> bool {
+    matches!(
+        name,
+        "GEOMETRY"
+            | "POINT"
+            | "LINESTRING"
+            | "POLYGON"
+            | "MULTIPOINT"
+            | "MULTILINESTRING"
+            | "MULTIPOLYGON"
+            | "GEOMETRYCOLLECTION"
+    )
+}
+
+fn sql_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, Ident, Index, IndexMethod, QualifiedName, Table};
+
+    fn make_column(name: &str, pg_type: PgType) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type: None,
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn make_model(columns: Vec<Column>, extensions: &[&str]) -> SchemaModel {
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("places")),
+                columns,
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_citext_nocase_collation() {
+        let mut model = make_model(vec![make_column("email", PgType::Citext)], &["citext"]);
+        let mut w = Vec::new();
+        apply_extension_types(&mut model, &mut w);
+        assert_eq!(
+            model.tables[0].columns[0].collation.as_deref(),
+            Some("NOCASE")
+        );
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_undeclared_extension_reported_once() {
+        let mut model = make_model(
+            vec![
+                make_column("a", PgType::Ltree),
+                make_column("b", PgType::Ltree),
+            ],
+            &[],
+        );
+        let mut w = Vec::new();
+        apply_extension_types(&mut model, &mut w);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].code, warning::EXTENSION_NOT_DECLARED);
+        assert_eq!(
+            model.tables[0].columns[0].check.as_ref().unwrap().to_sql(),
+            "NOT (a GLOB '*[^A-Za-z0-9_.-]*' OR a GLOB '.*' OR a GLOB '*.' OR a GLOB '*..*')"
+        );
+    }
+
+    #[test]
+    fn test_hstore_default_and_check() {
+        let mut col = make_column("tags", PgType::Hstore);
+        col.default = Some(Expr::Cast {
+            expr: Box::new(Expr::StringLiteral(r#""a"=>"1", b=>NULL"#.to_string())),
+            type_name: "hstore".to_string(),
+        });
+        let mut model = make_model(vec![col], &["hstore"]);
+        let mut w = Vec::new();
+        apply_extension_types(&mut model, &mut w);
+        let col = &model.tables[0].columns[0];
+        assert_eq!(
+            col.default,
+            Some(Expr::StringLiteral(r#"{"a":"1","b":null}"#.to_string()))
+        );
+        assert!(
+            col.check
+                .as_ref()
+                .unwrap()
+                .to_sql()
+                .contains("json_valid(tags)")
+        );
+    }
+
+    #[test]
+    fn test_parse_hstore() {
+        assert_eq!(parse_hstore(""), Some(vec![]));
+        assert_eq!(
+            parse_hstore(r#"a=>1, "k\"q"=>"NULL", a=>2"#),
+            Some(vec![
+                ("a".to_string(), Some("1".to_string())),
+                ("k\"q".to_string(), Some("NULL".to_string())),
+            ])
+        );
+        assert_eq!(parse_hstore("a=1"), None);
+        assert_eq!(parse_hstore(r#""a"=>"unterminated"#), None);
+    }
+
+    #[test]
+    fn test_extract_spatialite() {
+        let mut geom = make_column(
+            "geom",
+            PgType::Geometry {
+                geography: false,
+                subtype: Some("pointz".to_string()),
+                srid: Some(4326),
+            },
+        );
+        geom.not_null = true;
+        let mut model = make_model(vec![make_column("id", PgType::Integer), geom], &["postgis"]);
+        model.indexes.push(Index {
+            name: Ident::new("places_geom_idx"),
+            table: QualifiedName::new(Ident::new("places")),
+            columns: vec![IndexColumn::Column(Ident::new("geom"))],
+            unique: false,
+            method: Some(IndexMethod::Gist),
+            where_clause: None,
+        });
+        let mut w = Vec::new();
+        let script = extract_spatialite(&mut model, &mut w).unwrap();
+
+        assert_eq!(model.tables[0].columns.len(), 1);
+        assert!(model.indexes.is_empty());
+        assert!(script.contains("SELECT InitSpatialMetadata(1);"));
+        assert!(
+            script.contains("SELECT AddGeometryColumn('places', 'geom', 4326, 'POINT', 'XYZ', 1);")
+        );
+        assert!(script.contains("SELECT CreateSpatialIndex('places', 'geom');"));
+    }
+
+    #[test]
+    fn test_extract_spatialite_without_geometry() {
+        let mut model = make_model(vec![make_column("id", PgType::Integer)], &[]);
+        let mut w = Vec::new();
+        assert_eq!(extract_spatialite(&mut model, &mut w), None);
+    }
+}
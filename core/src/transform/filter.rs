@@ -0,0 +1,648 @@
+/// Table and column filters: drop excluded objects and the constraints and
+/// indexes that depend on them.
+use std::collections::HashSet;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{
+    AlterConstraint, Expr, Ident, Index, IndexColumn, QualifiedName, SchemaModel, TableConstraint,
+};
+use crate::transform::overrides::glob_match;
+
+/// Glob patterns selecting the tables and columns to convert.
+#[derive(Debug, Clone, Default)]
+pub struct FilterOptions {
+    /// Schema assumed for unqualified tables when matching `schema.table` patterns.
+    pub schema: Option<String>,
+    /// Keep only tables matching a `table` or `schema.table` pattern (all when empty).
+    pub include_tables: Vec<String>,
+    /// Drop tables matching a `table` or `schema.table` pattern.
+    pub exclude_tables: Vec<String>,
+    /// Drop columns matching a `table.column` or `schema.table.column` pattern.
+    pub exclude_columns: Vec<String>,
+}
+
+impl FilterOptions {
+    fn is_empty(&self) -> bool {
+        self.include_tables.is_empty()
+            && self.exclude_tables.is_empty()
+            && self.exclude_columns.is_empty()
+    }
+}
+
+/// Remove filtered tables and columns.
+///
+/// Must run before planning, so pending ALTER constraints are filtered too.
+/// Objects belonging to a removed table or column go with it. Indexes, keys,
+/// foreign keys and CHECKs of kept tables that reference a removed object are
+/// dropped, or rewritten without it where that keeps their meaning: CHECKs
+/// lose the AND-ed conditions on removed columns, non-unique indexes lose the
+/// removed columns.
+pub fn apply_filters(model: &mut SchemaModel, opts: &FilterOptions, warnings: &mut Vec<Warning>) {
+    if opts.is_empty() {
+        return;
+    }
+
+    let mut removed = Removed::default();
+    model.tables.retain(|t| {
+        let names = qualified_names(&t.name, None, opts.schema.as_deref());
+        let matches = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|p| names.iter().any(|name| glob_match(p, name)))
+        };
+        let keep = (opts.include_tables.is_empty() || matches(&opts.include_tables))
+            && !matches(&opts.exclude_tables);
+        if !keep {
+            removed.tables.push(t.name.clone());
+        }
+        keep
+    });
+    for table in &mut model.tables {
+        let mut columns = HashSet::new();
+        table.columns.retain(|c| {
+            let names = qualified_names(&table.name, Some(&c.name), opts.schema.as_deref());
+            let excluded = opts
+                .exclude_columns
+                .iter()
+                .any(|p| names.iter().any(|name| glob_match(p, name)));
+            if excluded {
+                columns.insert(c.name.normalized.clone());
+            }
+            !excluded
+        });
+        if !columns.is_empty() {
+            removed.columns.push((table.name.clone(), columns));
+        }
+    }
+
+    // Objects owned by a removed table or column go with it
+    model.indexes.retain(|i| !removed.has_table(&i.table));
+    model
+        .alter_constraints
+        .retain(|a| !removed.has_table(&a.table));
+    model
+        .identity_columns
+        .retain(|ic| !removed.has_table(&ic.table) && !removed.has_column(&ic.table, &ic.column));
+    model.sequences.retain(|s| {
+        s.owned_by
+            .as_ref()
+            .is_none_or(|(t, c)| !removed.has_table(t) && !removed.has_column(t, c))
+    });
+
+    for table in &mut model.tables {
+        let table_name = table.name.clone();
+        for col in &mut table.columns {
+            let obj = format!("{}.{}", table_name.name.normalized, col.name.normalized);
+            if let Some(fk) = &col.references
+                && let Some(reason) = removed.fk_target(&fk.table, fk.column.as_slice())
+            {
+                warnings.push(dropped(&obj, &format!("REFERENCES {}", fk.table), &reason));
+                col.references = None;
+            }
+            if let Some(check) = col.check.take() {
+                col.check = removed.rewrite_check(&table_name, check, &obj, warnings);
+            }
+        }
+        table.constraints = std::mem::take(&mut table.constraints)
+            .into_iter()
+            .filter_map(|c| removed.constraint(&table_name, c, warnings))
+            .collect();
+    }
+    model.alter_constraints = std::mem::take(&mut model.alter_constraints)
+        .into_iter()
+        .filter_map(|a| {
+            let constraint = removed.constraint(&a.table, a.constraint, warnings)?;
+            Some(AlterConstraint {
+                table: a.table,
+                constraint,
+            })
+        })
+        .collect();
+    model.indexes = std::mem::take(&mut model.indexes)
+        .into_iter()
+        .filter_map(|i| removed.index(i, warnings))
+        .collect();
+}
+
+/// Tables and columns removed by the filters.
+#[derive(Default)]
+struct Removed {
+    tables: Vec<QualifiedName>,
+    columns: Vec<(QualifiedName, HashSet<String>)>,
+}
+
+impl Removed {
+    fn has_table(&self, name: &QualifiedName) -> bool {
+        self.tables.iter().any(|t| same_table(t, name))
+    }
+
+    fn columns_of(&self, table: &QualifiedName) -> Option<&HashSet<String>> {
+        self.columns
+            .iter()
+            .find(|(t, _)| same_table(t, table))
+            .map(|(_, columns)| columns)
+    }
+
+    fn has_column(&self, table: &QualifiedName, column: &Ident) -> bool {
+        self.columns_of(table)
+            .is_some_and(|columns| columns.contains(&column.normalized))
+    }
+
+    /// Why a foreign key to `table(columns)` can no longer be enforced.
+    fn fk_target(&self, table: &QualifiedName, columns: &[Ident]) -> Option<String> {
+        if self.has_table(table) {
+            return Some(format!("table {} is excluded", table.name.normalized));
+        }
+        columns.iter().find(|c| self.has_column(table, c)).map(|c| {
+            format!(
+                "column {}.{} is excluded",
+                table.name.normalized, c.normalized
+            )
+        })
+    }
+
+    /// The first removed column among `columns` of `table`.
+    fn first_removed<'a>(&self, table: &QualifiedName, columns: &'a [Ident]) -> Option<&'a Ident> {
+        columns.iter().find(|c| self.has_column(table, c))
+    }
+
+    /// Keep, rewrite or drop a table constraint.
+    fn constraint(
+        &self,
+        table: &QualifiedName,
+        constraint: TableConstraint,
+        warnings: &mut Vec<Warning>,
+    ) -> Option<TableConstraint> {
+        let obj = |name: &Option<Ident>| match name {
+            Some(name) => format!("{}.{}", table.name.normalized, name.normalized),
+            None => table.name.normalized.clone(),
+        };
+        let column_reason = |c: &Ident| {
+            format!(
+                "column {}.{} is excluded",
+                table.name.normalized, c.normalized
+            )
+        };
+
+        match constraint {
+            TableConstraint::PrimaryKey { name, columns } => {
+                if let Some(c) = self.first_removed(table, &columns) {
+                    warnings.push(dropped(&obj(&name), "PRIMARY KEY", &column_reason(c)));
+                    return None;
+                }
+                Some(TableConstraint::PrimaryKey { name, columns })
+            }
+            TableConstraint::Unique { name, columns } => {
+                if let Some(c) = self.first_removed(table, &columns) {
+                    warnings.push(dropped(&obj(&name), "UNIQUE", &column_reason(c)));
+                    return None;
+                }
+                Some(TableConstraint::Unique { name, columns })
+            }
+            TableConstraint::ForeignKey {
+                name,
+                columns,
+                ref_table,
+                ref_columns,
+                on_delete,
+                on_update,
+                deferrable,
+            } => {
+                let reason = match self.first_removed(table, &columns) {
+                    Some(c) => Some(column_reason(c)),
+                    None => self.fk_target(&ref_table, &ref_columns),
+                };
+                if let Some(reason) = reason {
+                    warnings.push(dropped(&obj(&name), "FOREIGN KEY", &reason));
+                    return None;
+                }
+                Some(TableConstraint::ForeignKey {
+                    name,
+                    columns,
+                    ref_table,
+                    ref_columns,
+                    on_delete,
+                    on_update,
+                    deferrable,
+                })
+            }
+            TableConstraint::Check { name, expr } => {
+                let expr = self.rewrite_check(table, expr, &obj(&name), warnings)?;
+                Some(TableConstraint::Check { name, expr })
+            }
+        }
+    }
+
+    /// Drop the AND-ed conditions of a CHECK that mention removed columns.
+    fn rewrite_check(
+        &self,
+        table: &QualifiedName,
+        check: Expr,
+        obj: &str,
+        warnings: &mut Vec<Warning>,
+    ) -> Option<Expr> {
+        let Some(columns) = self.columns_of(table) else {
+            return Some(check);
+        };
+        if !mentions(&check, columns) {
+            return Some(check);
+        }
+        let kept: Vec<Expr> = conjuncts(check)
+            .into_iter()
+            .filter(|c| !mentions(c, columns))
+            .collect();
+        match kept.into_iter().reduce(|l, r| Expr::binary(l, "AND", r)) {
+            Some(check) => {
+                warnings.push(
+                    Warning::new(
+                        warning::FILTER_DEPENDENT_REWRITTEN,
+                        Severity::Info,
+                        "conditions on excluded columns removed from CHECK",
+                    )
+                    .with_object(obj),
+                );
+                Some(check)
+            }
+            None => {
+                warnings.push(dropped(obj, "CHECK", "it references excluded columns"));
+                None
+            }
+        }
+    }
+
+    /// Keep, rewrite or drop an index on a kept table.
+    fn index(&self, mut index: Index, warnings: &mut Vec<Warning>) -> Option<Index> {
+        let Some(columns) = self.columns_of(&index.table) else {
+            return Some(index);
+        };
+        let obj = index.name.normalized.clone();
+        let uses = |c: &IndexColumn| match c {
+            IndexColumn::Column(ident) => columns.contains(&ident.normalized),
+            IndexColumn::Expression(expr) => mentions(expr, columns),
+        };
+        let where_uses = index
+            .where_clause
+            .as_ref()
+            .is_some_and(|w| mentions(w, columns));
+        if !where_uses && !index.columns.iter().any(uses) {
+            return Some(index);
+        }
+
+        let remaining = index.columns.iter().filter(|c| !uses(c)).count();
+        if index.unique || where_uses || remaining == 0 {
+            warnings.push(dropped(&obj, "index", "it references excluded columns"));
+            return None;
+        }
+        index.columns.retain(|c| !uses(c));
+        warnings.push(
+            Warning::new(
+                warning::FILTER_DEPENDENT_REWRITTEN,
+                Severity::Info,
+                "excluded columns removed from index",
+            )
+            .with_object(&obj),
+        );
+        Some(index)
+    }
+}
+
+fn dropped(obj: &str, what: &str, reason: &str) -> Warning {
+    Warning::new(
+        warning::FILTER_DEPENDENT_DROPPED,
+        Severity::Info,
+        format!("{what} dropped: {reason}"),
+    )
+    .with_object(obj)
+}
+
+/// Names a table, or a column of it, can be matched by.
+fn qualified_names(
+    table: &QualifiedName,
+    column: Option<&Ident>,
+    default_schema: Option<&str>,
+) -> Vec<String> {
+    let suffix = column
+        .map(|c| format!(".{}", c.normalized))
+        .unwrap_or_default();
+    let mut names = vec![format!("{}{suffix}", table.name.normalized)];
+    let schema = table
+        .schema
+        .as_ref()
+        .map(|s| s.normalized.as_str())
+        .or(default_schema);
+    if let Some(schema) = schema {
+        names.push(format!("{schema}.{}{suffix}", table.name.normalized));
+    }
+    names
+}
+
+/// Names match by table name; schemas only need to agree when both are given.
+fn same_table(a: &QualifiedName, b: &QualifiedName) -> bool {
+    a.name.normalized == b.name.normalized
+        && match (&a.schema, &b.schema) {
+            (Some(x), Some(y)) => x.normalized == y.normalized,
+            _ => true,
+        }
+}
+
+/// Whether an expression references one of `columns`.
+fn mentions(expr: &Expr, columns: &HashSet<String>) -> bool {
+    let mut found = false;
+    expr.clone().walk_mut(&mut |e| {
+        if let Expr::ColumnRef(name) = e {
+            let column = name.rsplit('.').next().unwrap_or(name);
+            found |= columns.contains(column);
+        }
+    });
+    found
+}
+
+/// Split a condition into its top-level AND-ed parts.
+fn conjuncts(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::BinaryOp { left, op, right } if op.eq_ignore_ascii_case("AND") => {
+            let mut parts = conjuncts(*left);
+            parts.extend(conjuncts(*right));
+            parts
+        }
+        Expr::Nested(inner) if matches!(&*inner, Expr::BinaryOp { op, .. } if op.eq_ignore_ascii_case("AND")) => {
+            conjuncts(*inner)
+        }
+        other => vec![other],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg::parser;
+
+    fn filtered(sql: &str, opts: FilterOptions) -> (SchemaModel, Vec<Warning>) {
+        let (mut model, _) = parser::parse(sql);
+        let mut warnings = Vec::new();
+        apply_filters(&mut model, &opts, &mut warnings);
+        (model, warnings)
+    }
+
+    fn table_names(model: &SchemaModel) -> Vec<&str> {
+        model
+            .tables
+            .iter()
+            .map(|t| t.name.name.normalized.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_include_and_exclude_tables() {
+        let sql = r#"
+            CREATE TABLE users (id INTEGER PRIMARY KEY);
+            CREATE TABLE audit_log (id INTEGER, user_id INTEGER REFERENCES users(id));
+            CREATE TABLE audit_archive (id INTEGER);
+            CREATE TABLE orders (id INTEGER, user_id INTEGER REFERENCES users(id));
+            CREATE INDEX idx_audit_user ON audit_log (user_id);
+        "#;
+        let (model, w) = filtered(
+            sql,
+            FilterOptions {
+                exclude_tables: vec!["audit_*".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(table_names(&model), ["users", "orders"]);
+        assert!(model.indexes.is_empty());
+        assert!(w.is_empty());
+
+        let (model, w) = filtered(
+            sql,
+            FilterOptions {
+                schema: Some("public".to_string()),
+                include_tables: vec!["public.orders".to_string(), "audit_log".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(table_names(&model), ["audit_log", "orders"]);
+        assert!(
+            model
+                .tables
+                .iter()
+                .all(|t| t.columns[1].references.is_none())
+        );
+        assert_eq!(w.len(), 2);
+        assert_eq!(w[0].code, warning::FILTER_DEPENDENT_DROPPED);
+        assert_eq!(w[0].object.as_deref(), Some("audit_log.user_id"));
+        assert!(w[0].message.contains("table users is excluded"));
+    }
+
+    #[test]
+    fn test_exclude_columns_rewrites_dependents() {
+        let sql = r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                email TEXT NOT NULL,
+                password_hash TEXT,
+                salt TEXT,
+                CONSTRAINT users_secret_check CHECK (email <> '' AND length(password_hash) = 60),
+                CONSTRAINT users_salt_check CHECK (salt IS NOT NULL OR password_hash IS NULL),
+                UNIQUE (email, password_hash)
+            );
+            CREATE TABLE sessions (id INTEGER, user_salt TEXT);
+            ALTER TABLE sessions ADD CONSTRAINT sessions_salt_fkey
+                FOREIGN KEY (user_salt) REFERENCES users(salt);
+            CREATE INDEX idx_users_email_hash ON users (email, password_hash);
+            CREATE UNIQUE INDEX idx_users_hash ON users (password_hash);
+            CREATE INDEX idx_users_salt ON users (lower(salt));
+        "#;
+        let (model, w) = filtered(
+            sql,
+            FilterOptions {
+                exclude_columns: vec!["users.password_hash".to_string(), "*.salt".to_string()],
+                ..Default::default()
+            },
+        );
+        let users = &model.tables[0];
+        let columns: Vec<&str> = users
+            .columns
+            .iter()
+            .map(|c| c.name.normalized.as_str())
+            .collect();
+        assert_eq!(columns, ["id", "email"]);
+        assert_eq!(users.constraints.len(), 1);
+        let TableConstraint::Check { expr, .. } = &users.constraints[0] else {
+            panic!("expected CHECK");
+        };
+        assert_eq!(expr.to_sql(), "email <> ''");
+        assert_eq!(model.tables[1].columns.len(), 2);
+        assert!(model.alter_constraints.is_empty());
+
+        assert_eq!(model.indexes.len(), 1);
+        assert_eq!(model.indexes[0].name.normalized, "idx_users_email_hash");
+        assert_eq!(model.indexes[0].columns.len(), 1);
+
+        let diagnostics: Vec<(&str, &str)> = w
+            .iter()
+            .map(|w| (w.code, w.object.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    warning::FILTER_DEPENDENT_REWRITTEN,
+                    "users.users_secret_check"
+                ),
+                (warning::FILTER_DEPENDENT_DROPPED, "users.users_salt_check"),
+                (warning::FILTER_DEPENDENT_DROPPED, "users"),
+                (
+                    warning::FILTER_DEPENDENT_DROPPED,
+                    "sessions.sessions_salt_fkey"
+                ),
+                (warning::FILTER_DEPENDENT_REWRITTEN, "idx_users_email_hash"),
+                (warning::FILTER_DEPENDENT_DROPPED, "idx_users_hash"),
+                (warning::FILTER_DEPENDENT_DROPPED, "idx_users_salt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exclude_wins_over_include() {
+        let sql = r#"
+            CREATE TABLE audit_log (id INTEGER);
+            CREATE TABLE audit_archive (id INTEGER);
+            CREATE TABLE users (id INTEGER);
+        "#;
+        let (model, w) = filtered(
+            sql,
+            FilterOptions {
+                include_tables: vec!["audit_*".to_string(), "audit_archive".to_string()],
+                exclude_tables: vec!["*_archive".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(table_names(&model), ["audit_log"]);
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_schema_qualified_patterns() {
+        let sql = r#"
+            CREATE TABLE app.users (id INTEGER, email TEXT);
+            CREATE TABLE billing.users (id INTEGER, email TEXT);
+            CREATE TABLE billing.invoices (id INTEGER, total NUMERIC);
+            CREATE TABLE settings (key TEXT, email TEXT);
+        "#;
+        let names = |model: &SchemaModel| -> Vec<String> {
+            model.tables.iter().map(|t| t.name.to_string()).collect()
+        };
+
+        // A schema pattern leaves same-named tables of other schemas alone
+        let (model, _) = filtered(
+            sql,
+            FilterOptions {
+                schema: Some("app".to_string()),
+                exclude_tables: vec!["billing.*".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(names(&model), ["app.users", "settings"]);
+
+        // Unqualified tables match through the default schema
+        let (model, _) = filtered(
+            sql,
+            FilterOptions {
+                schema: Some("app".to_string()),
+                include_tables: vec!["app.*".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(names(&model), ["app.users", "settings"]);
+
+        // A bare name matches in every schema
+        let (model, _) = filtered(
+            sql,
+            FilterOptions {
+                exclude_tables: vec!["users".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(names(&model), ["billing.invoices", "settings"]);
+
+        let (model, _) = filtered(
+            sql,
+            FilterOptions {
+                exclude_columns: vec!["billing.*.email".to_string()],
+                ..Default::default()
+            },
+        );
+        let columns: Vec<usize> = model.tables.iter().map(|t| t.columns.len()).collect();
+        assert_eq!(columns, [2, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_excluded_table_drops_dependents() {
+        let sql = r#"
+            CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT);
+            CREATE TABLE orders (
+                id INTEGER,
+                user_id INTEGER REFERENCES users(id),
+                buyer_id INTEGER,
+                CONSTRAINT orders_buyer_fkey FOREIGN KEY (buyer_id) REFERENCES users(id),
+                CONSTRAINT orders_id_check CHECK (id > 0)
+            );
+            CREATE TABLE reviews (id INTEGER, author_id INTEGER);
+            ALTER TABLE reviews ADD CONSTRAINT reviews_author_fkey
+                FOREIGN KEY (author_id) REFERENCES users(id);
+            ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
+            ALTER TABLE users ALTER COLUMN id ADD GENERATED ALWAYS AS IDENTITY;
+            CREATE INDEX idx_users_email ON users (email);
+            CREATE INDEX idx_orders_user ON orders (user_id);
+        "#;
+        let (model, w) = filtered(
+            sql,
+            FilterOptions {
+                exclude_tables: vec!["users".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(table_names(&model), ["orders", "reviews"]);
+
+        // Owned by the removed table: dropped without a warning
+        let indexes: Vec<&str> = model
+            .indexes
+            .iter()
+            .map(|i| i.name.normalized.as_str())
+            .collect();
+        assert_eq!(indexes, ["idx_orders_user"]);
+        assert!(model.identity_columns.is_empty());
+        assert!(model.alter_constraints.is_empty());
+
+        // Referencing it from kept tables: dropped with a warning
+        let orders = &model.tables[0];
+        assert!(orders.columns[1].references.is_none());
+        assert_eq!(orders.constraints.len(), 1);
+        assert!(matches!(
+            orders.constraints[0],
+            TableConstraint::Check { .. }
+        ));
+        let diagnostics: Vec<(&str, &str)> = w
+            .iter()
+            .map(|w| (w.code, w.object.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            [
+                (warning::FILTER_DEPENDENT_DROPPED, "orders.user_id"),
+                (
+                    warning::FILTER_DEPENDENT_DROPPED,
+                    "orders.orders_buyer_fkey"
+                ),
+                (
+                    warning::FILTER_DEPENDENT_DROPPED,
+                    "reviews.reviews_author_fkey"
+                ),
+            ]
+        );
+        assert!(
+            w.iter()
+                .all(|w| w.message.contains("table users is excluded"))
+        );
+    }
+}
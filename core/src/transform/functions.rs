@@ -0,0 +1,819 @@
+/// PG function → SQLite translation table.
+use crate::ir::Expr;
+
+/// How a PG function call is translated, given its already-mapped arguments.
+#[derive(Debug, Clone, Copy)]
+pub enum Translation {
+    /// Same name and arguments in SQLite.
+    Same,
+    /// Same arguments under another SQLite name.
+    Rename(&'static str),
+    /// Rewritten into an equivalent expression; `None` when the arguments can't be translated.
+    Rewrite(fn(Vec<Expr>) -> Option<Expr>),
+    /// Kept as is; not built into SQLite, so the application must register it.
+    Extension,
+}
+
+/// Supported PG functions, keyed by lowercase name.
+const FUNCTIONS: &[(&str, Translation)] = &[
+    // Built into both
+    ("abs", Translation::Same),
+    ("avg", Translation::Same),
+    ("coalesce", Translation::Same),
+    ("count", Translation::Same),
+    ("group_concat", Translation::Same),
+    ("hex", Translation::Same),
+    ("instr", Translation::Same),
+    ("length", Translation::Same),
+    ("lower", Translation::Same),
+    ("ltrim", Translation::Same),
+    ("max", Translation::Same),
+    ("min", Translation::Same),
+    ("nullif", Translation::Same),
+    ("quote", Translation::Same),
+    ("randomblob", Translation::Same),
+    ("replace", Translation::Same),
+    ("round", Translation::Same),
+    ("rtrim", Translation::Same),
+    ("substr", Translation::Same),
+    ("sum", Translation::Same),
+    ("total", Translation::Same),
+    ("trim", Translation::Same),
+    ("typeof", Translation::Same),
+    ("unhex", Translation::Same),
+    ("unicode", Translation::Same),
+    ("upper", Translation::Same),
+    ("zeroblob", Translation::Same),
+    // Strings
+    ("btrim", Translation::Rename("trim")),
+    ("char_length", Translation::Rename("length")),
+    ("character_length", Translation::Rename("length")),
+    ("strpos", Translation::Rename("instr")),
+    ("octet_length", Translation::Rewrite(octet_length)),
+    ("left", Translation::Rewrite(left)),
+    ("right", Translation::Rewrite(right)),
+    ("concat", Translation::Rewrite(concat)),
+    ("concat_ws", Translation::Rewrite(concat_ws)),
+    ("split_part", Translation::Rewrite(split_part)),
+    ("md5", Translation::Extension),
+    // Date/time
+    ("now", Translation::Rewrite(current_timestamp)),
+    ("current_timestamp", Translation::Rewrite(current_timestamp)),
+    (
+        "transaction_timestamp",
+        Translation::Rewrite(current_timestamp),
+    ),
+    (
+        "statement_timestamp",
+        Translation::Rewrite(current_timestamp),
+    ),
+    ("current_date", Translation::Rewrite(current_date)),
+    ("current_time", Translation::Rewrite(current_time)),
+    ("localtimestamp", Translation::Rewrite(localtimestamp)),
+    ("localtime", Translation::Rewrite(localtime)),
+    ("clock_timestamp", Translation::Rewrite(clock_timestamp)),
+    ("date_trunc", Translation::Rewrite(date_trunc)),
+    ("to_char", Translation::Rewrite(to_char)),
+    // Math
+    ("greatest", Translation::Rewrite(greatest)),
+    ("least", Translation::Rewrite(least)),
+    ("random", Translation::Rewrite(random)),
+];
+
+/// Look up the translation for a PG function.
+pub fn lookup(name: &str) -> Option<Translation> {
+    FUNCTIONS
+        .iter()
+        .find(|(function, _)| *function == name)
+        .map(|(_, translation)| *translation)
+}
+
+fn text(value: &str) -> Expr {
+    Expr::StringLiteral(value.to_string())
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::IntegerLiteral(_) | Expr::FloatLiteral(_) | Expr::StringLiteral(_)
+    )
+}
+
+/// Integer literal value, looking through a unary minus.
+fn integer_literal(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::IntegerLiteral(n) => Some(*n),
+        Expr::UnaryOp { op, expr } if op == "-" => integer_literal(expr).map(|n| -n),
+        _ => None,
+    }
+}
+
+fn string_literal(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::StringLiteral(s) => Some(s),
+        _ => None,
+    }
+}
+
+/// `x` with NULL replaced by `''`, as PG's concat functions skip NULL arguments.
+fn or_empty(expr: Expr) -> Expr {
+    if is_literal(&expr) {
+        expr
+    } else {
+        Expr::func("coalesce", vec![expr, text("")])
+    }
+}
+
+/// Join expressions with `||`.
+fn concatenate(parts: Vec<Expr>) -> Option<Expr> {
+    parts
+        .into_iter()
+        .reduce(|joined, part| Expr::binary(joined, "||", part))
+}
+
+fn case_when(condition: Expr, then: Expr, otherwise: Expr) -> Expr {
+    Expr::Case {
+        operand: None,
+        branches: vec![(condition, then)],
+        else_result: Some(std::boxed::Box::new(otherwise)),
+    }
+}
+
+/// octet_length(x) → length(CAST(x AS BLOB)), counting bytes instead of characters.
+fn octet_length(args: Vec<Expr>) -> Option<Expr> {
+    let [value] = <[Expr; 1]>::try_from(args).ok()?;
+    let blob = Expr::Cast {
+        expr: std::boxed::Box::new(value),
+        type_name: "BLOB".to_string(),
+    };
+    Some(Expr::func("length", vec![blob]))
+}
+
+/// left(s, n) → substr(s, 1, n); a negative `n` drops characters from the end.
+fn left(args: Vec<Expr>) -> Option<Expr> {
+    let [string, count] = <[Expr; 2]>::try_from(args).ok()?;
+    let length = Expr::func("length", vec![string.clone()]);
+    let count = match integer_literal(&count) {
+        Some(n) if n >= 0 => count,
+        Some(n) => Expr::binary(length, "-", Expr::IntegerLiteral(-n)),
+        None => case_when(
+            Expr::binary(count.clone(), "<", Expr::IntegerLiteral(0)),
+            Expr::func(
+                "max",
+                vec![
+                    Expr::binary(length, "+", count.clone()),
+                    Expr::IntegerLiteral(0),
+                ],
+            ),
+            count,
+        ),
+    };
+    Some(Expr::func(
+        "substr",
+        vec![string, Expr::IntegerLiteral(1), count],
+    ))
+}
+
+/// right(s, n) → the last `n` characters; a negative `n` drops characters from the start.
+fn right(args: Vec<Expr>) -> Option<Expr> {
+    let [string, count] = <[Expr; 2]>::try_from(args).ok()?;
+    // substr(s, -n) would return the whole string for n = 0
+    let from_end = |count: Expr| {
+        Expr::binary(
+            Expr::func(
+                "max",
+                vec![
+                    Expr::binary(Expr::func("length", vec![string.clone()]), "-", count),
+                    Expr::IntegerLiteral(0),
+                ],
+            ),
+            "+",
+            Expr::IntegerLiteral(1),
+        )
+    };
+    let start = match integer_literal(&count) {
+        Some(n) if n > 0 => Expr::IntegerLiteral(-n),
+        Some(n) if n < 0 => Expr::IntegerLiteral(1 - n),
+        Some(_) => return Some(text("")),
+        None => case_when(
+            Expr::binary(count.clone(), "<", Expr::IntegerLiteral(0)),
+            Expr::binary(Expr::IntegerLiteral(1), "-", count.clone()),
+            from_end(count),
+        ),
+    };
+    Some(Expr::func("substr", vec![string, start]))
+}
+
+/// concat(a, b, …) → coalesce(a, '') || coalesce(b, '') || …
+fn concat(args: Vec<Expr>) -> Option<Expr> {
+    concatenate(args.into_iter().map(or_empty).collect())
+}
+
+/// concat_ws(sep, a, b, …) → separator-prefixed non-NULL parts with the first separator cut off.
+fn concat_ws(args: Vec<Expr>) -> Option<Expr> {
+    let mut args = args.into_iter();
+    let separator = args.next()?;
+    let parts: Vec<Expr> = args
+        .map(|part| or_empty(Expr::binary(separator.clone(), "||", part)))
+        .collect();
+    let joined = concatenate(parts)?;
+    let start = Expr::binary(
+        Expr::func("length", vec![separator]),
+        "+",
+        Expr::IntegerLiteral(1),
+    );
+    Some(Expr::func("substr", vec![joined, start]))
+}
+
+/// Largest field number `split_part` is expanded for; each field doubles the expression.
+const SPLIT_PART_MAX_FIELD: i64 = 3;
+
+/// split_part(s, 'delim', n) for a literal delimiter and a small literal field number.
+///
+/// A trailing delimiter is appended so every field ends in one; each step then
+/// cuts up to and including the next delimiter.
+fn split_part(args: Vec<Expr>) -> Option<Expr> {
+    let [string, delimiter, field] = <[Expr; 3]>::try_from(args).ok()?;
+    let width = string_literal(&delimiter)?.chars().count() as i64;
+    let field = integer_literal(&field).filter(|n| (1..=SPLIT_PART_MAX_FIELD).contains(n))?;
+    if width == 0 {
+        return None;
+    }
+
+    let position = |rest: &Expr| Expr::func("instr", vec![rest.clone(), delimiter.clone()]);
+    let mut rest = Expr::binary(string, "||", delimiter.clone());
+    for _ in 1..field {
+        let start = Expr::binary(position(&rest), "+", Expr::IntegerLiteral(width));
+        rest = Expr::func("substr", vec![rest, start]);
+    }
+    let length = Expr::binary(position(&rest), "-", Expr::IntegerLiteral(1));
+    Some(Expr::func(
+        "substr",
+        vec![rest, Expr::IntegerLiteral(1), length],
+    ))
+}
+
+fn current_timestamp(_args: Vec<Expr>) -> Option<Expr> {
+    Some(Expr::CurrentTimestamp)
+}
+
+fn current_date(args: Vec<Expr>) -> Option<Expr> {
+    args.is_empty()
+        .then(|| Expr::Raw("CURRENT_DATE".to_string()))
+}
+
+fn current_time(_args: Vec<Expr>) -> Option<Expr> {
+    Some(Expr::Raw("CURRENT_TIME".to_string()))
+}
+
+fn localtimestamp(_args: Vec<Expr>) -> Option<Expr> {
+    Some(Expr::func("datetime", vec![text("now"), text("localtime")]))
+}
+
+fn localtime(_args: Vec<Expr>) -> Option<Expr> {
+    Some(Expr::func("time", vec![text("now"), text("localtime")]))
+}
+
+/// clock_timestamp() → the current time with milliseconds.
+fn clock_timestamp(args: Vec<Expr>) -> Option<Expr> {
+    args.is_empty()
+        .then(|| Expr::func("strftime", vec![text("%Y-%m-%d %H:%M:%f"), text("now")]))
+}
+
+/// date_trunc('field', ts) for a literal field, via datetime() modifiers or strftime().
+fn date_trunc(args: Vec<Expr>) -> Option<Expr> {
+    let [field, timestamp] = <[Expr; 2]>::try_from(args).ok()?;
+    let field = string_literal(&field)?.to_ascii_lowercase();
+    let datetime = |modifiers: Vec<Expr>| {
+        let mut args = vec![timestamp.clone()];
+        args.extend(modifiers);
+        Some(Expr::func("datetime", args))
+    };
+    let strftime = |format: &str| {
+        Some(Expr::func(
+            "strftime",
+            vec![text(format), timestamp.clone()],
+        ))
+    };
+    match field.as_str() {
+        "year" => datetime(vec![text("start of year")]),
+        "month" => datetime(vec![text("start of month")]),
+        "day" => datetime(vec![text("start of day")]),
+        // Back to the previous Monday, unless it already is one
+        "week" => datetime(vec![
+            text("-6 days"),
+            text("weekday 1"),
+            text("start of day"),
+        ]),
+        "quarter" => {
+            let month = Expr::Cast {
+                expr: std::boxed::Box::new(Expr::func(
+                    "strftime",
+                    vec![text("%m"), timestamp.clone()],
+                )),
+                type_name: "INTEGER".to_string(),
+            };
+            let months_back = Expr::binary(
+                Expr::binary(month, "-", Expr::IntegerLiteral(1)),
+                "%",
+                Expr::IntegerLiteral(3),
+            );
+            datetime(vec![
+                text("start of month"),
+                Expr::binary(
+                    Expr::binary(text("-"), "||", months_back),
+                    "||",
+                    text(" months"),
+                ),
+            ])
+        }
+        "hour" => strftime("%Y-%m-%d %H:00:00"),
+        "minute" => strftime("%Y-%m-%d %H:%M:00"),
+        "second" => strftime("%Y-%m-%d %H:%M:%S"),
+        _ => None,
+    }
+}
+
+/// to_char template patterns with a strftime() equivalent, longest first.
+const TO_CHAR_PATTERNS: &[(&str, &str)] = &[
+    ("YYYY", "%Y"),
+    ("HH24", "%H"),
+    ("DDD", "%j"),
+    ("DD", "%d"),
+    ("MM", "%m"),
+    ("MI", "%M"),
+    ("SS", "%S"),
+];
+
+/// to_char(ts, 'template') → strftime() when every pattern in the template is supported.
+fn to_char(args: Vec<Expr>) -> Option<Expr> {
+    let [value, template] = <[Expr; 2]>::try_from(args).ok()?;
+    let format = strftime_format(string_literal(&template)?)?;
+    Some(Expr::func("strftime", vec![text(&format), value]))
+}
+
+/// Translate a to_char date/time template to a strftime() format.
+///
+/// Letters outside the supported patterns are rejected rather than copied, since
+/// most of them are PG patterns; digits are rejected as numeric templates.
+fn strftime_format(template: &str) -> Option<String> {
+    let mut format = String::new();
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if let Some((pattern, code)) = TO_CHAR_PATTERNS
+            .iter()
+            .find(|(pattern, _)| rest.starts_with(pattern))
+        {
+            format.push_str(code);
+            rest = &rest[pattern.len()..];
+            continue;
+        }
+        rest = &rest[c.len_utf8()..];
+        match c {
+            // Double-quoted text is copied literally
+            '"' => {
+                let end = rest.find('"')?;
+                format.push_str(&rest[..end].replace('%', "%%"));
+                rest = &rest[end + 1..];
+            }
+            '%' => format.push_str("%%"),
+            c if c.is_alphanumeric() => return None,
+            c => format.push(c),
+        }
+    }
+    Some(format)
+}
+
+fn greatest(args: Vec<Expr>) -> Option<Expr> {
+    ignoring_nulls("max", args)
+}
+
+fn least(args: Vec<Expr>) -> Option<Expr> {
+    ignoring_nulls("min", args)
+}
+
+/// greatest/least → max/min, which would return NULL if any argument is NULL.
+///
+/// Each argument falls back to the others, so a NULL stands in for a value
+/// that is already compared and only all-NULL arguments give NULL.
+fn ignoring_nulls(function: &str, args: Vec<Expr>) -> Option<Expr> {
+    if args.len() < 2 {
+        return args.into_iter().next();
+    }
+    let compared = (0..args.len())
+        .map(|i| {
+            if is_literal(&args[i]) {
+                return args[i].clone();
+            }
+            let mut candidates = vec![args[i].clone()];
+            candidates.extend(
+                args.iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, arg)| arg.clone()),
+            );
+            Expr::func("coalesce", candidates)
+        })
+        .collect();
+    Some(Expr::func(function, compared))
+}
+
+/// random() → a float in [0, 1) from SQLite's 64-bit random integer.
+fn random(args: Vec<Expr>) -> Option<Expr> {
+    if !args.is_empty() {
+        return None;
+    }
+    // 2^64, rendered as an out-of-range integer that SQLite reads as REAL
+    Some(Expr::binary(
+        Expr::binary(
+            Expr::func("random", vec![]),
+            "/",
+            Expr::FloatLiteral(18446744073709551616.0),
+        ),
+        "+",
+        Expr::FloatLiteral(0.5),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn col(name: &str) -> Expr {
+        Expr::ColumnRef(name.to_string())
+    }
+
+    fn translate(name: &str, args: Vec<Expr>) -> Option<String> {
+        match lookup(name)? {
+            Translation::Same | Translation::Extension => Some(Expr::func(name, args)),
+            Translation::Rename(to) => Some(Expr::func(to, args)),
+            Translation::Rewrite(rewrite) => rewrite(args),
+        }
+        .map(|e| e.to_sql())
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert!(matches!(lookup("lower"), Some(Translation::Same)));
+        assert!(matches!(
+            lookup("char_length"),
+            Some(Translation::Rename("length"))
+        ));
+        assert!(matches!(lookup("md5"), Some(Translation::Extension)));
+        assert!(lookup("txid_current").is_none());
+    }
+
+    #[test]
+    fn test_string_functions() {
+        assert_eq!(
+            translate("octet_length", vec![col("s")]).as_deref(),
+            Some("length(CAST(s AS BLOB))")
+        );
+        assert_eq!(
+            translate("left", vec![col("s"), Expr::IntegerLiteral(2)]).as_deref(),
+            Some("substr(s, 1, 2)")
+        );
+        assert_eq!(
+            translate(
+                "left",
+                vec![
+                    col("s"),
+                    Expr::UnaryOp {
+                        op: "-".to_string(),
+                        expr: std::boxed::Box::new(Expr::IntegerLiteral(2)),
+                    },
+                ],
+            )
+            .as_deref(),
+            Some("substr(s, 1, length(s) - 2)")
+        );
+        assert_eq!(
+            translate("left", vec![col("s"), col("n")]).as_deref(),
+            Some("substr(s, 1, CASE WHEN n < 0 THEN max(length(s) + n, 0) ELSE n END)")
+        );
+        assert_eq!(
+            translate("right", vec![col("s"), Expr::IntegerLiteral(2)]).as_deref(),
+            Some("substr(s, -2)")
+        );
+        assert_eq!(
+            translate("right", vec![col("s"), Expr::IntegerLiteral(0)]).as_deref(),
+            Some("''")
+        );
+        assert_eq!(
+            translate("right", vec![col("s"), col("n")]).as_deref(),
+            Some("substr(s, CASE WHEN n < 0 THEN 1 - n ELSE max(length(s) - n, 0) + 1 END)")
+        );
+        assert_eq!(
+            translate("concat", vec![col("a"), text("-"), col("b")]).as_deref(),
+            Some("coalesce(a, '') || '-' || coalesce(b, '')")
+        );
+        assert_eq!(
+            translate("concat_ws", vec![text(", "), col("a"), col("b")]).as_deref(),
+            Some("substr(coalesce(', ' || a, '') || coalesce(', ' || b, ''), length(', ') + 1)")
+        );
+        assert_eq!(translate("concat_ws", vec![]), None);
+        assert_eq!(
+            translate("btrim", vec![col("s")]).as_deref(),
+            Some("trim(s)")
+        );
+        assert_eq!(
+            translate("strpos", vec![col("s"), text("@")]).as_deref(),
+            Some("instr(s, '@')")
+        );
+    }
+
+    #[test]
+    fn test_split_part() {
+        assert_eq!(
+            translate(
+                "split_part",
+                vec![col("s"), text(","), Expr::IntegerLiteral(1)]
+            )
+            .as_deref(),
+            Some("substr(s || ',', 1, instr(s || ',', ',') - 1)")
+        );
+        assert_eq!(
+            translate(
+                "split_part",
+                vec![col("s"), text("::"), Expr::IntegerLiteral(2)]
+            )
+            .as_deref(),
+            Some(
+                "substr(substr(s || '::', instr(s || '::', '::') + 2), 1, \
+                 instr(substr(s || '::', instr(s || '::', '::') + 2), '::') - 1)"
+            )
+        );
+        // Field out of range, empty or non-literal delimiter
+        for (delimiter, field) in [(text(","), 4), (text(""), 1), (col("d"), 1)] {
+            assert_eq!(
+                translate(
+                    "split_part",
+                    vec![col("s"), delimiter, Expr::IntegerLiteral(field)]
+                ),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_functions() {
+        assert_eq!(
+            translate("current_timestamp", vec![]).as_deref(),
+            Some("CURRENT_TIMESTAMP")
+        );
+        assert_eq!(
+            translate("current_date", vec![]).as_deref(),
+            Some("CURRENT_DATE")
+        );
+        assert_eq!(
+            translate("localtimestamp", vec![]).as_deref(),
+            Some("datetime('now', 'localtime')")
+        );
+        assert_eq!(
+            translate("clock_timestamp", vec![]).as_deref(),
+            Some("strftime('%Y-%m-%d %H:%M:%f', 'now')")
+        );
+        assert_eq!(
+            translate("date_trunc", vec![text("Month"), col("ts")]).as_deref(),
+            Some("datetime(ts, 'start of month')")
+        );
+        assert_eq!(
+            translate("date_trunc", vec![text("week"), col("ts")]).as_deref(),
+            Some("datetime(ts, '-6 days', 'weekday 1', 'start of day')")
+        );
+        assert_eq!(
+            translate("date_trunc", vec![text("quarter"), col("ts")]).as_deref(),
+            Some(
+                "datetime(ts, 'start of month', \
+                 '-' || ((CAST(strftime('%m', ts) AS INTEGER) - 1) % 3) || ' months')"
+            )
+        );
+        assert_eq!(
+            translate("date_trunc", vec![text("hour"), col("ts")]).as_deref(),
+            Some("strftime('%Y-%m-%d %H:00:00', ts)")
+        );
+        assert_eq!(
+            translate("date_trunc", vec![text("millennium"), col("ts")]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_to_char() {
+        assert_eq!(
+            translate("to_char", vec![col("ts"), text("YYYY-MM-DD HH24:MI:SS")]).as_deref(),
+            Some("strftime('%Y-%m-%d %H:%M:%S', ts)")
+        );
+        assert_eq!(
+            translate("to_char", vec![col("ts"), text(r#""day" DDD, YYYY %"#)]).as_deref(),
+            Some("strftime('day %j, %Y %%', ts)")
+        );
+        // Unsupported patterns and numeric templates
+        for template in ["Mon DD", "HH:MI", "999.99"] {
+            assert_eq!(translate("to_char", vec![col("ts"), text(template)]), None);
+        }
+    }
+
+    #[test]
+    fn test_greatest_least() {
+        assert_eq!(
+            translate(
+                "greatest",
+                vec![col("a"), col("b"), Expr::IntegerLiteral(0)]
+            )
+            .as_deref(),
+            Some("max(coalesce(a, b, 0), coalesce(b, a, 0), 0)")
+        );
+        assert_eq!(
+            translate("least", vec![col("a"), col("b")]).as_deref(),
+            Some("min(coalesce(a, b), coalesce(b, a))")
+        );
+        assert_eq!(translate("least", vec![col("a")]).as_deref(), Some("a"));
+    }
+
+    /// Evaluates every table entry in SQLite against one row of sample values,
+    /// checking each result (`v`) with a predicate.
+    #[test]
+    fn test_functions_in_sqlite() {
+        let int = Expr::IntegerLiteral;
+        let cases: Vec<(&str, Vec<Expr>, &str)> = vec![
+            ("abs", vec![col("n")], "v = 7"),
+            ("avg", vec![col("n")], "v = -7.0"),
+            ("coalesce", vec![col("d"), col("s")], "v = s"),
+            ("count", vec![col("d")], "v = 0"),
+            ("group_concat", vec![col("s")], "v = s"),
+            ("hex", vec![text("ab")], "v = '6162'"),
+            ("instr", vec![col("s"), text("w")], "v = 8"),
+            ("length", vec![col("s")], "v = 12"),
+            ("lower", vec![col("s")], "v = 'héllo, wörld'"),
+            ("ltrim", vec![text("  x ")], "v = 'x '"),
+            ("max", vec![col("n")], "v = -7"),
+            ("min", vec![col("n")], "v = -7"),
+            ("nullif", vec![col("n"), int(-7)], "v IS NULL"),
+            ("quote", vec![text("it's")], "v = '''it''''s'''"),
+            ("randomblob", vec![int(4)], "length(v) = 4"),
+            (
+                "replace",
+                vec![col("s"), text("l"), text("L")],
+                "v = 'HéLLo, wörLd'",
+            ),
+            ("round", vec![Expr::FloatLiteral(2.5)], "v = 3"),
+            ("rtrim", vec![text(" x  ")], "v = ' x'"),
+            ("substr", vec![col("s"), int(2), int(3)], "v = 'éll'"),
+            ("sum", vec![col("n")], "v = -7"),
+            ("total", vec![col("d")], "v = 0.0"),
+            ("trim", vec![text("  x  ")], "v = 'x'"),
+            ("typeof", vec![col("n")], "v = 'integer'"),
+            ("unhex", vec![text("6162")], "v = X'6162'"),
+            ("unicode", vec![text("é")], "v = 233"),
+            ("upper", vec![col("s")], "v = 'HéLLO, WöRLD'"),
+            ("zeroblob", vec![int(2)], "v = X'0000'"),
+            ("btrim", vec![text("  x  ")], "v = 'x'"),
+            ("char_length", vec![col("s")], "v = 12"),
+            ("character_length", vec![col("s")], "v = 12"),
+            ("strpos", vec![col("s"), text("w")], "v = 8"),
+            ("octet_length", vec![col("s")], "v = 14"),
+            ("left", vec![col("s"), int(5)], "v = 'Héllo'"),
+            ("left", vec![col("s"), int(-7)], "v = 'Héllo'"),
+            ("left", vec![col("s"), col("n")], "v = 'Héllo'"),
+            ("left", vec![col("s"), int(-20)], "v = ''"),
+            ("right", vec![col("s"), int(5)], "v = 'wörld'"),
+            ("right", vec![col("s"), int(-7)], "v = 'wörld'"),
+            ("right", vec![col("s"), col("n")], "v = 'wörld'"),
+            ("right", vec![col("s"), int(0)], "v = ''"),
+            (
+                "concat",
+                vec![col("s"), col("d"), text("!")],
+                "v = s || '!'",
+            ),
+            (
+                "concat_ws",
+                vec![text(" - "), text("a"), col("d"), text("b")],
+                "v = 'a - b'",
+            ),
+            ("concat_ws", vec![text("-"), col("d")], "v = ''"),
+            (
+                "split_part",
+                vec![col("s"), text(", "), int(1)],
+                "v = 'Héllo'",
+            ),
+            (
+                "split_part",
+                vec![col("s"), text(", "), int(2)],
+                "v = 'wörld'",
+            ),
+            ("split_part", vec![col("s"), text(", "), int(3)], "v = ''"),
+            ("md5", vec![col("s")], "v = 'md5:' || s"),
+            ("now", vec![], "v = datetime('now')"),
+            ("current_timestamp", vec![], "v = datetime('now')"),
+            ("transaction_timestamp", vec![], "v = datetime('now')"),
+            ("statement_timestamp", vec![], "v = datetime('now')"),
+            ("current_date", vec![], "v = date('now')"),
+            ("current_time", vec![], "v = time('now')"),
+            ("localtimestamp", vec![], "v = datetime('now', 'localtime')"),
+            ("localtime", vec![], "v = time('now', 'localtime')"),
+            (
+                "clock_timestamp",
+                vec![],
+                "v GLOB '????-??-?? ??:??:??.???' AND substr(v, 1, 19) = datetime('now')",
+            ),
+            (
+                "date_trunc",
+                vec![text("year"), col("ts")],
+                "v = '2024-01-01 00:00:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("quarter"), col("ts")],
+                "v = '2024-04-01 00:00:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("month"), col("ts")],
+                "v = '2024-05-01 00:00:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("week"), col("ts")],
+                "v = '2024-05-13 00:00:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("week"), col("monday")],
+                "v = '2024-05-13 00:00:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("day"), col("ts")],
+                "v = '2024-05-17 00:00:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("hour"), col("ts")],
+                "v = '2024-05-17 13:00:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("minute"), col("ts")],
+                "v = '2024-05-17 13:45:00'",
+            ),
+            (
+                "date_trunc",
+                vec![text("second"), col("ts")],
+                "v = '2024-05-17 13:45:30'",
+            ),
+            (
+                "to_char",
+                vec![col("ts"), text("YYYY-MM-DD HH24:MI:SS")],
+                "v = '2024-05-17 13:45:30'",
+            ),
+            (
+                "to_char",
+                vec![col("ts"), text(r#""day" DDD"#)],
+                "v = 'day 138'",
+            ),
+            ("greatest", vec![col("n"), col("d"), int(3)], "v = 3"),
+            ("greatest", vec![col("d"), col("d")], "v IS NULL"),
+            ("least", vec![col("n"), col("d")], "v = -7"),
+            ("random", vec![], "v >= 0 AND v < 1"),
+        ];
+        for (function, _) in FUNCTIONS {
+            assert!(
+                cases.iter().any(|(name, _, _)| name == function),
+                "{function} is not evaluated"
+            );
+        }
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (s TEXT, n INTEGER, d INTEGER, ts TEXT, monday TEXT);
+             INSERT INTO t VALUES
+                 ('Héllo, wörld', -7, NULL, '2024-05-17 13:45:30', '2024-05-13 08:00:00');",
+        )
+        .unwrap();
+        // Stands in for the md5() the application registers
+        conn.create_scalar_function(
+            "md5",
+            1,
+            rusqlite::functions::FunctionFlags::SQLITE_DETERMINISTIC,
+            |ctx| Ok(format!("md5:{}", ctx.get::<String>(0)?)),
+        )
+        .unwrap();
+        for (name, args, check) in cases {
+            let expr = translate(name, args).unwrap();
+            let sql = format!("SELECT {check} FROM (SELECT {expr} AS v, * FROM t)");
+            let passed: bool = conn
+                .query_row(&sql, [], |row| row.get(0))
+                .unwrap_or_else(|e| panic!("{sql}: {e}"));
+            assert!(passed, "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_random() {
+        assert_eq!(
+            translate("random", vec![]).as_deref(),
+            Some("random() / 18446744073709552000 + 0.5")
+        );
+    }
+}
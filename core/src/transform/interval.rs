@@ -0,0 +1,806 @@
+/// Interval storage: INTEGER seconds/milliseconds or ISO-8601 duration text.
+///
+/// Runs after expression mapping. Converts interval literals in defaults,
+/// CHECKs and partial index predicates to match the chosen representation.
+use std::collections::HashSet;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Expr, PgType, SchemaModel, TableConstraint};
+use crate::transform::type_map::IntervalMode;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+/// PG's epoch conversion uses 30-day months and 365.25-day years.
+const MICROS_PER_MONTH: i64 = 30 * MICROS_PER_DAY;
+const MICROS_PER_YEAR: i64 = 365 * MICROS_PER_DAY + MICROS_PER_DAY / 4;
+
+/// A PG interval value, split into the same month/day/time fields PG keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Interval {
+    pub months: i64,
+    pub days: i64,
+    pub micros: i64,
+}
+
+impl Interval {
+    /// Parse interval text in PG input syntax (`1 day 02:00:00`, `@ 3 hours ago`)
+    /// or ISO-8601 duration syntax (`P1DT2H`).
+    pub fn parse(text: &str) -> Option<Interval> {
+        let text = text.trim();
+        let mut acc = Accumulator::default();
+        if let Some(iso) = text.strip_prefix(['P', 'p']) {
+            parse_iso8601(iso, &mut acc)?;
+        } else if let Some(iso) = text.strip_prefix("-P").or(text.strip_prefix("-p")) {
+            parse_iso8601(iso, &mut acc)?;
+            acc.negate();
+        } else {
+            parse_pg(text, &mut acc)?;
+        }
+        Some(acc.finish())
+    }
+
+    /// Whether the value has a month (or year) component of variable length.
+    pub fn has_months(&self) -> bool {
+        self.months != 0
+    }
+
+    /// Total length in microseconds, using PG's 30-day month and 365.25-day year.
+    pub fn total_micros(&self) -> i64 {
+        (self.months / 12) * MICROS_PER_YEAR
+            + (self.months % 12) * MICROS_PER_MONTH
+            + self.days * MICROS_PER_DAY
+            + self.micros
+    }
+
+    /// ISO-8601 duration text, e.g. `P1Y2M3DT4H5M6.5S` or `-PT1M30S`.
+    ///
+    /// `None` when the fields have different signs (`1 mon -2 days`), which
+    /// a single leading sign cannot express.
+    pub fn to_iso8601(&self) -> Option<String> {
+        let signs: Vec<i64> = [self.months, self.days, self.micros]
+            .into_iter()
+            .filter(|v| *v != 0)
+            .map(i64::signum)
+            .collect();
+        let Some(&sign) = signs.first() else {
+            return Some("PT0S".to_string());
+        };
+        if signs.iter().any(|s| *s != sign) {
+            return None;
+        }
+
+        let mut out = String::from(if sign < 0 { "-P" } else { "P" });
+        let months = self.months.abs();
+        for (value, designator) in [
+            (months / 12, 'Y'),
+            (months % 12, 'M'),
+            (self.days.abs(), 'D'),
+        ] {
+            if value != 0 {
+                out.push_str(&format!("{value}{designator}"));
+            }
+        }
+        if self.micros != 0 {
+            out.push('T');
+            let (hours, minutes, seconds) = split_time(self.micros.abs());
+            if hours != 0 {
+                out.push_str(&format!("{hours}H"));
+            }
+            if minutes != 0 {
+                out.push_str(&format!("{minutes}M"));
+            }
+            if seconds != 0 {
+                out.push_str(&format!("{}S", format_seconds(seconds)));
+            }
+        }
+        Some(out)
+    }
+
+    /// SQLite date/time modifiers (`+1 days`, `-2 hours`) that add this interval.
+    pub fn sqlite_modifiers(&self, negate: bool) -> Vec<String> {
+        let sign = if negate { -1 } else { 1 };
+        let (hours, minutes, seconds) = split_time(self.micros * sign);
+        let mut modifiers = Vec::new();
+        for (value, unit) in [
+            (self.months / 12 * sign, "years"),
+            (self.months % 12 * sign, "months"),
+            (self.days * sign, "days"),
+            (hours, "hours"),
+            (minutes, "minutes"),
+        ] {
+            if value != 0 {
+                modifiers.push(format!("{value:+} {unit}"));
+            }
+        }
+        if seconds != 0 {
+            let sign = if seconds < 0 { '-' } else { '+' };
+            modifiers.push(format!("{sign}{} seconds", format_seconds(seconds.abs())));
+        }
+        modifiers
+    }
+}
+
+/// Split microseconds into whole hours, whole minutes and remaining microseconds.
+fn split_time(micros: i64) -> (i64, i64, i64) {
+    (
+        micros / MICROS_PER_HOUR,
+        micros % MICROS_PER_HOUR / MICROS_PER_MINUTE,
+        micros % MICROS_PER_MINUTE,
+    )
+}
+
+/// Format microseconds as decimal seconds without trailing zeros.
+fn format_seconds(micros: i64) -> String {
+    let sign = if micros < 0 { "-" } else { "" };
+    let micros = micros.abs();
+    let whole = micros / MICROS_PER_SECOND;
+    let frac = micros % MICROS_PER_SECOND;
+    if frac == 0 {
+        format!("{sign}{whole}")
+    } else {
+        let frac = format!("{frac:06}");
+        format!("{sign}{whole}.{}", frac.trim_end_matches('0'))
+    }
+}
+
+/// Fractional field accumulator; fractions cascade down like PG's interval input.
+#[derive(Default)]
+struct Accumulator {
+    months: f64,
+    days: f64,
+    micros: f64,
+}
+
+impl Accumulator {
+    fn add(&mut self, value: f64, unit: &str) -> Option<()> {
+        let unit = unit.to_ascii_lowercase();
+        match unit.as_str() {
+            "millennium" | "millennia" | "millenniums" => self.months += value * 12_000.0,
+            "century" | "centuries" => self.months += value * 1_200.0,
+            "decade" | "decades" => self.months += value * 120.0,
+            "y" | "yr" | "yrs" | "year" | "years" => self.months += value * 12.0,
+            "mon" | "mons" | "month" | "months" => self.months += value,
+            "w" | "week" | "weeks" => self.days += value * 7.0,
+            "d" | "day" | "days" => self.days += value,
+            "h" | "hr" | "hrs" | "hour" | "hours" => self.micros += value * MICROS_PER_HOUR as f64,
+            "m" | "min" | "mins" | "minute" | "minutes" => {
+                self.micros += value * MICROS_PER_MINUTE as f64
+            }
+            "s" | "sec" | "secs" | "second" | "seconds" => {
+                self.micros += value * MICROS_PER_SECOND as f64
+            }
+            "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => {
+                self.micros += value * 1_000.0
+            }
+            "us" | "usec" | "usecs" | "microsecond" | "microseconds" => self.micros += value,
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn negate(&mut self) {
+        self.months = -self.months;
+        self.days = -self.days;
+        self.micros = -self.micros;
+    }
+
+    fn finish(mut self) -> Interval {
+        let months = self.months.trunc();
+        self.days += (self.months - months) * 30.0;
+        let days = self.days.trunc();
+        self.micros += (self.days - days) * MICROS_PER_DAY as f64;
+        Interval {
+            months: months as i64,
+            days: days as i64,
+            micros: self.micros.round() as i64,
+        }
+    }
+}
+
+/// Parse PG interval input: `<number> <unit>` pairs, `hh:mm[:ss]`, `@` and `ago`.
+fn parse_pg(text: &str, acc: &mut Accumulator) -> Option<()> {
+    let mut tokens = text.split_whitespace().peekable();
+    let mut parsed_any = false;
+    let mut ago = false;
+
+    while let Some(token) = tokens.next() {
+        if token == "@" {
+            continue;
+        }
+        if token.eq_ignore_ascii_case("ago") {
+            ago = true;
+            continue;
+        }
+        if token.contains(':') {
+            acc.micros += parse_time(token)? as f64;
+            parsed_any = true;
+            continue;
+        }
+
+        // Number, optionally followed directly by its unit (`10min`)
+        let split = token
+            .char_indices()
+            .find(|(i, c)| c.is_ascii_alphabetic() && *i > 0)
+            .map(|(i, _)| i)
+            .unwrap_or(token.len());
+        let value: f64 = token[..split].parse().ok()?;
+        let unit = if split < token.len() {
+            &token[split..]
+        } else {
+            match tokens.peek() {
+                Some(next) if next.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    tokens.next()?
+                }
+                // A bare number is seconds
+                _ => "seconds",
+            }
+        };
+        acc.add(value, unit.trim_end_matches(','))?;
+        parsed_any = true;
+    }
+
+    if ago {
+        acc.negate();
+    }
+    parsed_any.then_some(())
+}
+
+/// Parse `[+-]hh:mm[:ss[.ffffff]]` into microseconds.
+fn parse_time(token: &str) -> Option<i64> {
+    let (sign, body) = match token.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, token.strip_prefix('+').unwrap_or(token)),
+    };
+    let mut parts = body.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = match parts.next() {
+        Some(s) => s.parse().ok()?,
+        None => 0.0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    let micros = (hours * 3600.0 + minutes * 60.0 + seconds) * MICROS_PER_SECOND as f64;
+    Some((sign * micros).round() as i64)
+}
+
+/// Parse the part of an ISO-8601 duration after the leading `P`.
+fn parse_iso8601(text: &str, acc: &mut Accumulator) -> Option<()> {
+    let (date, time) = match text.find(['T', 't']) {
+        Some(i) => (&text[..i], Some(&text[i + 1..])),
+        None => (text, None),
+    };
+    let mut parsed_any = false;
+    for (part, is_time) in [(date, false), (time.unwrap_or(""), true)] {
+        let mut number = String::new();
+        for c in part.chars() {
+            if c.is_ascii_digit() || matches!(c, '.' | '-' | '+') {
+                number.push(c);
+                continue;
+            }
+            let value: f64 = number.parse().ok()?;
+            number.clear();
+            let unit = match (c.to_ascii_uppercase(), is_time) {
+                ('Y', false) => "years",
+                ('M', false) => "months",
+                ('W', false) => "weeks",
+                ('D', false) => "days",
+                ('H', true) => "hours",
+                ('M', true) => "minutes",
+                ('S', true) => "seconds",
+                _ => return None,
+            };
+            acc.add(value, unit)?;
+            parsed_any = true;
+        }
+        if !number.is_empty() {
+            return None;
+        }
+    }
+    parsed_any.then_some(())
+}
+
+/// Whether a cast target names the interval type.
+pub fn is_interval_type(type_name: &str) -> bool {
+    type_name.trim().eq_ignore_ascii_case("interval")
+}
+
+/// Apply the interval storage mode to defaults, CHECKs and index predicates.
+pub fn apply_interval_mode(
+    model: &mut SchemaModel,
+    mode: IntervalMode,
+    warnings: &mut Vec<Warning>,
+) {
+    if mode == IntervalMode::Text {
+        return;
+    }
+    let mut interval_by_table: std::collections::HashMap<String, HashSet<String>> =
+        std::collections::HashMap::new();
+
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        let columns: HashSet<String> = table
+            .columns
+            .iter()
+            .filter(|c| c.pg_type == PgType::Interval)
+            .map(|c| c.name.normalized.clone())
+            .collect();
+        if columns.is_empty() {
+            continue;
+        }
+
+        for col in &mut table.columns {
+            let obj = format!("{}.{}", table_name, col.name.normalized);
+            if columns.contains(&col.name.normalized)
+                && let Some(default) = &mut col.default
+                && !matches!(default, Expr::Null)
+                && !convert_literal(default, mode, &obj, warnings)
+            {
+                warnings.push(unconverted(default, mode, &obj));
+            }
+            if let Some(check) = &mut col.check {
+                convert_comparisons(check, &columns, mode, &obj, warnings);
+            }
+        }
+
+        for constraint in &mut table.constraints {
+            if let TableConstraint::Check { expr, .. } = constraint {
+                let obj = format!("{table_name}.CHECK");
+                convert_comparisons(expr, &columns, mode, &obj, warnings);
+            }
+        }
+
+        interval_by_table.insert(table_name, columns);
+    }
+
+    for index in &mut model.indexes {
+        if let (Some(columns), Some(where_clause)) = (
+            interval_by_table.get(&index.table.name.normalized),
+            &mut index.where_clause,
+        ) {
+            let obj = index.name.normalized.clone();
+            convert_comparisons(where_clause, columns, mode, &obj, warnings);
+        }
+    }
+}
+
+fn unconverted(expr: &Expr, mode: IntervalMode, object: &str) -> Warning {
+    Warning::new(
+        warning::INTERVAL_EXPR_LOSSY,
+        Severity::Lossy,
+        format!(
+            "'{}' is not an interval literal; not converted to {mode} storage",
+            expr.to_sql()
+        ),
+    )
+    .with_object(object)
+}
+
+/// Rewrite literals compared against interval columns.
+fn convert_comparisons(
+    expr: &mut Expr,
+    columns: &HashSet<String>,
+    mode: IntervalMode,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) {
+    let is_interval_column =
+        |e: &Expr| matches!(e, Expr::ColumnRef(name) if columns.contains(name));
+
+    expr.walk_mut(&mut |node| {
+        let (literals, ordered): (Vec<&mut Expr>, bool) = match node {
+            Expr::BinaryOp { left, op, right } if is_comparison(op) => {
+                let ordered = !matches!(op.as_str(), "=" | "<>" | "!=");
+                if is_interval_column(left) {
+                    (vec![&mut **right], ordered)
+                } else if is_interval_column(right) {
+                    (vec![&mut **left], ordered)
+                } else {
+                    return;
+                }
+            }
+            Expr::Between {
+                expr: inner,
+                low,
+                high,
+                ..
+            } if is_interval_column(inner) => (vec![&mut **low, &mut **high], true),
+            Expr::InList {
+                expr: inner, list, ..
+            } if is_interval_column(inner) => (list.iter_mut().collect(), false),
+            _ => return,
+        };
+
+        let mut converted_any = false;
+        for literal in literals {
+            if convert_literal(literal, mode, object, warnings) {
+                converted_any = true;
+            } else if !matches!(literal, Expr::ColumnRef(_) | Expr::Null) {
+                warnings.push(unconverted(literal, mode, object));
+            }
+        }
+        if converted_any && ordered && mode == IntervalMode::Iso8601 {
+            warnings.push(
+                Warning::new(
+                    warning::INTERVAL_EXPR_LOSSY,
+                    Severity::Lossy,
+                    "ISO-8601 interval text compares lexically, not by duration",
+                )
+                .with_object(object),
+            );
+        }
+    });
+}
+
+fn is_comparison(op: &str) -> bool {
+    matches!(op, "=" | "<>" | "!=" | "<" | "<=" | ">" | ">=")
+}
+
+/// Convert an interval string literal in place. Returns false if it is not one.
+fn convert_literal(
+    expr: &mut Expr,
+    mode: IntervalMode,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> bool {
+    let text = match &*expr {
+        Expr::StringLiteral(text) => text.clone(),
+        Expr::TypedLiteral { type_name, value } if is_interval_type(type_name) => value.clone(),
+        Expr::Cast {
+            expr: inner,
+            type_name,
+        } if is_interval_type(type_name) => match inner.as_ref() {
+            Expr::StringLiteral(text) => text.clone(),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let Some(interval) = Interval::parse(&text) else {
+        return false;
+    };
+
+    *expr = match mode {
+        IntervalMode::Text => return true,
+        IntervalMode::Iso8601 => match interval.to_iso8601() {
+            Some(iso) => Expr::StringLiteral(iso),
+            None => {
+                warnings.push(
+                    Warning::new(
+                        warning::INTERVAL_EXPR_LOSSY,
+                        Severity::Lossy,
+                        format!(
+                            "interval '{text}' mixes positive and negative fields, which ISO-8601 cannot express; kept as PostgreSQL text"
+                        ),
+                    )
+                    .with_object(object),
+                );
+                Expr::StringLiteral(text)
+            }
+        },
+        IntervalMode::Seconds | IntervalMode::Milliseconds => {
+            if interval.has_months() {
+                warnings.push(
+                    Warning::new(
+                        warning::INTERVAL_EXPR_LOSSY,
+                        Severity::Lossy,
+                        format!(
+                            "interval '{text}' has a month component; counted as 30-day months and 365.25-day years"
+                        ),
+                    )
+                    .with_object(object),
+                );
+            }
+            let unit = if mode == IntervalMode::Seconds {
+                MICROS_PER_SECOND
+            } else {
+                1_000
+            };
+            let micros = interval.total_micros();
+            if micros % unit != 0 {
+                warnings.push(
+                    Warning::new(
+                        warning::INTERVAL_EXPR_LOSSY,
+                        Severity::Lossy,
+                        format!("interval '{text}' rounded to whole {mode}"),
+                    )
+                    .with_object(object),
+                );
+            }
+            Expr::IntegerLiteral((micros as f64 / unit as f64).round() as i64)
+        }
+    };
+    true
+}
+
+/// Translate `now() ± interval '…'` into `datetime('now', '±N unit', …)`.
+///
+/// `CURRENT_DATE ± interval` becomes `date('now', …)`. Returns `None` when the
+/// expression is not timestamp arithmetic with an interval literal.
+pub fn datetime_arithmetic(left: &Expr, op: &str, right: &Expr) -> Option<Expr> {
+    let negate = match op {
+        "+" => false,
+        "-" => true,
+        _ => return None,
+    };
+    let (base, interval) = match (interval_literal(left), interval_literal(right)) {
+        (None, Some(interval)) => (left, interval),
+        // interval + now() is commutative; interval - now() is not meaningful
+        (Some(interval), None) if !negate => (right, interval),
+        _ => return None,
+    };
+    let function = match base {
+        Expr::CurrentTimestamp => "datetime",
+        Expr::FunctionCall { name, args } if args.is_empty() => match name.as_str() {
+            "now"
+            | "current_timestamp"
+            | "localtimestamp"
+            | "transaction_timestamp"
+            | "statement_timestamp" => "datetime",
+            "current_date" => "date",
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let mut args = vec![Expr::StringLiteral("now".to_string())];
+    args.extend(
+        interval
+            .sqlite_modifiers(negate)
+            .into_iter()
+            .map(Expr::StringLiteral),
+    );
+    Some(Expr::func(function, args))
+}
+
+/// Parse an `interval '…'` / `'…'::interval` literal.
+fn interval_literal(expr: &Expr) -> Option<Interval> {
+    match expr {
+        Expr::TypedLiteral { type_name, value } if is_interval_type(type_name) => {
+            Interval::parse(value)
+        }
+        Expr::Cast { expr, type_name } if is_interval_type(type_name) => match expr.as_ref() {
+            Expr::StringLiteral(text) => Interval::parse(text),
+            _ => None,
+        },
+        Expr::Nested(inner) => interval_literal(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, Ident, QualifiedName, Table};
+
+    fn interval(months: i64, days: i64, micros: i64) -> Interval {
+        Interval {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    #[test]
+    fn test_parse_pg_styles() {
+        assert_eq!(Interval::parse("30 days"), Some(interval(0, 30, 0)));
+        assert_eq!(
+            Interval::parse("1 day 02:00:00"),
+            Some(interval(0, 1, 2 * MICROS_PER_HOUR))
+        );
+        assert_eq!(
+            Interval::parse("1 year 2 mons -3 days +04:05:06.5"),
+            Some(interval(14, -3, 14_706_500_000))
+        );
+        assert_eq!(
+            Interval::parse("@ 2 hours 30 mins ago"),
+            Some(interval(0, 0, -9_000_000_000))
+        );
+        assert_eq!(Interval::parse("1.5 months"), Some(interval(1, 15, 0)));
+        assert_eq!(Interval::parse("90"), Some(interval(0, 0, 90_000_000)));
+        assert_eq!(Interval::parse("10min"), Some(interval(0, 0, 600_000_000)));
+        assert_eq!(Interval::parse("1 fortnight"), None);
+        assert_eq!(Interval::parse(""), None);
+    }
+
+    #[test]
+    fn test_parse_iso8601() {
+        assert_eq!(
+            Interval::parse("P1Y2M3DT4H5M6S"),
+            Some(interval(14, 3, 14_706_000_000))
+        );
+        assert_eq!(Interval::parse("PT1.5S"), Some(interval(0, 0, 1_500_000)));
+        assert_eq!(Interval::parse("P2W"), Some(interval(0, 14, 0)));
+        assert_eq!(Interval::parse("P1H"), None);
+    }
+
+    #[test]
+    fn test_to_iso8601() {
+        let iso = |i: Interval| i.to_iso8601();
+        assert_eq!(iso(interval(0, 30, 0)).as_deref(), Some("P30D"));
+        assert_eq!(
+            iso(interval(14, 3, 14_706_500_000)).as_deref(),
+            Some("P1Y2M3DT4H5M6.5S")
+        );
+        assert_eq!(iso(Interval::default()).as_deref(), Some("PT0S"));
+    }
+
+    #[test]
+    fn test_to_iso8601_signs() {
+        let iso = |i: Interval| i.to_iso8601();
+        assert_eq!(
+            iso(interval(0, 0, -90_000_000)).as_deref(),
+            Some("-PT1M30S")
+        );
+        assert_eq!(
+            iso(interval(-14, -2, -1_500_000)).as_deref(),
+            Some("-P1Y2M2DT1.5S")
+        );
+        assert_eq!(
+            Interval::parse("-P1Y2M2DT1.5S"),
+            Some(interval(-14, -2, -1_500_000))
+        );
+        assert_eq!(iso(interval(1, -2, 0)), None);
+        assert_eq!(iso(interval(0, 1, -1_500_000)), None);
+    }
+
+    #[test]
+    fn test_total_micros_uses_pg_epoch_rules() {
+        assert_eq!(
+            interval(12, 0, 0).total_micros(),
+            31_557_600 * MICROS_PER_SECOND
+        );
+        assert_eq!(
+            interval(1, 0, 0).total_micros(),
+            2_592_000 * MICROS_PER_SECOND
+        );
+    }
+
+    #[test]
+    fn test_datetime_arithmetic() {
+        let iv = |text: &str| Expr::Cast {
+            expr: Box::new(Expr::StringLiteral(text.to_string())),
+            type_name: "INTERVAL".to_string(),
+        };
+        let now = Expr::func("now", vec![]);
+
+        let expr = datetime_arithmetic(&now, "+", &iv("1 day")).unwrap();
+        assert_eq!(expr.to_sql(), "datetime('now', '+1 days')");
+
+        let expr =
+            datetime_arithmetic(&Expr::CurrentTimestamp, "-", &iv("1 day 02:30:00")).unwrap();
+        assert_eq!(
+            expr.to_sql(),
+            "datetime('now', '-1 days', '-2 hours', '-30 minutes')"
+        );
+
+        let today = Expr::func("current_date", vec![]);
+        let expr = datetime_arithmetic(&iv("1 month"), "+", &today).unwrap();
+        assert_eq!(expr.to_sql(), "date('now', '+1 months')");
+
+        assert!(datetime_arithmetic(&now, "*", &iv("1 day")).is_none());
+        assert!(datetime_arithmetic(&Expr::ColumnRef("t".into()), "+", &iv("1 day")).is_none());
+    }
+
+    fn job_model(default: Expr, check: Expr) -> SchemaModel {
+        let timeout = Column {
+            name: Ident::new("timeout"),
+            pg_type: PgType::Interval,
+            sqlite_type: None,
+            not_null: false,
+            default: Some(default),
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        };
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("jobs")),
+                columns: vec![timeout],
+                constraints: vec![TableConstraint::Check {
+                    name: None,
+                    expr: check,
+                }],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn check_sql(model: &SchemaModel) -> String {
+        match &model.tables[0].constraints[0] {
+            TableConstraint::Check { expr, .. } => expr.to_sql(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_apply_seconds_mode() {
+        let mut model = job_model(
+            Expr::StringLiteral("30 days".to_string()),
+            Expr::Between {
+                expr: Box::new(Expr::ColumnRef("timeout".to_string())),
+                low: Box::new(Expr::StringLiteral("1 second".to_string())),
+                high: Box::new(Expr::StringLiteral("1 mon".to_string())),
+                negated: false,
+            },
+        );
+        let mut w = Vec::new();
+        apply_interval_mode(&mut model, IntervalMode::Seconds, &mut w);
+
+        assert_eq!(
+            model.tables[0].columns[0].default,
+            Some(Expr::IntegerLiteral(2_592_000))
+        );
+        assert_eq!(check_sql(&model), "timeout BETWEEN 1 AND 2592000");
+        assert!(w.iter().any(|w| w.code == warning::INTERVAL_EXPR_LOSSY));
+    }
+
+    #[test]
+    fn test_apply_iso8601_mode() {
+        let mut model = job_model(
+            Expr::StringLiteral("1 day 02:00:00".to_string()),
+            Expr::binary(
+                Expr::ColumnRef("timeout".to_string()),
+                "<>",
+                Expr::StringLiteral("0".to_string()),
+            ),
+        );
+        let mut w = Vec::new();
+        apply_interval_mode(&mut model, IntervalMode::Iso8601, &mut w);
+
+        assert_eq!(
+            model.tables[0].columns[0].default,
+            Some(Expr::StringLiteral("P1DT2H".to_string()))
+        );
+        assert_eq!(check_sql(&model), "timeout <> 'PT0S'");
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_apply_iso8601_mode_keeps_mixed_signs() {
+        let mut model = job_model(
+            Expr::StringLiteral("-1 day".to_string()),
+            Expr::binary(
+                Expr::ColumnRef("timeout".to_string()),
+                "<>",
+                Expr::StringLiteral("1 mon -2 days".to_string()),
+            ),
+        );
+        let mut w = Vec::new();
+        apply_interval_mode(&mut model, IntervalMode::Iso8601, &mut w);
+
+        assert_eq!(
+            model.tables[0].columns[0].default,
+            Some(Expr::StringLiteral("-P1D".to_string()))
+        );
+        assert_eq!(check_sql(&model), "timeout <> '1 mon -2 days'");
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].code, warning::INTERVAL_EXPR_LOSSY);
+        assert!(w[0].message.contains("mixes positive and negative"));
+    }
+
+    #[test]
+    fn test_apply_milliseconds_reports_unparseable_default() {
+        let mut model = job_model(
+            Expr::StringLiteral("forever".to_string()),
+            Expr::binary(
+                Expr::ColumnRef("timeout".to_string()),
+                ">",
+                Expr::StringLiteral("250 ms".to_string()),
+            ),
+        );
+        let mut w = Vec::new();
+        apply_interval_mode(&mut model, IntervalMode::Milliseconds, &mut w);
+
+        assert_eq!(check_sql(&model), "timeout > 250");
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].code, warning::INTERVAL_EXPR_LOSSY);
+    }
+}
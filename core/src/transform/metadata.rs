@@ -0,0 +1,286 @@
+/// Column metadata: record original PG types for the `_pg2sqlite_columns` table.
+use std::collections::HashMap;
+
+use crate::ir::{Column, ColumnMetadata, PgType, SchemaModel, SqliteType};
+use crate::transform::type_map::{IntervalMode, numeric_scale};
+
+/// A column's PostgreSQL type as declared, before transforms rewrite it.
+#[derive(Debug, Clone)]
+pub struct ColumnOrigin {
+    /// Declared type, with enum, composite and domain names resolved.
+    pub pg_type: PgType,
+    /// The domain the column was declared with, if any.
+    pub domain: Option<String>,
+}
+
+/// Record the declared type of every column, one map per table in model order.
+///
+/// Must run before planning, which flattens domains and SERIAL types.
+pub fn collect_origins(model: &SchemaModel) -> Vec<HashMap<String, ColumnOrigin>> {
+    let resolve = |pg_type: &PgType| match pg_type {
+        PgType::Other { name } if model.enums.iter().any(|e| e.name.name.normalized == *name) => {
+            PgType::Enum { name: name.clone() }
+        }
+        PgType::Other { name }
+            if model
+                .composites
+                .iter()
+                .any(|c| c.name.name.normalized == *name) =>
+        {
+            PgType::Composite { name: name.clone() }
+        }
+        other => other.clone(),
+    };
+
+    model
+        .tables
+        .iter()
+        .map(|table| {
+            table
+                .columns
+                .iter()
+                .map(|col| {
+                    let domain = match &col.pg_type {
+                        PgType::Other { name } => model
+                            .domains
+                            .iter()
+                            .find(|d| d.name.name.normalized == *name),
+                        _ => None,
+                    };
+                    let origin = match domain {
+                        Some(d) => ColumnOrigin {
+                            pg_type: resolve(&d.base_type),
+                            domain: Some(d.name.name.normalized.clone()),
+                        },
+                        None => ColumnOrigin {
+                            pg_type: resolve(&col.pg_type),
+                            domain: None,
+                        },
+                    };
+                    (col.name.normalized.clone(), origin)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Build the metadata rows for every rendered column.
+///
+/// `origins` must line up with `model.tables`; tables are neither added,
+/// removed nor reordered between [`collect_origins`] and this call. Columns
+/// created by transforms (range bounds, composite attributes) are described
+/// by their own type.
+pub fn build_column_metadata(
+    model: &mut SchemaModel,
+    origins: &[HashMap<String, ColumnOrigin>],
+    interval_mode: IntervalMode,
+) {
+    let mut rows = Vec::new();
+    for (table, origins) in model.tables.iter().zip(origins) {
+        for col in &table.columns {
+            let Some(sqlite_type) = col.sqlite_type else {
+                continue;
+            };
+            let (pg_type, domain) = match origins.get(&col.name.normalized) {
+                Some(origin) => (&origin.pg_type, origin.domain.clone()),
+                None => (&col.pg_type, None),
+            };
+            let enum_labels = match pg_type {
+                PgType::Enum { name } => model
+                    .enums
+                    .iter()
+                    .find(|e| e.name.name.normalized == *name)
+                    .map(|e| e.values.clone()),
+                _ => None,
+            };
+            rows.push(ColumnMetadata {
+                table: table.name.name.normalized.clone(),
+                column: col.name.normalized.clone(),
+                pg_type: pg_type.to_string(),
+                sqlite_type: sqlite_type.to_string(),
+                strategy: strategy(pg_type, col, interval_mode),
+                domain,
+                enum_labels,
+            });
+        }
+    }
+    model.column_metadata = rows;
+}
+
+/// How values of `pg_type` are encoded in the column.
+fn strategy(pg_type: &PgType, col: &Column, interval_mode: IntervalMode) -> String {
+    let sqlite_type = col.sqlite_type;
+    let strategy = match pg_type {
+        PgType::SmallInt
+        | PgType::Integer
+        | PgType::BigInt
+        | PgType::SmallSerial
+        | PgType::Serial
+        | PgType::BigSerial => "integer",
+        PgType::Numeric { .. } | PgType::Money => match sqlite_type {
+            Some(SqliteType::Integer) => {
+                return format!(
+                    "scaled-integer({})",
+                    numeric_scale(pg_type).unwrap_or_default()
+                );
+            }
+            Some(SqliteType::Text) => "decimal-text",
+            Some(SqliteType::Real) => "real",
+            _ => "numeric",
+        },
+        PgType::Real | PgType::DoublePrecision => "real",
+        PgType::Boolean => "boolean-integer",
+        PgType::Date => "iso8601-date",
+        PgType::Time { .. } => "iso8601-time",
+        PgType::Timestamp { .. } => "iso8601-timestamp",
+        PgType::Interval => match interval_mode {
+            IntervalMode::Text => "interval-text",
+            IntervalMode::Iso8601 => "iso8601-duration",
+            IntervalMode::Seconds => "interval-seconds",
+            IntervalMode::Milliseconds => "interval-milliseconds",
+        },
+        PgType::Bytea => "blob",
+        PgType::Uuid if sqlite_type == Some(SqliteType::Blob) => "uuid-blob",
+        PgType::Uuid => "uuid-text",
+        PgType::Json | PgType::Jsonb | PgType::Hstore | PgType::Composite { .. } => "json",
+        PgType::Enum { .. } => "enum",
+        PgType::Int4Range
+        | PgType::Int8Range
+        | PgType::NumRange
+        | PgType::TsRange
+        | PgType::TsTzRange
+        | PgType::DateRange => "range-text",
+        PgType::Geometry { .. } => "wkb",
+        PgType::Array { .. } => "array-text",
+        _ => "text",
+    };
+    strategy.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{DomainDef, EnumDef, Ident, QualifiedName, Table};
+
+    fn column(name: &str, pg_type: PgType, sqlite_type: Option<SqliteType>) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type,
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn other(name: &str) -> PgType {
+        PgType::Other {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_origins_resolve_domains_and_enums() {
+        let model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("users")),
+                columns: vec![
+                    column("id", PgType::Serial, None),
+                    column("email", other("email"), None),
+                    column("mood", other("mood"), None),
+                ],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            enums: vec![EnumDef {
+                name: QualifiedName::new(Ident::new("mood")),
+                values: vec!["happy".to_string(), "sad".to_string()],
+            }],
+            domains: vec![DomainDef {
+                name: QualifiedName::new(Ident::new("email")),
+                base_type: PgType::Citext,
+                collation: None,
+                not_null: false,
+                default: None,
+                check: None,
+            }],
+            ..Default::default()
+        };
+        let origins = collect_origins(&model);
+        assert_eq!(origins[0]["id"].pg_type, PgType::Serial);
+        assert_eq!(origins[0]["email"].pg_type, PgType::Citext);
+        assert_eq!(origins[0]["email"].domain.as_deref(), Some("email"));
+        assert_eq!(
+            origins[0]["mood"].pg_type,
+            PgType::Enum {
+                name: "mood".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_column_metadata() {
+        let mut model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("events")),
+                columns: vec![
+                    column("mood", PgType::Text, Some(SqliteType::Text)),
+                    column(
+                        "price",
+                        PgType::Numeric {
+                            precision: Some(10),
+                            scale: Some(2),
+                        },
+                        Some(SqliteType::Integer),
+                    ),
+                    column("timeout", PgType::Interval, Some(SqliteType::Integer)),
+                    column(
+                        "during_lower",
+                        PgType::Timestamp { with_tz: true },
+                        Some(SqliteType::Text),
+                    ),
+                ],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            enums: vec![EnumDef {
+                name: QualifiedName::new(Ident::new("mood")),
+                values: vec!["happy".to_string()],
+            }],
+            ..Default::default()
+        };
+        let origins = vec![HashMap::from([(
+            "mood".to_string(),
+            ColumnOrigin {
+                pg_type: PgType::Enum {
+                    name: "mood".to_string(),
+                },
+                domain: None,
+            },
+        )])];
+        build_column_metadata(&mut model, &origins, IntervalMode::Milliseconds);
+
+        let rows = &model.column_metadata;
+        let strategies: Vec<&str> = rows.iter().map(|r| r.strategy.as_str()).collect();
+        assert_eq!(
+            strategies,
+            [
+                "enum",
+                "scaled-integer(2)",
+                "interval-milliseconds",
+                "iso8601-timestamp"
+            ]
+        );
+        assert_eq!(rows[0].pg_type, "mood");
+        assert_eq!(rows[0].enum_labels, Some(vec!["happy".to_string()]));
+        assert_eq!(rows[3].pg_type, "timestamp with time zone");
+        assert_eq!(rows[3].sqlite_type, "TEXT");
+    }
+}
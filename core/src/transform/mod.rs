@@ -1,7 +1,24 @@
+pub mod attach;
+pub mod catalog;
+pub mod collation;
+pub mod composite;
 pub mod constraint;
 pub mod expr_map;
+pub mod extension;
+pub mod filter;
+pub mod functions;
 pub mod index;
+pub mod interval;
+pub mod metadata;
 pub mod name_resolve;
+pub mod numeric;
+pub mod overrides;
 pub mod planner;
+pub mod range;
+pub mod regex;
+pub mod rename;
+pub mod strict;
 pub mod topo;
 pub mod type_map;
+pub mod uuid;
+pub mod without_rowid;
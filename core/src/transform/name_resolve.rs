@@ -1,134 +1,149 @@
 /// Schema stripping and name collision handling.
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::diagnostics::warning::{self, Severity, Warning};
 use crate::ir::{Ident, QualifiedName, SchemaModel, TableConstraint};
+use crate::transform::catalog::{self, Catalog, Lookup};
 
 /// Strip schema prefixes from all identifiers.
-/// When include_all_schemas is true and names collide, prefix with schema__table.
+///
+/// SQLite compares table names case-insensitively, so names that differ only
+/// in case (`"UserProfile"` and `userprofile`) collide as well. When several
+/// schemas are converted, colliding tables are prefixed with schema__table;
+/// names that still collide get a numeric suffix.
 pub fn resolve_names(
     model: &mut SchemaModel,
-    include_all_schemas: bool,
+    catalog: &Catalog,
+    multi_schema: bool,
     warnings: &mut Vec<Warning>,
 ) {
-    if !include_all_schemas {
-        // Just strip schema prefixes
-        strip_schemas(model);
-        return;
+    if multi_schema {
+        qualify_references(model, catalog, warnings);
     }
+    // Single-schema references may or may not spell out the schema
+    let key = |name: &QualifiedName| {
+        let schema = name.schema.as_ref().filter(|_| multi_schema);
+        (
+            schema.map(|s| s.normalized.clone()),
+            name.name.normalized.clone(),
+        )
+    };
 
     // Detect collisions
-    let mut name_counts: HashMap<String, Vec<(Option<String>, usize)>> = HashMap::new();
-    for (i, table) in model.tables.iter().enumerate() {
-        let schema = table.name.schema.as_ref().map(|s| s.normalized.clone());
+    let mut name_counts: HashMap<String, Vec<(Option<String>, String)>> = HashMap::new();
+    for table in &model.tables {
+        let (schema, name) = key(&table.name);
         name_counts
-            .entry(table.name.name.normalized.clone())
+            .entry(name.to_ascii_lowercase())
             .or_default()
-            .push((schema, i));
+            .push((schema, name));
     }
+    let mut taken: HashSet<String> = name_counts.keys().cloned().collect();
 
     // Build rename map for collisions
     let mut rename_map: HashMap<(Option<String>, String), String> = HashMap::new();
-    for (name, entries) in &name_counts {
-        if entries.len() > 1 {
-            for (schema, _) in entries {
-                if let Some(s) = schema {
-                    let new_name = format!("{s}__{name}");
-                    rename_map.insert((Some(s.clone()), name.clone()), new_name.clone());
-                    warnings.push(
-                        Warning::new(
-                            warning::SCHEMA_PREFIXED,
-                            Severity::Lossy,
-                            format!(
-                                "table '{s}.{name}' renamed to '{new_name}' to avoid collision"
-                            ),
-                        )
-                        .with_object(&new_name),
-                    );
-                }
+    let mut collisions: Vec<_> = name_counts
+        .into_iter()
+        .filter(|(_, entries)| entries.len() > 1)
+        .collect();
+    collisions.sort();
+    for (folded, entries) in collisions {
+        taken.remove(&folded);
+        for (schema, name) in entries {
+            let base = match &schema {
+                Some(s) => format!("{s}__{name}"),
+                None => name.clone(),
+            };
+            let mut new_name = base.clone();
+            let mut n = 2;
+            while !taken.insert(new_name.to_ascii_lowercase()) {
+                new_name = format!("{base}_{n}");
+                n += 1;
             }
+            if new_name == name {
+                continue;
+            }
+            let old_name = match &schema {
+                Some(s) => format!("{s}.{name}"),
+                None => name.clone(),
+            };
+            warnings.push(
+                Warning::new(
+                    warning::SCHEMA_PREFIXED,
+                    Severity::Lossy,
+                    format!("table '{old_name}' renamed to '{new_name}' to avoid collision"),
+                )
+                .with_object(&new_name),
+            );
+            rename_map.insert((schema, name), new_name);
         }
     }
 
+    let rename = |name: &mut QualifiedName| match rename_map.get(&key(name)) {
+        Some(new_name) => *name = QualifiedName::new(Ident::quoted(new_name)),
+        None => name.schema = None,
+    };
+
     // Apply renames
     for table in &mut model.tables {
-        let key = (
-            table.name.schema.as_ref().map(|s| s.normalized.clone()),
-            table.name.name.normalized.clone(),
-        );
-        if let Some(new_name) = rename_map.get(&key) {
-            table.name = QualifiedName::new(Ident::new(new_name));
-        } else {
-            table.name.schema = None;
-        }
+        rename(&mut table.name);
     }
 
     // Rename FK references in constraints
     for table in &mut model.tables {
         for constraint in &mut table.constraints {
             if let TableConstraint::ForeignKey { ref_table, .. } = constraint {
-                let key = (
-                    ref_table.schema.as_ref().map(|s| s.normalized.clone()),
-                    ref_table.name.normalized.clone(),
-                );
-                if let Some(new_name) = rename_map.get(&key) {
-                    *ref_table = QualifiedName::new(Ident::new(new_name));
-                } else {
-                    ref_table.schema = None;
-                }
+                rename(ref_table);
             }
         }
 
         // Also rename column-level FK refs
         for col in &mut table.columns {
             if let Some(fk) = &mut col.references {
-                let key = (
-                    fk.table.schema.as_ref().map(|s| s.normalized.clone()),
-                    fk.table.name.normalized.clone(),
-                );
-                if let Some(new_name) = rename_map.get(&key) {
-                    fk.table = QualifiedName::new(Ident::new(new_name));
-                } else {
-                    fk.table.schema = None;
-                }
+                rename(&mut fk.table);
             }
         }
     }
 
     // Rename index table references
     for index in &mut model.indexes {
-        let key = (
-            index.table.schema.as_ref().map(|s| s.normalized.clone()),
-            index.table.name.normalized.clone(),
-        );
-        if let Some(new_name) = rename_map.get(&key) {
-            index.table = QualifiedName::new(Ident::new(new_name));
-        } else {
-            index.table.schema = None;
-        }
+        rename(&mut index.table);
     }
 }
 
-/// Simple schema stripping (no collision handling).
-fn strip_schemas(model: &mut SchemaModel) {
-    for table in &mut model.tables {
-        table.name.schema = None;
-    }
-    for index in &mut model.indexes {
-        index.table.schema = None;
-    }
+/// Point table references at the schema of the table the catalog resolves
+/// them to, so they follow that table through collision renames.
+pub fn qualify_references(model: &mut SchemaModel, catalog: &Catalog, warnings: &mut Vec<Warning>) {
+    let mut qualify = |reference: &mut QualifiedName, object: &str| match catalog.table(reference) {
+        Lookup::Found(target) => reference.schema = target.schema,
+        Lookup::Ambiguous(candidates) => warnings.push(catalog::ambiguous_reference(
+            &reference.name.normalized,
+            &candidates,
+            "reference left unresolved",
+            object,
+        )),
+        Lookup::Missing => {}
+    };
+
     for table in &mut model.tables {
+        let table_name = table.name.to_string();
         for constraint in &mut table.constraints {
             if let TableConstraint::ForeignKey { ref_table, .. } = constraint {
-                ref_table.schema = None;
+                qualify(ref_table, &table_name);
             }
         }
         for col in &mut table.columns {
             if let Some(fk) = &mut col.references {
-                fk.table.schema = None;
+                qualify(
+                    &mut fk.table,
+                    &format!("{table_name}.{}", col.name.normalized),
+                );
             }
         }
     }
+    for index in &mut model.indexes {
+        qualify(&mut index.table, &index.name.normalized);
+    }
 }
 
 #[cfg(test)]
@@ -153,8 +168,11 @@ mod tests {
                 autoincrement: false,
                 references: None,
                 check: None,
+                collation: None,
             }],
             constraints: vec![],
+            without_rowid: false,
+            note: None,
         }
     }
 
@@ -165,7 +183,8 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        resolve_names(&mut model, false, &mut w);
+        let catalog = Catalog::build(&model);
+        resolve_names(&mut model, &catalog, false, &mut w);
         assert!(model.tables[0].name.schema.is_none());
     }
 
@@ -179,7 +198,8 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        resolve_names(&mut model, true, &mut w);
+        let catalog = Catalog::build(&model);
+        resolve_names(&mut model, &catalog, true, &mut w);
 
         let names: Vec<&str> = model
             .tables
@@ -190,4 +210,50 @@ mod tests {
         assert!(names.contains(&"other__users"));
         assert!(w.iter().any(|w| w.code == warning::SCHEMA_PREFIXED));
     }
+
+    #[test]
+    fn test_case_insensitive_collision() {
+        let mut model = SchemaModel {
+            tables: vec![
+                make_table(Some("public"), "users"),
+                make_table(Some("other"), "users"),
+            ],
+            ..Default::default()
+        };
+        model.tables[0].name.name = Ident::quoted("Users");
+        let mut w = Vec::new();
+        let catalog = Catalog::build(&model);
+        resolve_names(&mut model, &catalog, true, &mut w);
+
+        assert_eq!(model.tables[0].name.name.normalized, "public__Users");
+        assert_eq!(model.tables[1].name.name.normalized, "other__users");
+    }
+
+    #[test]
+    fn test_case_insensitive_collision_same_schema() {
+        let mut model = SchemaModel {
+            tables: vec![
+                make_table(Some("public"), "userprofile"),
+                make_table(Some("public"), "x"),
+            ],
+            ..Default::default()
+        };
+        model.tables[1].name.name = Ident::quoted("UserProfile");
+        model.tables[1].columns[0].references = Some(crate::ir::ForeignKeyRef {
+            table: QualifiedName::new(Ident::quoted("UserProfile")),
+            column: None,
+            on_delete: None,
+            on_update: None,
+        });
+        let mut w = Vec::new();
+        let catalog = Catalog::build(&model);
+        resolve_names(&mut model, &catalog, false, &mut w);
+
+        assert_eq!(model.tables[0].name.name.normalized, "userprofile");
+        assert_eq!(model.tables[1].name.name.normalized, "UserProfile_2");
+        let fk = model.tables[1].columns[0].references.as_ref().unwrap();
+        assert_eq!(fk.table.name.normalized, "UserProfile_2");
+        assert!(model.tables.iter().all(|t| t.name.schema.is_none()));
+        assert!(w.iter().any(|w| w.code == warning::SCHEMA_PREFIXED));
+    }
 }
@@ -0,0 +1,882 @@
+/// Exact numeric storage: scaled INTEGER minor units or decimal TEXT.
+///
+/// Runs after expression mapping. Rescales literals in defaults, CHECKs and
+/// partial index predicates to match the chosen representation, and adds a
+/// format CHECK for decimal TEXT columns.
+use std::collections::HashMap;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Expr, SchemaModel};
+use crate::transform::type_map::{NumericMode, TypeMapOptions, numeric_scale};
+
+/// Storage chosen for a single numeric/money column.
+#[derive(Debug, Clone, Copy)]
+struct ExactColumn {
+    mode: NumericMode,
+    scale: u32,
+}
+
+/// Apply the numeric storage modes to defaults, CHECKs and index predicates.
+pub fn apply_numeric_mode(
+    model: &mut SchemaModel,
+    opts: &TypeMapOptions,
+    warnings: &mut Vec<Warning>,
+) {
+    let mut exact_by_table: HashMap<String, HashMap<String, ExactColumn>> = HashMap::new();
+
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        let mut exact: HashMap<String, ExactColumn> = HashMap::new();
+
+        for col in &table.columns {
+            let obj = format!("{}.{}", table_name, col.name.normalized);
+            let mode = opts.numeric_mode_for(&obj, &col.pg_type);
+            if mode == NumericMode::Native {
+                continue;
+            }
+            // Unconstrained numeric (always TEXT mode here) has no fixed scale
+            let scale = numeric_scale(&col.pg_type).unwrap_or(u32::MAX);
+            exact.insert(col.name.normalized.clone(), ExactColumn { mode, scale });
+        }
+
+        if exact.is_empty() {
+            continue;
+        }
+
+        // Column defaults and column-level CHECKs
+        for col in &mut table.columns {
+            let obj = format!("{}.{}", table_name, col.name.normalized);
+            if let Some(column) = exact.get(&col.name.normalized).copied()
+                && let Some(default) = &col.default
+            {
+                col.default = rescale_default(default, column, &obj, warnings);
+            }
+            if let Some(check) = &mut col.check {
+                rescale_expr(check, &exact, &obj, warnings);
+            }
+        }
+
+        // Table-level CHECKs
+        for constraint in &mut table.constraints {
+            if let crate::ir::TableConstraint::Check { expr, .. } = constraint {
+                let obj = format!("{table_name}.CHECK");
+                rescale_expr(expr, &exact, &obj, warnings);
+            }
+        }
+
+        // Decimal TEXT format CHECKs (added after rescaling so they stay textual)
+        for col in &mut table.columns {
+            if let Some(column) = exact.get(&col.name.normalized)
+                && column.mode == NumericMode::Text
+            {
+                col.add_check(decimal_format_check(&col.name.normalized, column.scale));
+            }
+        }
+
+        exact_by_table.insert(table_name, exact);
+    }
+
+    // Partial index predicates
+    for index in &mut model.indexes {
+        if let (Some(exact), Some(where_clause)) = (
+            exact_by_table.get(&index.table.name.normalized),
+            &mut index.where_clause,
+        ) {
+            let obj = index.name.normalized.clone();
+            rescale_expr(where_clause, exact, &obj, warnings);
+        }
+    }
+}
+
+/// Convert a default value to the column's numeric representation.
+fn rescale_default(
+    default: &Expr,
+    column: ExactColumn,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<Expr> {
+    match convert_literal(
+        default,
+        column,
+        Rounding::HalfAwayFromZero,
+        object,
+        warnings,
+    ) {
+        Some(converted) => Some(converted),
+        None if is_null(default) => Some(default.clone()),
+        None => {
+            warnings.push(
+                Warning::new(
+                    warning::NUMERIC_EXPR_UNSCALED,
+                    Severity::Lossy,
+                    format!(
+                        "default '{}' is not a numeric literal; not converted to {} storage",
+                        default.to_sql(),
+                        column.mode
+                    ),
+                )
+                .with_object(object),
+            );
+            Some(default.clone())
+        }
+    }
+}
+
+/// Rewrite literals compared against exact numeric columns.
+///
+/// Scaled INTEGER columns get their comparison literals multiplied by 10^scale;
+/// other references to them are reported since they cannot be rescaled safely.
+/// Decimal TEXT columns are compared numerically via `CAST(col AS NUMERIC)`.
+fn rescale_expr(
+    expr: &mut Expr,
+    exact: &HashMap<String, ExactColumn>,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) {
+    let integer_column = |e: &Expr| match e {
+        Expr::ColumnRef(name) => exact
+            .get(name)
+            .copied()
+            .filter(|c| c.mode == NumericMode::Integer),
+        _ => None,
+    };
+
+    match expr {
+        Expr::BinaryOp { left, op, right } if is_comparison(op) => {
+            let (column, column_expr, literal, op) =
+                match (integer_column(left), integer_column(right)) {
+                    (Some(l), Some(r)) if l.scale == r.scale => return,
+                    (Some(column), None) => (column, &**left, &mut **right, op.as_str()),
+                    (None, Some(column)) => (column, &**right, &mut **left, mirror_comparison(op)),
+                    _ => {
+                        rescale_expr(left, exact, object, warnings);
+                        rescale_expr(right, exact, object, warnings);
+                        return;
+                    }
+                };
+            match convert_compared(literal, op, column, object, warnings) {
+                Some(Compared::Literal(lit)) => *literal = lit,
+                Some(Compared::NeverEqual) => {
+                    let folded = fold_never_equal(column_expr, op);
+                    *expr = folded;
+                }
+                None => {
+                    rescale_expr(left, exact, object, warnings);
+                    rescale_expr(right, exact, object, warnings);
+                }
+            }
+        }
+        Expr::Between {
+            expr: inner,
+            low,
+            high,
+            ..
+        } => {
+            // `col BETWEEN low AND high` is `col >= low AND col <= high`
+            if let Some(column) = integer_column(inner)
+                && let Some(l) = convert_literal(low, column, Rounding::Ceil, object, warnings)
+                && let Some(h) = convert_literal(high, column, Rounding::Floor, object, warnings)
+            {
+                **low = l;
+                **high = h;
+                return;
+            }
+            rescale_expr(inner, exact, object, warnings);
+            rescale_expr(low, exact, object, warnings);
+            rescale_expr(high, exact, object, warnings);
+        }
+        Expr::InList {
+            expr: inner,
+            list,
+            negated,
+        } => {
+            if let Some(column) = integer_column(inner) {
+                let converted: Option<Vec<Compared>> = list
+                    .iter()
+                    .map(|e| convert_compared(e, "=", column, object, warnings))
+                    .collect();
+                if let Some(converted) = converted {
+                    // Items that can never match are dropped
+                    let items: Vec<Expr> = converted
+                        .into_iter()
+                        .filter_map(|c| match c {
+                            Compared::Literal(lit) => Some(lit),
+                            Compared::NeverEqual => None,
+                        })
+                        .collect();
+                    if items.is_empty() {
+                        let op = if *negated { "<>" } else { "=" };
+                        let folded = fold_never_equal(inner, op);
+                        *expr = folded;
+                    } else {
+                        *list = items;
+                    }
+                    return;
+                }
+            }
+            rescale_expr(inner, exact, object, warnings);
+            for item in list {
+                rescale_expr(item, exact, object, warnings);
+            }
+        }
+        Expr::ColumnRef(name) => match exact.get(name.as_str()) {
+            Some(column) if column.mode == NumericMode::Text => {
+                *expr = Expr::Cast {
+                    expr: Box::new(expr.clone()),
+                    type_name: "NUMERIC".to_string(),
+                };
+            }
+            Some(column) => {
+                warnings.push(
+                    Warning::new(
+                        warning::NUMERIC_EXPR_UNSCALED,
+                        Severity::Lossy,
+                        format!(
+                            "column '{name}' is stored x 10^{} but used outside a literal comparison; expression not rescaled",
+                            column.scale
+                        ),
+                    )
+                    .with_object(object),
+                );
+            }
+            None => {}
+        },
+        Expr::BinaryOp { left, right, .. }
+        | Expr::Like {
+            expr: left,
+            pattern: right,
+            ..
+        }
+        | Expr::Position {
+            needle: left,
+            haystack: right,
+        }
+        | Expr::AtTimeZone {
+            expr: left,
+            zone: right,
+        } => {
+            rescale_expr(left, exact, object, warnings);
+            rescale_expr(right, exact, object, warnings);
+        }
+        Expr::Case {
+            operand,
+            branches,
+            else_result,
+        } => {
+            let results = branches.iter_mut().flat_map(|(c, r)| [c, r]);
+            for part in operand
+                .iter_mut()
+                .chain(else_result)
+                .map(|e| &mut **e)
+                .chain(results)
+            {
+                rescale_expr(part, exact, object, warnings);
+            }
+        }
+        Expr::Substring {
+            expr: inner,
+            from,
+            length,
+        } => {
+            rescale_expr(inner, exact, object, warnings);
+            for part in [from, length].into_iter().flatten() {
+                rescale_expr(part, exact, object, warnings);
+            }
+        }
+        Expr::UnaryOp { expr: inner, .. }
+        | Expr::Extract { expr: inner, .. }
+        | Expr::IsNull { expr: inner, .. }
+        | Expr::Nested(inner)
+        | Expr::Native(inner)
+        | Expr::Collate { expr: inner, .. }
+        | Expr::FieldAccess { expr: inner, .. }
+        | Expr::Cast { expr: inner, .. } => rescale_expr(inner, exact, object, warnings),
+        Expr::FunctionCall { args, .. } => {
+            for arg in args {
+                rescale_expr(arg, exact, object, warnings);
+            }
+        }
+        Expr::IntegerLiteral(_)
+        | Expr::FloatLiteral(_)
+        | Expr::StringLiteral(_)
+        | Expr::BooleanLiteral(_)
+        | Expr::Null
+        | Expr::TypedLiteral { .. }
+        | Expr::NextVal(_)
+        | Expr::CurrentTimestamp
+        | Expr::Raw(_) => {}
+    }
+}
+
+fn is_comparison(op: &str) -> bool {
+    matches!(op, "=" | "<>" | "!=" | "<" | "<=" | ">" | ">=")
+}
+
+fn is_null(expr: &Expr) -> bool {
+    match expr {
+        Expr::Null => true,
+        Expr::Nested(inner) => is_null(inner),
+        _ => false,
+    }
+}
+
+/// Convert a numeric literal to the column's representation.
+/// Returns `None` if the expression is not a numeric literal.
+///
+/// Half-away-from-zero rounding of an inexact literal changes its value and is
+/// reported; floor/ceil are only used where they keep a comparison's meaning.
+fn convert_literal(
+    expr: &Expr,
+    column: ExactColumn,
+    rounding: Rounding,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<Expr> {
+    let text = literal_text(expr)?;
+    let decimal = Decimal::parse(&text)?;
+
+    let (converted, exact) = match column.mode {
+        NumericMode::Integer => {
+            let (value, exact) = decimal.to_scaled_integer(column.scale, rounding)?;
+            (Expr::IntegerLiteral(value), exact)
+        }
+        NumericMode::Text => {
+            let (value, exact) = decimal.to_text(column.scale, rounding);
+            (Expr::StringLiteral(value), exact)
+        }
+        NumericMode::Native => return None,
+    };
+    if !exact && matches!(rounding, Rounding::HalfAwayFromZero) {
+        warnings.push(
+            Warning::new(
+                warning::NUMERIC_PRECISION_LOSS,
+                Severity::Lossy,
+                format!(
+                    "literal {text} has more than {} decimal places; rounded",
+                    column.scale
+                ),
+            )
+            .with_object(object),
+        );
+    }
+    Some(converted)
+}
+
+/// A literal compared against a scaled column, converted to its representation.
+enum Compared {
+    Literal(Expr),
+    /// The literal is not representable at the column's scale, so equality never holds
+    NeverEqual,
+}
+
+/// Convert the literal of `column <op> literal` to the column's scale.
+///
+/// Ordering comparisons round the literal in the direction that selects the
+/// same rows (`col > 1.234` is `col > 1.23` at scale 2, `col < 1.234` is
+/// `col < 1.24`). Returns `None` if the expression is not a numeric literal.
+fn convert_compared(
+    literal: &Expr,
+    op: &str,
+    column: ExactColumn,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<Compared> {
+    let rounding = match op {
+        ">" | "<=" => Rounding::Floor,
+        "<" | ">=" => Rounding::Ceil,
+        _ => {
+            let text = literal_text(literal)?;
+            let (_, exact) = Decimal::parse(&text)?.round(column.scale, Rounding::Floor);
+            if !exact {
+                warnings.push(
+                    Warning::new(
+                        warning::NUMERIC_PRECISION_LOSS,
+                        Severity::Info,
+                        format!(
+                            "literal {text} has more than {} decimal places and never equals the column; comparison folded",
+                            column.scale
+                        ),
+                    )
+                    .with_object(object),
+                );
+                return Some(Compared::NeverEqual);
+            }
+            Rounding::HalfAwayFromZero
+        }
+    };
+    convert_literal(literal, column, rounding, object, warnings).map(Compared::Literal)
+}
+
+/// The constant result of `column <op> literal` when equality never holds.
+///
+/// Built as `col <> col` / `col = col` so a NULL column still yields NULL,
+/// as the original comparison would.
+fn fold_never_equal(column: &Expr, op: &str) -> Expr {
+    let folded_op = if op == "=" { "<>" } else { "=" };
+    Expr::binary(column.clone(), folded_op, column.clone())
+}
+
+/// Mirror a comparison operator so the column can be read on the left.
+fn mirror_comparison(op: &str) -> &str {
+    match op {
+        "<" => ">",
+        "<=" => ">=",
+        ">" => "<",
+        ">=" => "<=",
+        other => other,
+    }
+}
+
+/// Extract the source text of a numeric literal (possibly negated or quoted).
+fn literal_text(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::IntegerLiteral(n) => Some(n.to_string()),
+        Expr::FloatLiteral(f) => Some(f.to_string()),
+        // Quoted numerics such as '12.50'::numeric or '$1,000.00'::money
+        Expr::StringLiteral(s) => Some(s.trim().replace(['$', ','], "")),
+        Expr::UnaryOp { op, expr } if op == "-" => {
+            let inner = literal_text(expr)?;
+            match inner.strip_prefix('-') {
+                Some(positive) => Some(positive.to_string()),
+                None => Some(format!("-{inner}")),
+            }
+        }
+        Expr::UnaryOp { op, expr } if op == "+" => literal_text(expr),
+        Expr::Nested(inner) => literal_text(inner),
+        _ => None,
+    }
+}
+
+/// How a literal with more decimal places than the column's scale is rounded.
+#[derive(Debug, Clone, Copy)]
+enum Rounding {
+    HalfAwayFromZero,
+    /// Towards negative infinity
+    Floor,
+    /// Towards positive infinity
+    Ceil,
+}
+
+/// A decimal number held as its digits, avoiding floating-point rounding.
+#[derive(Debug, Clone, PartialEq)]
+struct Decimal {
+    negative: bool,
+    int_digits: String,
+    frac_digits: String,
+}
+
+impl Decimal {
+    fn parse(text: &str) -> Option<Self> {
+        let (negative, unsigned) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (int_part, frac_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (int_part.is_empty() && frac_part.is_empty())
+            || !all_digits(int_part)
+            || !all_digits(frac_part)
+        {
+            return None;
+        }
+        let int_digits = int_part.trim_start_matches('0');
+        Some(Self {
+            negative,
+            int_digits: if int_digits.is_empty() {
+                "0".to_string()
+            } else {
+                int_digits.to_string()
+            },
+            frac_digits: frac_part.to_string(),
+        })
+    }
+
+    /// Round to `scale` fractional digits.
+    /// Returns the rounded value and whether it was exact; `u32::MAX` keeps
+    /// the literal's own fractional digits.
+    fn round(&self, scale: u32, rounding: Rounding) -> (Self, bool) {
+        if scale == u32::MAX {
+            let frac_digits = self.frac_digits.trim_end_matches('0').to_string();
+            return (
+                Self {
+                    frac_digits,
+                    ..self.clone()
+                },
+                true,
+            );
+        }
+        let scale = scale as usize;
+        let kept: String = self
+            .frac_digits
+            .chars()
+            .chain(std::iter::repeat('0'))
+            .take(scale)
+            .collect();
+        let dropped = self.frac_digits.get(scale..).unwrap_or("");
+        let exact = dropped.chars().all(|c| c == '0');
+        let away_from_zero = match rounding {
+            Rounding::HalfAwayFromZero => dropped.starts_with(['5', '6', '7', '8', '9']),
+            Rounding::Floor => !exact && self.negative,
+            Rounding::Ceil => !exact && !self.negative,
+        };
+
+        let mut digits: Vec<u8> = format!("{}{kept}", self.int_digits).into_bytes();
+        if away_from_zero {
+            match digits.iter().rposition(|&d| d != b'9') {
+                Some(pos) => {
+                    digits[pos] += 1;
+                    digits[pos + 1..].fill(b'0');
+                }
+                None => {
+                    digits.fill(b'0');
+                    digits.insert(0, b'1');
+                }
+            }
+        }
+        let digits = String::from_utf8(digits).expect("ASCII digits");
+        let (int_digits, frac_digits) = digits.split_at(digits.len() - scale);
+        let int_digits = match int_digits.trim_start_matches('0') {
+            "" => "0",
+            trimmed => trimmed,
+        };
+        let rounded = Self {
+            negative: self.negative,
+            int_digits: int_digits.to_string(),
+            frac_digits: frac_digits.to_string(),
+        };
+        (rounded, exact)
+    }
+
+    /// Multiply by 10^scale with the given rounding.
+    /// Returns the value and whether it was exact, or `None` on overflow.
+    fn to_scaled_integer(&self, scale: u32, rounding: Rounding) -> Option<(i64, bool)> {
+        let (rounded, exact) = self.round(scale, rounding);
+        let magnitude: i128 = format!("{}{}", rounded.int_digits, rounded.frac_digits)
+            .parse()
+            .ok()?;
+        let value = if self.negative { -magnitude } else { magnitude };
+        Some((i64::try_from(value).ok()?, exact))
+    }
+
+    /// Render as decimal text with exactly `scale` fractional digits.
+    /// Returns the text and whether it was exact.
+    fn to_text(&self, scale: u32, rounding: Rounding) -> (String, bool) {
+        let (rounded, exact) = self.round(scale, rounding);
+        let frac = &rounded.frac_digits;
+        let is_zero = rounded.int_digits == "0" && frac.chars().all(|c| c == '0');
+        let sign = if self.negative && !is_zero { "-" } else { "" };
+        let text = if frac.is_empty() {
+            format!("{sign}{}", rounded.int_digits)
+        } else {
+            format!("{sign}{}.{frac}", rounded.int_digits)
+        };
+        (text, exact)
+    }
+}
+
+/// Build a CHECK that a TEXT column holds a plain decimal string
+/// (optional leading `-`, digits, at most one `.` and at most `scale` fractional digits).
+fn decimal_format_check(column: &str, scale: u32) -> Expr {
+    let col = || Expr::ColumnRef(column.to_string());
+    let text = |s: &str| Expr::StringLiteral(s.to_string());
+    let unsigned = || Expr::func("ltrim", vec![col(), text("-")]);
+    let dot_pos = || Expr::func("instr", vec![col(), text(".")]);
+
+    let mut parts = vec![
+        Expr::binary(Expr::func("typeof", vec![col()]), "=", text("text")),
+        Expr::binary(unsigned(), "GLOB", text("[0-9]*")),
+        Expr::binary(unsigned(), "NOT GLOB", text("*[^0-9.]*")),
+        Expr::binary(col(), "NOT GLOB", text("--*")),
+        Expr::binary(col(), "NOT GLOB", text("*.*.*")),
+    ];
+    let no_dot = Expr::binary(dot_pos(), "=", Expr::IntegerLiteral(0));
+    let fraction = match scale {
+        0 => no_dot,
+        u32::MAX => Expr::binary(col(), "NOT GLOB", text("*.")),
+        _ => Expr::Nested(Box::new(Expr::binary(
+            no_dot,
+            "OR",
+            Expr::Between {
+                expr: Box::new(Expr::binary(
+                    Expr::func("length", vec![col()]),
+                    "-",
+                    dot_pos(),
+                )),
+                low: Box::new(Expr::IntegerLiteral(1)),
+                high: Box::new(Expr::IntegerLiteral(scale as i64)),
+                negated: false,
+            },
+        ))),
+    };
+    parts.push(fraction);
+
+    parts
+        .into_iter()
+        .reduce(|left, right| Expr::binary(left, "AND", right))
+        .expect("format check has parts")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, Ident, Index, IndexColumn, PgType, QualifiedName, Table};
+
+    fn make_column(name: &str, pg_type: PgType) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type: None,
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn price_model(default: Expr, check: Expr) -> SchemaModel {
+        let mut col = make_column(
+            "price",
+            PgType::Numeric {
+                precision: Some(10),
+                scale: Some(2),
+            },
+        );
+        col.default = Some(default);
+        col.check = Some(check);
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("items")),
+                columns: vec![col],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn opts(mode: NumericMode) -> TypeMapOptions {
+        TypeMapOptions {
+            numeric_mode: mode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decimal_scaling() {
+        let d = Decimal::parse("12.5").unwrap();
+        let half = Rounding::HalfAwayFromZero;
+        assert_eq!(d.to_scaled_integer(2, half), Some((1250, true)));
+        assert_eq!(d.to_text(2, half), ("12.50".to_string(), true));
+        assert_eq!(d.to_text(u32::MAX, half), ("12.5".to_string(), true));
+
+        let d = Decimal::parse("-0.005").unwrap();
+        assert_eq!(d.to_scaled_integer(2, half), Some((-1, false)));
+        assert_eq!(d.to_scaled_integer(2, Rounding::Floor), Some((-1, false)));
+        assert_eq!(d.to_scaled_integer(2, Rounding::Ceil), Some((0, false)));
+        assert_eq!(d.to_text(2, Rounding::Ceil), ("0.00".to_string(), false));
+
+        let d = Decimal::parse("9.999").unwrap();
+        assert_eq!(d.to_text(2, half), ("10.00".to_string(), false));
+        assert_eq!(d.to_text(2, Rounding::Floor), ("9.99".to_string(), false));
+        assert_eq!(d.to_scaled_integer(0, Rounding::Ceil), Some((10, false)));
+
+        assert_eq!(Decimal::parse("abc"), None);
+        assert_eq!(
+            Decimal::parse("99999999999999999999")
+                .unwrap()
+                .to_scaled_integer(2, Rounding::HalfAwayFromZero),
+            None
+        );
+    }
+
+    #[test]
+    fn test_integer_mode_rescales_default_and_check() {
+        let mut model = price_model(
+            Expr::FloatLiteral(9.99),
+            Expr::binary(
+                Expr::ColumnRef("price".to_string()),
+                ">=",
+                Expr::FloatLiteral(0.5),
+            ),
+        );
+        let mut w = Vec::new();
+        apply_numeric_mode(&mut model, &opts(NumericMode::Integer), &mut w);
+
+        let col = &model.tables[0].columns[0];
+        assert_eq!(col.default, Some(Expr::IntegerLiteral(999)));
+        assert_eq!(col.check.as_ref().unwrap().to_sql(), "price >= 50");
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_integer_mode_warns_on_arithmetic() {
+        let mut model = price_model(
+            Expr::IntegerLiteral(0),
+            Expr::binary(
+                Expr::binary(
+                    Expr::ColumnRef("price".to_string()),
+                    "*",
+                    Expr::IntegerLiteral(2),
+                ),
+                "<",
+                Expr::IntegerLiteral(100),
+            ),
+        );
+        let mut w = Vec::new();
+        apply_numeric_mode(&mut model, &opts(NumericMode::Integer), &mut w);
+        assert!(w.iter().any(|w| w.code == warning::NUMERIC_EXPR_UNSCALED));
+    }
+
+    #[test]
+    fn test_text_mode_quotes_default_and_casts_check() {
+        let mut model = price_model(
+            Expr::StringLiteral("5".to_string()),
+            Expr::Between {
+                expr: Box::new(Expr::ColumnRef("price".to_string())),
+                low: Box::new(Expr::IntegerLiteral(0)),
+                high: Box::new(Expr::IntegerLiteral(100)),
+                negated: false,
+            },
+        );
+        let mut w = Vec::new();
+        apply_numeric_mode(&mut model, &opts(NumericMode::Text), &mut w);
+
+        let col = &model.tables[0].columns[0];
+        assert_eq!(col.default, Some(Expr::StringLiteral("5.00".to_string())));
+        let check = col.check.as_ref().unwrap().to_sql();
+        assert!(check.starts_with("(CAST(price AS NUMERIC) BETWEEN 0 AND 100) AND"));
+        assert!(check.contains("typeof(price) = 'text'"));
+        assert!(check.ends_with(
+            "(instr(price, '.') = 0 OR length(price) - instr(price, '.') BETWEEN 1 AND 2)"
+        ));
+    }
+
+    #[test]
+    fn test_non_numeric_columns_untouched() {
+        let mut model = price_model(Expr::IntegerLiteral(1), Expr::BooleanLiteral(true));
+        model.tables[0]
+            .columns
+            .push(make_column("qty", PgType::Integer));
+        let mut w = Vec::new();
+        apply_numeric_mode(&mut model, &opts(NumericMode::Text), &mut w);
+        assert!(model.tables[0].columns[1].check.is_none());
+    }
+
+    #[test]
+    fn test_text_mode_rounds_default() {
+        let mut model = price_model(
+            Expr::StringLiteral("1.005".to_string()),
+            Expr::BooleanLiteral(true),
+        );
+        let mut w = Vec::new();
+        apply_numeric_mode(&mut model, &opts(NumericMode::Text), &mut w);
+
+        let col = &model.tables[0].columns[0];
+        assert_eq!(col.default, Some(Expr::StringLiteral("1.01".to_string())));
+        assert!(w.iter().any(|w| w.code == warning::NUMERIC_PRECISION_LOSS));
+    }
+
+    #[test]
+    fn test_integer_mode_rounds_comparisons_by_operator() {
+        let price = || Expr::ColumnRef("price".to_string());
+        let cases = [
+            (
+                Expr::binary(price(), ">", Expr::FloatLiteral(1.234)),
+                "price > 123",
+            ),
+            (
+                Expr::binary(price(), "<=", Expr::FloatLiteral(1.234)),
+                "price <= 123",
+            ),
+            (
+                Expr::binary(price(), "<", Expr::FloatLiteral(1.234)),
+                "price < 124",
+            ),
+            (
+                Expr::binary(price(), ">=", Expr::FloatLiteral(1.234)),
+                "price >= 124",
+            ),
+            (
+                Expr::binary(Expr::FloatLiteral(1.234), "<", price()),
+                "123 < price",
+            ),
+            (
+                Expr::binary(price(), ">", Expr::FloatLiteral(-1.234)),
+                "price > -124",
+            ),
+            (
+                Expr::binary(price(), "=", Expr::FloatLiteral(1.234)),
+                "price <> price",
+            ),
+            (
+                Expr::binary(price(), "<>", Expr::FloatLiteral(1.234)),
+                "price = price",
+            ),
+            (
+                Expr::binary(price(), "=", Expr::FloatLiteral(1.5)),
+                "price = 150",
+            ),
+            (
+                Expr::Between {
+                    expr: Box::new(price()),
+                    low: Box::new(Expr::FloatLiteral(0.001)),
+                    high: Box::new(Expr::FloatLiteral(9.999)),
+                    negated: false,
+                },
+                "price BETWEEN 1 AND 999",
+            ),
+            (
+                Expr::InList {
+                    expr: Box::new(price()),
+                    list: vec![Expr::FloatLiteral(1.234), Expr::IntegerLiteral(2)],
+                    negated: false,
+                },
+                "price IN (200)",
+            ),
+            (
+                Expr::InList {
+                    expr: Box::new(price()),
+                    list: vec![Expr::FloatLiteral(1.234)],
+                    negated: true,
+                },
+                "price = price",
+            ),
+        ];
+        for (check, expected) in cases {
+            let mut model = price_model(Expr::Null, check);
+            let mut w = Vec::new();
+            apply_numeric_mode(&mut model, &opts(NumericMode::Integer), &mut w);
+            let col = &model.tables[0].columns[0];
+            assert_eq!(col.check.as_ref().unwrap().to_sql(), expected);
+            assert!(w.iter().all(|w| w.severity == Severity::Info), "{expected}");
+        }
+    }
+
+    #[test]
+    fn test_partial_index_rescaled() {
+        let mut model = price_model(Expr::Null, Expr::BooleanLiteral(true));
+        model.indexes.push(Index {
+            name: Ident::new("idx_expensive"),
+            table: QualifiedName::new(Ident::new("items")),
+            columns: vec![IndexColumn::Column(Ident::new("price"))],
+            unique: false,
+            method: None,
+            where_clause: Some(Expr::binary(
+                Expr::ColumnRef("price".to_string()),
+                ">",
+                Expr::IntegerLiteral(100),
+            )),
+        });
+        let mut w = Vec::new();
+        apply_numeric_mode(&mut model, &opts(NumericMode::Integer), &mut w);
+        assert_eq!(
+            model.indexes[0].where_clause.as_ref().unwrap().to_sql(),
+            "price > 10000"
+        );
+    }
+}
@@ -0,0 +1,353 @@
+/// Mapping overrides: storage types, CHECKs and warning suppression from the config file.
+use std::collections::{HashMap, HashSet};
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Expr, PgType, SchemaModel, SqliteType};
+use crate::transform::metadata::ColumnOrigin;
+
+/// Placeholder for the column name in CHECK templates.
+pub const COLUMN_PLACEHOLDER: &str = "{column}";
+
+/// How to store the columns matched by a type or column override.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MappingOverride {
+    /// SQLite type declared instead of the built-in mapping.
+    pub sqlite_type: Option<SqliteType>,
+    /// CHECK template added to the column, with `{column}` standing for its name.
+    pub check: Option<String>,
+    /// Warning codes dropped for the column.
+    pub suppress: Vec<String>,
+}
+
+/// An override for columns whose `table.column` name matches a glob pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnOverride {
+    /// `table.column` glob; `*` matches any run of characters, `?` a single one.
+    pub pattern: String,
+    pub mapping: MappingOverride,
+}
+
+/// Warning codes to drop, keyed by `table.column`.
+pub type Suppressions = HashMap<String, HashSet<String>>;
+
+/// Instantiate a CHECK template for a column. Templates are SQLite SQL and
+/// are emitted as written.
+pub fn check_expr(template: &str, column: &str) -> Expr {
+    Expr::Raw(template.replace(COLUMN_PLACEHOLDER, column))
+}
+
+/// Apply overrides to every column, returning the warnings they suppress.
+///
+/// The first matching column override wins over a type override. Type
+/// overrides match the declared type from `origins` (see
+/// [`collect_origins`](crate::transform::metadata::collect_origins)): a
+/// domain name, the type name without modifiers, or a common alias such as
+/// `timestamptz`. Columns given a SQLite type are retyped as
+/// [`PgType::Other`], so later type transforms leave them alone.
+pub fn apply_overrides(
+    model: &mut SchemaModel,
+    origins: &[HashMap<String, ColumnOrigin>],
+    type_overrides: &HashMap<String, MappingOverride>,
+    column_overrides: &[ColumnOverride],
+    warnings: &mut Vec<Warning>,
+) -> Suppressions {
+    let mut suppressions = Suppressions::new();
+    if type_overrides.is_empty() && column_overrides.is_empty() {
+        return suppressions;
+    }
+    let type_overrides: HashMap<String, &MappingOverride> = type_overrides
+        .iter()
+        .map(|(name, mapping)| (name.trim().to_lowercase(), mapping))
+        .collect();
+
+    for (table, origins) in model.tables.iter_mut().zip(origins) {
+        let schema = table.name.schema.as_ref().map(|s| s.normalized.clone());
+        for col in &mut table.columns {
+            let obj = format!("{}.{}", table.name.name.normalized, col.name.normalized);
+            let origin = origins.get(&col.name.normalized);
+
+            let column_match = column_overrides
+                .iter()
+                .find(|o| matches_column(&o.pattern, schema.as_deref(), &obj));
+            let (mapping, source) = match column_match {
+                Some(o) => (&o.mapping, format!("column override '{}'", o.pattern)),
+                None => {
+                    let Some((name, mapping)) = origin.and_then(|origin| {
+                        type_names(origin)
+                            .into_iter()
+                            .find_map(|name| type_overrides.get(&name).map(|m| (name, *m)))
+                    }) else {
+                        continue;
+                    };
+                    (mapping, format!("type override '{name}'"))
+                }
+            };
+
+            if let Some(sqlite_type) = mapping.sqlite_type {
+                let original = match origin {
+                    Some(ColumnOrigin {
+                        domain: Some(domain),
+                        ..
+                    }) => domain.clone(),
+                    Some(origin) => origin.pg_type.to_string(),
+                    None => col.pg_type.to_string(),
+                };
+                warnings.push(
+                    Warning::new(
+                        warning::TYPE_OVERRIDDEN,
+                        Severity::Info,
+                        format!("{original} stored as {sqlite_type} by {source}"),
+                    )
+                    .with_object(&obj),
+                );
+                col.pg_type = PgType::Other { name: original };
+                col.sqlite_type = Some(sqlite_type);
+            }
+
+            if let Some(template) = &mapping.check {
+                let check = check_expr(template, &col.name.to_sql());
+                col.check = Some(match col.check.take() {
+                    Some(existing) => Expr::and(existing, Expr::Nested(Box::new(check))),
+                    None => check,
+                });
+            }
+
+            if !mapping.suppress.is_empty() {
+                suppressions
+                    .entry(obj)
+                    .or_default()
+                    .extend(mapping.suppress.iter().cloned());
+            }
+        }
+    }
+    suppressions
+}
+
+/// Drop warnings that an override suppresses for the column they concern.
+pub fn suppress_warnings(warnings: &mut Vec<Warning>, suppressions: &Suppressions) {
+    if suppressions.is_empty() {
+        return;
+    }
+    warnings.retain(|w| {
+        !w.object
+            .as_ref()
+            .and_then(|obj| suppressions.get(obj))
+            .is_some_and(|codes| codes.contains(w.code))
+    });
+}
+
+/// Match `text` against a glob `pattern`, ignoring ASCII case. `*` matches
+/// any run of characters and `?` exactly one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it currently absorbs up to
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A `table.column` pattern matches with or without the schema prefix.
+fn matches_column(pattern: &str, schema: Option<&str>, obj: &str) -> bool {
+    glob_match(pattern, obj) || schema.is_some_and(|s| glob_match(pattern, &format!("{s}.{obj}")))
+}
+
+/// Names a column's declared type can be overridden by.
+fn type_names(origin: &ColumnOrigin) -> Vec<String> {
+    let mut names: Vec<String> = origin.domain.iter().cloned().collect();
+
+    // Drop modifiers: `numeric(10,2)[]` matches `numeric[]`
+    let mut depth = 0;
+    let base: String = origin
+        .pg_type
+        .to_string()
+        .chars()
+        .filter(|&c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => return depth == 0,
+            }
+            false
+        })
+        .collect();
+    if let Some((_, unqualified)) = base.rsplit_once('.') {
+        names.push(unqualified.to_string());
+    }
+    names.push(base);
+
+    let aliases: &[&str] = match origin.pg_type {
+        PgType::SmallInt => &["int2"],
+        PgType::Integer => &["int", "int4"],
+        PgType::BigInt => &["int8"],
+        PgType::SmallSerial => &["serial2"],
+        PgType::Serial => &["serial4"],
+        PgType::BigSerial => &["serial8"],
+        PgType::Numeric { .. } => &["decimal"],
+        PgType::Real => &["float4"],
+        PgType::DoublePrecision => &["float8"],
+        PgType::Varchar { .. } => &["character varying"],
+        PgType::Char { .. } => &["character", "bpchar"],
+        PgType::Boolean => &["bool"],
+        PgType::Time { with_tz: true } => &["timetz"],
+        PgType::Timestamp { with_tz: true } => &["timestamptz"],
+        PgType::VarBit { .. } => &["bit varying"],
+        _ => &[],
+    };
+    names.extend(aliases.iter().map(|a| a.to_string()));
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, Ident, QualifiedName, Table};
+
+    fn column(name: &str, pg_type: PgType) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type: None,
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn origins(model: &SchemaModel) -> Vec<HashMap<String, ColumnOrigin>> {
+        crate::transform::metadata::collect_origins(model)
+    }
+
+    fn sample() -> SchemaModel {
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("events")),
+                columns: vec![
+                    column("path", PgType::Ltree),
+                    column("created_at", PgType::Timestamp { with_tz: true }),
+                    column("payload", PgType::Jsonb),
+                ],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("audit_*.payload", "audit_2024.payload"));
+        assert!(glob_match("*.created_?t", "events.created_at"));
+        assert!(glob_match("Events.*", "events.id"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("audit_*.payload", "audit.payload"));
+        assert!(!glob_match("*.id", "events.user_id2"));
+    }
+
+    #[test]
+    fn test_type_override() {
+        let mut model = sample();
+        let origins = origins(&model);
+        let type_overrides = HashMap::from([
+            (
+                "ltree".to_string(),
+                MappingOverride {
+                    sqlite_type: Some(SqliteType::Blob),
+                    ..Default::default()
+                },
+            ),
+            (
+                "TIMESTAMPTZ".to_string(),
+                MappingOverride {
+                    sqlite_type: Some(SqliteType::Integer),
+                    check: Some("{column} >= 0".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let mut w = Vec::new();
+        apply_overrides(&mut model, &origins, &type_overrides, &[], &mut w);
+
+        let cols = &model.tables[0].columns;
+        assert_eq!(cols[0].sqlite_type, Some(SqliteType::Blob));
+        assert_eq!(
+            cols[0].pg_type,
+            PgType::Other {
+                name: "ltree".to_string()
+            }
+        );
+        assert_eq!(cols[1].sqlite_type, Some(SqliteType::Integer));
+        assert_eq!(cols[1].check.as_ref().unwrap().to_sql(), "created_at >= 0");
+        assert_eq!(cols[2].sqlite_type, None);
+        assert_eq!(w.len(), 2);
+        assert_eq!(w[0].code, warning::TYPE_OVERRIDDEN);
+        assert_eq!(w[0].object.as_deref(), Some("events.path"));
+    }
+
+    #[test]
+    fn test_column_override_wins_and_suppresses() {
+        let mut model = sample();
+        let origins = origins(&model);
+        let type_overrides = HashMap::from([(
+            "jsonb".to_string(),
+            MappingOverride {
+                sqlite_type: Some(SqliteType::Blob),
+                ..Default::default()
+            },
+        )]);
+        let column_overrides = [ColumnOverride {
+            pattern: "event?.pay*".to_string(),
+            mapping: MappingOverride {
+                sqlite_type: None,
+                check: Some("json_valid({column})".to_string()),
+                suppress: vec![warning::JSONB_LOSS.to_string()],
+            },
+        }];
+        let mut w = Vec::new();
+        let suppressions = apply_overrides(
+            &mut model,
+            &origins,
+            &type_overrides,
+            &column_overrides,
+            &mut w,
+        );
+
+        let payload = &model.tables[0].columns[2];
+        assert_eq!(payload.pg_type, PgType::Jsonb);
+        assert_eq!(payload.sqlite_type, None);
+        assert_eq!(
+            payload.check.as_ref().unwrap().to_sql(),
+            "json_valid(payload)"
+        );
+        assert!(w.is_empty());
+
+        let mut w = vec![
+            Warning::new(warning::JSONB_LOSS, Severity::Lossy, "").with_object("events.payload"),
+            Warning::new(warning::JSONB_LOSS, Severity::Lossy, "").with_object("logs.payload"),
+        ];
+        suppress_warnings(&mut w, &suppressions);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].object.as_deref(), Some("logs.payload"));
+    }
+}
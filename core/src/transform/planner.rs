@@ -1,31 +1,32 @@
 /// Planner: merge ALTER TABLE constraints and resolve SERIAL/IDENTITY/sequences.
 use crate::diagnostics::warning::{self, Severity, Warning};
-use crate::ir::{Expr, PgType, SchemaModel, TableConstraint};
+use crate::ir::{Expr, Ident, PgType, SchemaModel, TableConstraint};
+use crate::transform::catalog::{self, Catalog, Lookup, TypeRef};
 
 /// Plan and merge ALTER TABLE constraints into CREATE TABLE, resolve SERIAL/sequences.
-pub fn plan(model: &mut SchemaModel, warnings: &mut Vec<Warning>) {
-    merge_alter_constraints(model, warnings);
-    resolve_identity(model, warnings);
+pub fn plan(model: &mut SchemaModel, catalog: &Catalog, warnings: &mut Vec<Warning>) {
+    merge_alter_constraints(model, catalog, warnings);
+    resolve_identity(model, catalog, warnings);
     resolve_serials(model, warnings);
-    resolve_enums(model, warnings);
+    resolve_domains(model, catalog, warnings);
+    resolve_enums(model, catalog);
+    resolve_composites(model, catalog, warnings);
 }
 
 /// Merge ALTER TABLE ADD CONSTRAINT statements into the corresponding CREATE TABLE.
-fn merge_alter_constraints(model: &mut SchemaModel, warnings: &mut Vec<Warning>) {
+fn merge_alter_constraints(
+    model: &mut SchemaModel,
+    catalog: &Catalog,
+    warnings: &mut Vec<Warning>,
+) {
     let alters = std::mem::take(&mut model.alter_constraints);
 
     for alter in alters {
-        // Match by table name only (after normalize, all tables are in the same schema)
-        let target_table = model
-            .tables
-            .iter_mut()
-            .find(|t| t.name.name.normalized == alter.table.name.normalized);
-
-        match target_table {
-            Some(table) => {
-                table.constraints.push(alter.constraint);
+        match catalog.table_index(&model.tables, &alter.table) {
+            Lookup::Found(idx) => {
+                model.tables[idx].constraints.push(alter.constraint);
             }
-            None => {
+            Lookup::Missing => {
                 warnings.push(
                     Warning::new(
                         warning::ALTER_TARGET_MISSING,
@@ -38,35 +39,49 @@ fn merge_alter_constraints(model: &mut SchemaModel, warnings: &mut Vec<Warning>)
                     .with_object(&alter.table.name.normalized),
                 );
             }
+            Lookup::Ambiguous(candidates) => {
+                warnings.push(catalog::ambiguous_reference(
+                    &alter.table.name.normalized,
+                    &candidates,
+                    "ALTER TABLE constraint skipped",
+                    &alter.table.name.normalized,
+                ));
+            }
         }
     }
 }
 
 /// Resolve identity columns: if a column has both IDENTITY and single-column PK,
 /// convert to INTEGER PRIMARY KEY AUTOINCREMENT.
-fn resolve_identity(model: &mut SchemaModel, warnings: &mut Vec<Warning>) {
+fn resolve_identity(model: &mut SchemaModel, catalog: &Catalog, warnings: &mut Vec<Warning>) {
     let identities = std::mem::take(&mut model.identity_columns);
 
     for identity in identities {
-        // Match by table name only (after normalize, all tables are in the same schema)
-        let target_table = model
-            .tables
-            .iter_mut()
-            .find(|t| t.name.name.normalized == identity.table.name.normalized);
-
-        let Some(table) = target_table else {
-            warnings.push(
-                Warning::new(
-                    warning::ALTER_TARGET_MISSING,
-                    Severity::Unsupported,
-                    format!(
-                        "ALTER TABLE target '{}' not found; identity skipped",
-                        identity.table.name.normalized
-                    ),
-                )
-                .with_object(&identity.table.name.normalized),
-            );
-            continue;
+        let table = match catalog.table_index(&model.tables, &identity.table) {
+            Lookup::Found(idx) => &mut model.tables[idx],
+            Lookup::Missing => {
+                warnings.push(
+                    Warning::new(
+                        warning::ALTER_TARGET_MISSING,
+                        Severity::Unsupported,
+                        format!(
+                            "ALTER TABLE target '{}' not found; identity skipped",
+                            identity.table.name.normalized
+                        ),
+                    )
+                    .with_object(&identity.table.name.normalized),
+                );
+                continue;
+            }
+            Lookup::Ambiguous(candidates) => {
+                warnings.push(catalog::ambiguous_reference(
+                    &identity.table.name.normalized,
+                    &candidates,
+                    "identity skipped",
+                    &identity.table.name.normalized,
+                ));
+                continue;
+            }
         };
 
         let table_name = table.name.name.normalized.clone();
@@ -241,18 +256,95 @@ fn resolve_serials(model: &mut SchemaModel, warnings: &mut Vec<Warning>) {
     }
 }
 
-/// Resolve enum columns: replace PgType::Other with PgType::Enum where a matching enum exists.
-fn resolve_enums(model: &mut SchemaModel, _warnings: &mut [Warning]) {
-    let enum_names: std::collections::HashSet<String> = model
-        .enums
-        .iter()
-        .map(|e| e.name.name.normalized.clone())
-        .collect();
+/// Flatten domain columns to the domain's base type, carrying over its
+/// NOT NULL, default, collation and CHECK (with `VALUE` bound to the column).
+///
+/// Also reports column types that name a user-defined type in several schemas;
+/// those stay unresolved.
+fn resolve_domains(model: &mut SchemaModel, catalog: &Catalog, warnings: &mut Vec<Warning>) {
+    for table in &mut model.tables {
+        for col in &mut table.columns {
+            let PgType::Other { name } = col.pg_type.clone() else {
+                continue;
+            };
+            let domain = match catalog.user_type(&name, table.name.schema.as_ref()) {
+                Lookup::Found(TypeRef::Domain(idx)) => &model.domains[idx],
+                Lookup::Ambiguous(candidates) => {
+                    warnings.push(catalog::ambiguous_reference(
+                        &name,
+                        &candidates,
+                        "column type left unresolved",
+                        format!("{}.{}", table.name.name.normalized, col.name.normalized),
+                    ));
+                    continue;
+                }
+                _ => continue,
+            };
+
+            warnings.push(
+                Warning::new(
+                    warning::DOMAIN_FLATTENED,
+                    Severity::Info,
+                    format!(
+                        "domain '{name}' flattened to base type {}",
+                        domain.base_type
+                    ),
+                )
+                .with_object(format!(
+                    "{}.{}",
+                    table.name.name.normalized, col.name.normalized
+                )),
+            );
+            col.pg_type = domain.base_type.clone();
+            col.not_null |= domain.not_null;
+            if col.default.is_none() {
+                col.default = domain.default.clone();
+            }
+            if col.collation.is_none() {
+                col.collation = domain.collation.clone();
+            }
+            if let Some(check) = &domain.check {
+                let mut check = check.clone();
+                let mut unbound = false;
+                check.walk_mut(&mut |e| match e {
+                    Expr::ColumnRef(c) if c.eq_ignore_ascii_case("value") => {
+                        *c = col.name.normalized.clone();
+                    }
+                    Expr::Raw(sql) if sql.to_ascii_uppercase().contains("VALUE") => unbound = true,
+                    _ => {}
+                });
+                if unbound {
+                    warnings.push(
+                        Warning::new(
+                            warning::CHECK_EXPRESSION_UNSUPPORTED,
+                            Severity::Unsupported,
+                            format!(
+                                "CHECK of domain '{name}' could not be bound to the column; dropped"
+                            ),
+                        )
+                        .with_object(format!(
+                            "{}.{}",
+                            table.name.name.normalized, col.name.normalized
+                        )),
+                    );
+                    continue;
+                }
+                col.check = Some(match col.check.take() {
+                    Some(existing) => Expr::and(existing, check),
+                    None => check,
+                });
+            }
+        }
+    }
+}
 
+/// Resolve enum columns: replace PgType::Other with PgType::Enum where a matching enum exists.
+fn resolve_enums(model: &mut SchemaModel, catalog: &Catalog) {
     for table in &mut model.tables {
         for col in &mut table.columns {
             if let PgType::Other { name } = &col.pg_type
-                && enum_names.contains(name)
+                && let Lookup::Found(TypeRef::Enum(_)) =
+                    catalog.user_type(name, table.name.schema.as_ref())
             {
                 col.pg_type = PgType::Enum { name: name.clone() };
             }
@@ -260,16 +352,68 @@ fn resolve_enums(model: &mut SchemaModel, _warnings: &mut [Warning]) {
     }
 }
 
+/// Resolve composite columns and composite attributes: replace PgType::Other with
+/// PgType::Composite (or PgType::Enum for attributes) where a matching type exists.
+fn resolve_composites(model: &mut SchemaModel, catalog: &Catalog, warnings: &mut Vec<Warning>) {
+    for composite in &mut model.composites {
+        for attr in &mut composite.attributes {
+            if let Some((name, candidates)) =
+                resolve_type(&mut attr.pg_type, catalog, composite.name.schema.as_ref())
+            {
+                warnings.push(catalog::ambiguous_reference(
+                    &name,
+                    &candidates,
+                    "attribute type left unresolved",
+                    format!(
+                        "{}.{}",
+                        composite.name.name.normalized, attr.name.normalized
+                    ),
+                ));
+            }
+        }
+    }
+    for table in &mut model.tables {
+        for col in &mut table.columns {
+            // Ambiguous column types were already reported by resolve_domains
+            resolve_type(&mut col.pg_type, catalog, table.name.schema.as_ref());
+        }
+    }
+}
+
+/// Resolve a composite or enum type, returning the candidates of an ambiguous name.
+fn resolve_type(
+    pg_type: &mut PgType,
+    catalog: &Catalog,
+    schema: Option<&Ident>,
+) -> Option<(String, Vec<String>)> {
+    let PgType::Other { name } = pg_type else {
+        return None;
+    };
+    match catalog.user_type(name, schema) {
+        Lookup::Found(TypeRef::Composite(_)) => {
+            *pg_type = PgType::Composite { name: name.clone() };
+        }
+        Lookup::Found(TypeRef::Enum(_)) => {
+            *pg_type = PgType::Enum { name: name.clone() };
+        }
+        Lookup::Ambiguous(candidates) => return Some((name.clone(), candidates)),
+        _ => {}
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ir::{AlterConstraint, Column, FkAction, Ident, QualifiedName, Table};
+    use crate::ir::{AlterConstraint, Column, FkAction, QualifiedName, Table};
 
     fn make_table(name: &str, columns: Vec<Column>, constraints: Vec<TableConstraint>) -> Table {
         Table {
             name: QualifiedName::new(Ident::new(name)),
             columns,
             constraints,
+            without_rowid: false,
+            note: None,
         }
     }
 
@@ -285,6 +429,7 @@ mod tests {
             autoincrement: false,
             references: None,
             check: None,
+            collation: None,
         }
     }
 
@@ -314,7 +459,8 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        plan(&mut model, &mut w);
+        let catalog = Catalog::build(&model);
+        plan(&mut model, &catalog, &mut w);
         assert_eq!(model.tables[0].constraints.len(), 1);
     }
 
@@ -332,7 +478,8 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        plan(&mut model, &mut w);
+        let catalog = Catalog::build(&model);
+        plan(&mut model, &catalog, &mut w);
         assert!(w.iter().any(|w| w.code == warning::ALTER_TARGET_MISSING));
     }
 
@@ -345,7 +492,8 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        plan(&mut model, &mut w);
+        let catalog = Catalog::build(&model);
+        plan(&mut model, &catalog, &mut w);
         assert_eq!(model.tables[0].columns[0].pg_type, PgType::Integer);
         assert!(model.tables[0].columns[0].is_primary_key);
         assert!(w.iter().any(|w| w.code == warning::SERIAL_TO_ROWID));
@@ -359,7 +507,8 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        plan(&mut model, &mut w);
+        let catalog = Catalog::build(&model);
+        plan(&mut model, &catalog, &mut w);
         assert_eq!(model.tables[0].columns[0].pg_type, PgType::Integer);
         assert!(w.iter().any(|w| w.code == warning::SERIAL_NOT_PRIMARY_KEY));
     }
@@ -390,7 +539,8 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        plan(&mut model, &mut w);
+        let catalog = Catalog::build(&model);
+        plan(&mut model, &catalog, &mut w);
 
         let col = &model.tables[0].columns[0];
         assert!(col.autoincrement);
@@ -419,11 +569,58 @@ mod tests {
             ..Default::default()
         };
         let mut w = Vec::new();
-        plan(&mut model, &mut w);
+        let catalog = Catalog::build(&model);
+        plan(&mut model, &catalog, &mut w);
 
         let col = &model.tables[0].columns[0];
         assert!(!col.autoincrement);
         assert!(!col.is_primary_key);
         assert!(w.iter().any(|w| w.code == warning::IDENTITY_NO_PK));
     }
+
+    #[test]
+    fn test_domain_flattened() {
+        use crate::ir::DomainDef;
+
+        let mut col = make_column(
+            "contact",
+            PgType::Other {
+                name: "email".to_string(),
+            },
+        );
+        col.check = Some(Expr::binary(
+            Expr::ColumnRef("contact".to_string()),
+            "<>",
+            Expr::StringLiteral(String::new()),
+        ));
+        let mut model = SchemaModel {
+            tables: vec![make_table("users", vec![col], vec![])],
+            domains: vec![DomainDef {
+                name: QualifiedName::with_schema(Ident::new("public"), Ident::new("email")),
+                base_type: PgType::Varchar { length: Some(255) },
+                collation: None,
+                not_null: true,
+                default: Some(Expr::StringLiteral("n/a".to_string())),
+                check: Some(Expr::binary(
+                    Expr::ColumnRef("VALUE".to_string()),
+                    "LIKE",
+                    Expr::StringLiteral("%@%".to_string()),
+                )),
+            }],
+            ..Default::default()
+        };
+        let mut w = Vec::new();
+        let catalog = Catalog::build(&model);
+        plan(&mut model, &catalog, &mut w);
+
+        let col = &model.tables[0].columns[0];
+        assert_eq!(col.pg_type, PgType::Varchar { length: Some(255) });
+        assert!(col.not_null);
+        assert!(col.default.is_some());
+        assert_eq!(
+            col.check.as_ref().unwrap().to_sql(),
+            "(contact <> '') AND contact LIKE '%@%'"
+        );
+        assert!(w.iter().any(|w| w.code == warning::DOMAIN_FLATTENED));
+    }
 }
@@ -0,0 +1,748 @@
+/// Range type expansion: split range columns into lower/upper bound columns.
+use std::collections::HashMap;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Column, Expr, Ident, IndexColumn, PgType, SchemaModel, TableConstraint};
+
+/// Storage representation for range-typed columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RangeMode {
+    /// PG range literal text such as `[a,b)` (default).
+    #[default]
+    Text,
+    /// `<col>_lower` / `<col>_upper` columns, lower-inclusive and upper-exclusive.
+    Split,
+    /// Like `Split`, plus `<col>_lower_inc` / `<col>_upper_inc` flag columns.
+    SplitInclusive,
+}
+
+impl std::str::FromStr for RangeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(RangeMode::Text),
+            "split" => Ok(RangeMode::Split),
+            "split-inclusive" => Ok(RangeMode::SplitInclusive),
+            other => Err(format!(
+                "unknown range mode '{other}' (expected text, split or split-inclusive)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RangeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeMode::Text => write!(f, "text"),
+            RangeMode::Split => write!(f, "split"),
+            RangeMode::SplitInclusive => write!(f, "split-inclusive"),
+        }
+    }
+}
+
+/// Element (bound) type of a range type, or `None` for non-range types.
+pub fn range_element_type(pg_type: &PgType) -> Option<PgType> {
+    match pg_type {
+        PgType::Int4Range => Some(PgType::Integer),
+        PgType::Int8Range => Some(PgType::BigInt),
+        PgType::NumRange => Some(PgType::Numeric {
+            precision: None,
+            scale: None,
+        }),
+        PgType::TsRange => Some(PgType::Timestamp { with_tz: false }),
+        PgType::TsTzRange => Some(PgType::Timestamp { with_tz: true }),
+        PgType::DateRange => Some(PgType::Date),
+        _ => None,
+    }
+}
+
+/// A range column that was split, with the names of its replacement columns.
+#[derive(Debug, Clone)]
+struct SplitRange {
+    lower: Ident,
+    upper: Ident,
+    lower_inc: Option<Ident>,
+    upper_inc: Option<Ident>,
+}
+
+fn suffixed(ident: &Ident, suffix: &str) -> Ident {
+    Ident {
+        raw: format!("{}{suffix}", ident.raw),
+        normalized: format!("{}{suffix}", ident.normalized),
+    }
+}
+
+/// Expand range columns into bound columns and rewrite dependent objects.
+pub fn apply_range_mode(model: &mut SchemaModel, mode: RangeMode, warnings: &mut Vec<Warning>) {
+    if mode == RangeMode::Text {
+        return;
+    }
+    let with_flags = mode == RangeMode::SplitInclusive;
+    let mut split_by_table: HashMap<String, HashMap<String, SplitRange>> = HashMap::new();
+
+    for table in &mut model.tables {
+        let table_name = table.name.name.normalized.clone();
+        let mut splits: HashMap<String, SplitRange> = HashMap::new();
+        let mut columns = Vec::with_capacity(table.columns.len());
+        let mut extra_constraints = Vec::new();
+
+        for col in std::mem::take(&mut table.columns) {
+            let Some(element) = range_element_type(&col.pg_type) else {
+                columns.push(col);
+                continue;
+            };
+            let obj = format!("{}.{}", table_name, col.name.normalized);
+            let split = SplitRange {
+                lower: suffixed(&col.name, "_lower"),
+                upper: suffixed(&col.name, "_upper"),
+                lower_inc: with_flags.then(|| suffixed(&col.name, "_lower_inc")),
+                upper_inc: with_flags.then(|| suffixed(&col.name, "_upper_inc")),
+            };
+            warnings.push(
+                Warning::new(
+                    warning::RANGE_SPLIT,
+                    Severity::Info,
+                    format!(
+                        "{} split into {} and {} columns",
+                        col.pg_type, split.lower, split.upper
+                    ),
+                )
+                .with_object(&obj),
+            );
+
+            let bounds = col
+                .default
+                .as_ref()
+                .and_then(|d| split_range_default(d, &col.pg_type, with_flags, &obj, warnings));
+
+            let make = |name: &Ident, pg_type: PgType, default: Option<Expr>| Column {
+                name: name.clone(),
+                pg_type,
+                sqlite_type: None,
+                not_null: false,
+                default,
+                is_primary_key: false,
+                is_unique: false,
+                autoincrement: false,
+                references: None,
+                check: None,
+                collation: None,
+            };
+            let (lower_default, upper_default, lower_inc, upper_inc) = match bounds {
+                Some(b) => (b.lower, b.upper, b.lower_inc, b.upper_inc),
+                None => (None, None, true, false),
+            };
+            columns.push(make(&split.lower, element.clone(), lower_default));
+            columns.push(make(&split.upper, element, upper_default));
+            if let (Some(lower_flag), Some(upper_flag)) = (&split.lower_inc, &split.upper_inc) {
+                let mut flag = make(
+                    lower_flag,
+                    PgType::Boolean,
+                    Some(Expr::BooleanLiteral(lower_inc)),
+                );
+                flag.not_null = true;
+                columns.push(flag);
+                let mut flag = make(
+                    upper_flag,
+                    PgType::Boolean,
+                    Some(Expr::BooleanLiteral(upper_inc)),
+                );
+                flag.not_null = true;
+                columns.push(flag);
+            }
+
+            // Column-level constraints move to the table level
+            if col.is_primary_key {
+                extra_constraints.push(TableConstraint::PrimaryKey {
+                    name: None,
+                    columns: vec![split.lower.clone(), split.upper.clone()],
+                });
+            }
+            if col.is_unique {
+                extra_constraints.push(TableConstraint::Unique {
+                    name: None,
+                    columns: vec![split.lower.clone(), split.upper.clone()],
+                });
+            }
+            extra_constraints.push(TableConstraint::Check {
+                name: None,
+                expr: Expr::native(Expr::binary(
+                    Expr::ColumnRef(split.lower.normalized.clone()),
+                    "<=",
+                    Expr::ColumnRef(split.upper.normalized.clone()),
+                )),
+            });
+            // NULL bounds are infinite, so NOT NULL becomes "some bound is set"
+            if col.not_null {
+                let is_set = |name: &Ident| Expr::IsNull {
+                    expr: Box::new(Expr::ColumnRef(name.normalized.clone())),
+                    negated: true,
+                };
+                extra_constraints.push(TableConstraint::Check {
+                    name: None,
+                    expr: Expr::native(Expr::binary(
+                        is_set(&split.lower),
+                        "OR",
+                        is_set(&split.upper),
+                    )),
+                });
+                warnings.push(
+                    Warning::new(
+                        warning::RANGE_SPLIT,
+                        Severity::Lossy,
+                        "NOT NULL kept as a CHECK that a bound is set; the unbounded range '(,)' is rejected",
+                    )
+                    .with_object(&obj),
+                );
+            }
+            if let Some(check) = col.check {
+                extra_constraints.push(TableConstraint::Check {
+                    name: None,
+                    expr: check,
+                });
+            }
+
+            splits.insert(col.name.normalized.clone(), split);
+        }
+        table.columns = columns;
+
+        if splits.is_empty() {
+            continue;
+        }
+
+        // Rewrite table constraints (including those moved from range columns)
+        let mut kept = Vec::new();
+        for constraint in std::mem::take(&mut table.constraints)
+            .into_iter()
+            .chain(extra_constraints)
+        {
+            match constraint {
+                TableConstraint::PrimaryKey { name, columns } => {
+                    kept.push(TableConstraint::PrimaryKey {
+                        name,
+                        columns: expand_columns(&columns, &splits),
+                    });
+                }
+                TableConstraint::Unique { name, columns } => {
+                    kept.push(TableConstraint::Unique {
+                        name,
+                        columns: expand_columns(&columns, &splits),
+                    });
+                }
+                TableConstraint::Check { name, mut expr } => {
+                    match rewrite_range_expr(&mut expr, &splits) {
+                        Ok(()) => kept.push(TableConstraint::Check { name, expr }),
+                        Err(column) => warnings.push(
+                            Warning::new(
+                                warning::RANGE_EXPR_UNSUPPORTED,
+                                Severity::Unsupported,
+                                format!(
+                                    "CHECK uses range column '{column}' beyond lower()/upper(); dropped"
+                                ),
+                            )
+                            .with_object(&table_name),
+                        ),
+                    }
+                }
+                fk @ TableConstraint::ForeignKey { .. } => kept.push(fk),
+            }
+        }
+        table.constraints = kept;
+
+        // Column-level CHECKs on other columns may reference range bounds too
+        for col in &mut table.columns {
+            if let Some(check) = &mut col.check
+                && let Err(column) = rewrite_range_expr(check, &splits)
+            {
+                warnings.push(
+                    Warning::new(
+                        warning::RANGE_EXPR_UNSUPPORTED,
+                        Severity::Unsupported,
+                        format!(
+                            "CHECK uses range column '{column}' beyond lower()/upper(); dropped"
+                        ),
+                    )
+                    .with_object(format!("{}.{}", table_name, col.name.normalized)),
+                );
+                col.check = None;
+            }
+        }
+
+        split_by_table.insert(table_name, splits);
+    }
+
+    // Indexes: expand plain range columns, rewrite bound expressions
+    model.indexes.retain_mut(|index| {
+        let Some(splits) = split_by_table.get(&index.table.name.normalized) else {
+            return true;
+        };
+        let mut columns = Vec::new();
+        for col in std::mem::take(&mut index.columns) {
+            match col {
+                IndexColumn::Column(ident) => match splits.get(&ident.normalized) {
+                    Some(split) => {
+                        columns.push(IndexColumn::Column(split.lower.clone()));
+                        columns.push(IndexColumn::Column(split.upper.clone()));
+                    }
+                    None => columns.push(IndexColumn::Column(ident)),
+                },
+                IndexColumn::Expression(mut expr) => {
+                    if let Err(column) = rewrite_range_expr(&mut expr, splits) {
+                        warnings.push(index_dropped(&index.name, &column));
+                        return false;
+                    }
+                    columns.push(match expr {
+                        Expr::ColumnRef(name) => IndexColumn::Column(Ident {
+                            raw: name.clone(),
+                            normalized: name,
+                        }),
+                        expr => IndexColumn::Expression(expr),
+                    });
+                }
+            }
+        }
+        index.columns = columns;
+
+        if let Some(where_clause) = &mut index.where_clause
+            && let Err(column) = rewrite_range_expr(where_clause, splits)
+        {
+            warnings.push(index_dropped(&index.name, &column));
+            return false;
+        }
+        true
+    });
+}
+
+fn index_dropped(index: &Ident, column: &str) -> Warning {
+    Warning::new(
+        warning::RANGE_EXPR_UNSUPPORTED,
+        Severity::Unsupported,
+        format!("index uses range column '{column}' beyond lower()/upper(); index skipped"),
+    )
+    .with_object(&index.normalized)
+}
+
+/// Replace each split range column in a column list with its bound columns.
+fn expand_columns(columns: &[Ident], splits: &HashMap<String, SplitRange>) -> Vec<Ident> {
+    columns
+        .iter()
+        .flat_map(|c| match splits.get(&c.normalized) {
+            Some(split) => vec![split.lower.clone(), split.upper.clone()],
+            None => vec![c.clone()],
+        })
+        .collect()
+}
+
+/// Rewrite `lower(r)`, `upper(r)`, `lower_inc(r)` and `upper_inc(r)` to bound columns.
+/// Returns the name of a range column that is used in any other way.
+fn rewrite_range_expr(expr: &mut Expr, splits: &HashMap<String, SplitRange>) -> Result<(), String> {
+    let mut unsupported = None;
+    expr.walk_mut(&mut |node| {
+        let replacement = match node {
+            Expr::FunctionCall { name, args } if args.len() == 1 => match &args[0] {
+                Expr::ColumnRef(col) => splits.get(col).and_then(|split| match name.as_str() {
+                    "lower" => Some(&split.lower),
+                    "upper" => Some(&split.upper),
+                    "lower_inc" => split.lower_inc.as_ref(),
+                    "upper_inc" => split.upper_inc.as_ref(),
+                    _ => None,
+                }),
+                _ => None,
+            },
+            Expr::ColumnRef(col) if splits.contains_key(col) => {
+                unsupported.get_or_insert_with(|| col.clone());
+                None
+            }
+            _ => None,
+        };
+        if let Some(ident) = replacement {
+            *node = Expr::ColumnRef(ident.normalized.clone());
+        }
+    });
+    match unsupported {
+        Some(column) => Err(column),
+        None => Ok(()),
+    }
+}
+
+/// Bound defaults extracted from a range default expression.
+struct RangeBounds {
+    lower: Option<Expr>,
+    upper: Option<Expr>,
+    lower_inc: bool,
+    upper_inc: bool,
+}
+
+/// Split a range default (`'[a,b)'::tstzrange` or `tstzrange(a, b, '[)')`) into bound defaults.
+fn split_range_default(
+    default: &Expr,
+    pg_type: &PgType,
+    with_flags: bool,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<RangeBounds> {
+    let bounds = match default {
+        Expr::Cast { expr, .. } => match expr.as_ref() {
+            Expr::StringLiteral(text) => parse_range_literal(text, pg_type),
+            _ => None,
+        },
+        Expr::StringLiteral(text) => parse_range_literal(text, pg_type),
+        Expr::FunctionCall { name, args }
+            if *name == pg_type.to_string() && (2..=3).contains(&args.len()) =>
+        {
+            let flags = match args.get(2) {
+                Some(Expr::StringLiteral(flags)) => flags.clone(),
+                Some(_) => return unsupported_default(default, object, warnings),
+                None => "[)".to_string(),
+            };
+            let bound = |e: &Expr| (!matches!(e, Expr::Null)).then(|| e.clone());
+            Some(RangeBounds {
+                lower: bound(&args[0]),
+                upper: bound(&args[1]),
+                lower_inc: flags.starts_with('['),
+                upper_inc: flags.ends_with(']'),
+            })
+        }
+        _ => None,
+    };
+
+    let Some(mut bounds) = bounds else {
+        return unsupported_default(default, object, warnings);
+    };
+
+    // Without flag columns, bounds are stored as [lower, upper)
+    if !with_flags && (!bounds.lower_inc || bounds.upper_inc) {
+        let discrete = matches!(pg_type, PgType::Int4Range | PgType::Int8Range);
+        let step = |bound: &mut Option<Expr>| {
+            if let Some(Expr::IntegerLiteral(n)) = bound {
+                *n += 1;
+            }
+        };
+        if discrete {
+            if !bounds.lower_inc {
+                step(&mut bounds.lower);
+            }
+            if bounds.upper_inc {
+                step(&mut bounds.upper);
+            }
+        } else {
+            warnings.push(
+                Warning::new(
+                    warning::RANGE_EXPR_UNSUPPORTED,
+                    Severity::Lossy,
+                    "range default bound inclusivity not representable without flag columns; stored as [lower, upper)",
+                )
+                .with_object(object),
+            );
+        }
+        bounds.lower_inc = true;
+        bounds.upper_inc = false;
+    }
+
+    Some(bounds)
+}
+
+fn unsupported_default(
+    default: &Expr,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<RangeBounds> {
+    warnings.push(
+        Warning::new(
+            warning::RANGE_EXPR_UNSUPPORTED,
+            Severity::Unsupported,
+            format!(
+                "range default '{}' cannot be split into bounds; dropped",
+                default.to_sql()
+            ),
+        )
+        .with_object(object),
+    );
+    None
+}
+
+/// Parse a PG range literal such as `[1,10)` or `["2020-01-01 00:00","2021-01-01 00:00")`.
+fn parse_range_literal(text: &str, pg_type: &PgType) -> Option<RangeBounds> {
+    let text = text.trim();
+    let lower_inc = match text.chars().next()? {
+        '[' => true,
+        '(' => false,
+        _ => return None,
+    };
+    let upper_inc = match text.chars().last()? {
+        ']' => true,
+        ')' => false,
+        _ => return None,
+    };
+    let inner = &text[1..text.len() - 1];
+
+    // Split on the first comma outside double quotes
+    let mut in_quotes = false;
+    let comma = inner.char_indices().find_map(|(i, c)| {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => return Some(i),
+            _ => {}
+        }
+        None
+    })?;
+    let bound = |raw: &str| -> Option<Expr> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+        let value = raw.trim_matches('"');
+        match pg_type {
+            PgType::Int4Range | PgType::Int8Range => value
+                .parse::<i64>()
+                .map(Expr::IntegerLiteral)
+                .ok()
+                .or_else(|| Some(Expr::StringLiteral(value.to_string()))),
+            PgType::NumRange => value
+                .parse::<f64>()
+                .map(Expr::FloatLiteral)
+                .ok()
+                .or_else(|| Some(Expr::StringLiteral(value.to_string()))),
+            _ => Some(Expr::StringLiteral(value.to_string())),
+        }
+    };
+
+    Some(RangeBounds {
+        lower: bound(&inner[..comma]),
+        upper: bound(&inner[comma + 1..]),
+        lower_inc,
+        upper_inc,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Index, QualifiedName, Table};
+
+    fn make_column(name: &str, pg_type: PgType) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type: None,
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn booking_model(during: Column, constraints: Vec<TableConstraint>) -> SchemaModel {
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("bookings")),
+                columns: vec![make_column("id", PgType::Integer), during],
+                constraints,
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn column_names(model: &SchemaModel) -> Vec<&str> {
+        model.tables[0]
+            .columns
+            .iter()
+            .map(|c| c.name.normalized.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_columns_and_bounds_check() {
+        let mut model = booking_model(make_column("during", PgType::TsTzRange), vec![]);
+        let mut w = Vec::new();
+        apply_range_mode(&mut model, RangeMode::Split, &mut w);
+
+        assert_eq!(
+            column_names(&model),
+            vec!["id", "during_lower", "during_upper"]
+        );
+        assert_eq!(
+            model.tables[0].columns[1].pg_type,
+            PgType::Timestamp { with_tz: true }
+        );
+        assert!(matches!(
+            &model.tables[0].constraints[0],
+            TableConstraint::Check { expr, .. } if expr.to_sql() == "during_lower <= during_upper"
+        ));
+        assert!(w.iter().any(|w| w.code == warning::RANGE_SPLIT));
+    }
+
+    #[test]
+    fn test_not_null_kept_as_bound_check() {
+        let mut col = make_column("during", PgType::TsTzRange);
+        col.not_null = true;
+        let mut model = booking_model(col, vec![]);
+        let mut w = Vec::new();
+        apply_range_mode(&mut model, RangeMode::Split, &mut w);
+
+        let checks: Vec<String> = model.tables[0]
+            .constraints
+            .iter()
+            .filter_map(|c| match c {
+                TableConstraint::Check { expr, .. } => Some(expr.to_sql()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            checks,
+            [
+                "during_lower <= during_upper",
+                "during_lower IS NOT NULL OR during_upper IS NOT NULL"
+            ]
+        );
+        assert!(model.tables[0].columns.iter().all(|c| !c.not_null));
+        assert!(w.iter().any(|w| w.severity == Severity::Lossy));
+    }
+
+    #[test]
+    fn test_split_inclusive_adds_flags() {
+        let mut col = make_column("during", PgType::DateRange);
+        col.default = Some(Expr::Cast {
+            expr: Box::new(Expr::StringLiteral("[2020-01-01,2021-01-01]".to_string())),
+            type_name: "daterange".to_string(),
+        });
+        let mut model = booking_model(col, vec![]);
+        let mut w = Vec::new();
+        apply_range_mode(&mut model, RangeMode::SplitInclusive, &mut w);
+
+        assert_eq!(
+            column_names(&model),
+            vec![
+                "id",
+                "during_lower",
+                "during_upper",
+                "during_lower_inc",
+                "during_upper_inc"
+            ]
+        );
+        let cols = &model.tables[0].columns;
+        assert_eq!(
+            cols[1].default,
+            Some(Expr::StringLiteral("2020-01-01".to_string()))
+        );
+        assert_eq!(cols[4].default, Some(Expr::BooleanLiteral(true)));
+    }
+
+    #[test]
+    fn test_discrete_default_normalized_without_flags() {
+        let mut col = make_column("seats", PgType::Int4Range);
+        col.default = Some(Expr::FunctionCall {
+            name: "int4range".to_string(),
+            args: vec![
+                Expr::IntegerLiteral(1),
+                Expr::IntegerLiteral(10),
+                Expr::StringLiteral("[]".to_string()),
+            ],
+        });
+        let mut model = booking_model(col, vec![]);
+        let mut w = Vec::new();
+        apply_range_mode(&mut model, RangeMode::Split, &mut w);
+        let cols = &model.tables[0].columns;
+        assert_eq!(cols[1].default, Some(Expr::IntegerLiteral(1)));
+        assert_eq!(cols[2].default, Some(Expr::IntegerLiteral(11)));
+    }
+
+    #[test]
+    fn test_check_rewritten_or_dropped() {
+        let lower_check = TableConstraint::Check {
+            name: None,
+            expr: Expr::binary(
+                Expr::func("lower", vec![Expr::ColumnRef("during".to_string())]),
+                ">=",
+                Expr::StringLiteral("2020-01-01".to_string()),
+            ),
+        };
+        let opaque_check = TableConstraint::Check {
+            name: None,
+            expr: Expr::UnaryOp {
+                op: "NOT".to_string(),
+                expr: Box::new(Expr::func(
+                    "isempty",
+                    vec![Expr::ColumnRef("during".to_string())],
+                )),
+            },
+        };
+        let mut model = booking_model(
+            make_column("during", PgType::TsRange),
+            vec![lower_check, opaque_check],
+        );
+        let mut w = Vec::new();
+        apply_range_mode(&mut model, RangeMode::Split, &mut w);
+
+        let checks: Vec<String> = model.tables[0]
+            .constraints
+            .iter()
+            .filter_map(|c| match c {
+                TableConstraint::Check { expr, .. } => Some(expr.to_sql()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                "during_lower >= '2020-01-01'",
+                "during_lower <= during_upper"
+            ]
+        );
+        assert!(w.iter().any(|w| w.code == warning::RANGE_EXPR_UNSUPPORTED));
+    }
+
+    #[test]
+    fn test_index_columns_expanded_and_rewritten() {
+        let mut model = booking_model(make_column("during", PgType::TsTzRange), vec![]);
+        model.indexes = vec![
+            Index {
+                name: Ident::new("idx_during"),
+                table: QualifiedName::new(Ident::new("bookings")),
+                columns: vec![IndexColumn::Column(Ident::new("during"))],
+                unique: false,
+                method: None,
+                where_clause: None,
+            },
+            Index {
+                name: Ident::new("idx_during_end"),
+                table: QualifiedName::new(Ident::new("bookings")),
+                columns: vec![IndexColumn::Expression(Expr::func(
+                    "upper",
+                    vec![Expr::ColumnRef("during".to_string())],
+                ))],
+                unique: false,
+                method: None,
+                where_clause: None,
+            },
+        ];
+        let mut w = Vec::new();
+        apply_range_mode(&mut model, RangeMode::Split, &mut w);
+
+        assert!(matches!(
+            model.indexes[0].columns.as_slice(),
+            [IndexColumn::Column(a), IndexColumn::Column(b)]
+                if a.normalized == "during_lower" && b.normalized == "during_upper"
+        ));
+        assert!(matches!(
+            model.indexes[1].columns.as_slice(),
+            [IndexColumn::Column(c)] if c.normalized == "during_upper"
+        ));
+    }
+
+    #[test]
+    fn test_text_mode_is_noop() {
+        let mut model = booking_model(make_column("during", PgType::TsTzRange), vec![]);
+        let mut w = Vec::new();
+        apply_range_mode(&mut model, RangeMode::Text, &mut w);
+        assert_eq!(column_names(&model), vec!["id", "during"]);
+        assert!(w.is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+//! PG regular expression → SQLite GLOB / LIKE pattern translation.
+//!
+//! Only simple patterns translate: literal characters, `.`, `.*`, bracket
+//! classes, `\d`, escaped punctuation, `{n}` repeats and `^`/`$` anchors.
+//! Anything else (alternation, groups, `+`, `?`, `*` after an atom, …) needs
+//! a real regex engine.
+
+/// Largest `{n}` repeat that is expanded into `n` copies of the atom.
+const MAX_REPEAT: usize = 32;
+
+/// One matched element of a simple regex.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(char),
+    /// `.`
+    AnyChar,
+    /// `.*`
+    AnyString,
+    /// Bracket class contents, without the brackets (e.g. `A-Z`, `^0-9`)
+    Class(String),
+}
+
+/// A simple regex: an optionally anchored sequence of tokens.
+#[derive(Debug, PartialEq)]
+struct Pattern {
+    anchored_start: bool,
+    anchored_end: bool,
+    tokens: Vec<Token>,
+}
+
+/// Translate a regex to a case-sensitive GLOB pattern.
+pub fn to_glob(regex: &str) -> Option<String> {
+    let pattern = parse(regex)?;
+    let mut glob = String::new();
+    if !pattern.anchored_start {
+        glob.push('*');
+    }
+    for token in &pattern.tokens {
+        match token {
+            Token::Literal(c @ ('*' | '?' | '[')) => {
+                glob.push('[');
+                glob.push(*c);
+                glob.push(']');
+            }
+            Token::Literal(c) => glob.push(*c),
+            Token::AnyChar => glob.push('?'),
+            Token::AnyString => glob.push('*'),
+            Token::Class(class) => {
+                glob.push('[');
+                glob.push_str(class);
+                glob.push(']');
+            }
+        }
+    }
+    if !pattern.anchored_end {
+        glob.push('*');
+    }
+    Some(glob)
+}
+
+/// Translate a regex without bracket classes to a LIKE pattern.
+///
+/// Returns the pattern and whether it needs `ESCAPE '\'`.
+pub fn to_like(regex: &str) -> Option<(String, bool)> {
+    let pattern = parse(regex)?;
+    let mut like = String::new();
+    let mut escaped = false;
+    if !pattern.anchored_start {
+        like.push('%');
+    }
+    for token in &pattern.tokens {
+        match token {
+            Token::Literal(c @ ('%' | '_' | '\\')) => {
+                like.push('\\');
+                like.push(*c);
+                escaped = true;
+            }
+            Token::Literal(c) => like.push(*c),
+            Token::AnyChar => like.push('_'),
+            Token::AnyString => like.push('%'),
+            Token::Class(_) => return None,
+        }
+    }
+    if !pattern.anchored_end {
+        like.push('%');
+    }
+    Some((like, escaped))
+}
+
+fn parse(regex: &str) -> Option<Pattern> {
+    let (anchored_start, rest) = match regex.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, regex),
+    };
+    let mut chars: Vec<char> = rest.chars().collect();
+    // A trailing `$` anchors unless it is escaped
+    let trailing_backslashes = chars
+        .iter()
+        .rev()
+        .skip(1)
+        .take_while(|&&c| c == '\\')
+        .count();
+    let anchored_end = chars.last() == Some(&'$') && trailing_backslashes % 2 == 0;
+    if anchored_end {
+        chars.pop();
+    }
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let token = match chars[i] {
+            '\\' => {
+                i += 1;
+                match *chars.get(i)? {
+                    'd' => Token::Class("0-9".to_string()),
+                    // \w, \s, \b, \1, … have no pattern equivalent
+                    c if c.is_alphanumeric() => return None,
+                    c => Token::Literal(c),
+                }
+            }
+            '.' if chars.get(i + 1) == Some(&'*') => {
+                i += 1;
+                Token::AnyString
+            }
+            '.' => Token::AnyChar,
+            '[' => {
+                let len = chars[i + 1..].iter().position(|&c| c == ']')?;
+                let class: String = chars[i + 1..i + 1 + len].iter().collect();
+                // Empty, `[]…]` and POSIX `[[:alpha:]]` classes are not handled
+                if class.is_empty() || class == "^" || class.contains(['[', '\\']) {
+                    return None;
+                }
+                i += len + 1;
+                Token::Class(class)
+            }
+            '*' | '+' | '?' | '{' | '}' | '(' | ')' | '|' | '^' | '$' => return None,
+            c => Token::Literal(c),
+        };
+        i += 1;
+
+        // An exact `{n}` repeat of a single-character atom
+        let mut count = 1;
+        if chars.get(i) == Some(&'{') && token != Token::AnyString {
+            let len = chars[i + 1..].iter().position(|&c| c == '}')?;
+            let digits: String = chars[i + 1..i + 1 + len].iter().collect();
+            count = digits.parse().ok().filter(|n| *n <= MAX_REPEAT)?;
+            i += len + 2;
+        }
+        if matches!(chars.get(i), Some('*' | '+' | '?' | '{')) {
+            return None;
+        }
+        tokens.extend(std::iter::repeat_n(token, count));
+    }
+
+    Some(Pattern {
+        anchored_start,
+        anchored_end,
+        tokens,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_glob() {
+        assert_eq!(to_glob("^abc$").as_deref(), Some("abc"));
+        assert_eq!(to_glob("^abc").as_deref(), Some("abc*"));
+        assert_eq!(to_glob("abc").as_deref(), Some("*abc*"));
+        assert_eq!(to_glob("^a.c.*$").as_deref(), Some("a?c*"));
+        assert_eq!(to_glob("^[A-Z]{3}$").as_deref(), Some("[A-Z][A-Z][A-Z]"));
+        assert_eq!(
+            to_glob(r"^\d{4}-\d\d$").as_deref(),
+            Some("[0-9][0-9][0-9][0-9]-[0-9][0-9]")
+        );
+        assert_eq!(to_glob(r"^[^@]@x\.io$").as_deref(), Some("[^@]@x.io"));
+        assert_eq!(to_glob(r"^a\*b\?$").as_deref(), Some("a[*]b[?]"));
+        assert_eq!(to_glob(r"\$$").as_deref(), Some("*$"));
+    }
+
+    #[test]
+    fn test_not_simple() {
+        for regex in [
+            "^a+$",
+            "^(a|b)$",
+            "^a*",
+            "^[a-z]+@",
+            r"\w",
+            "a$b",
+            "^[[:alpha:]]$",
+            "^a{2,3}$",
+            "^[abc",
+        ] {
+            assert_eq!(to_glob(regex), None, "{regex}");
+        }
+    }
+
+    #[test]
+    fn test_to_like() {
+        assert_eq!(to_like("^abc").map(|l| l.0).as_deref(), Some("abc%"));
+        assert_eq!(to_like("^a.c.*$").map(|l| l.0).as_deref(), Some("a_c%"));
+        assert_eq!(
+            to_like("100%").as_ref().map(|(p, e)| (p.as_str(), *e)),
+            Some((r"%100\%%", true))
+        );
+        assert_eq!(to_like("^[a-z]$"), None);
+    }
+}
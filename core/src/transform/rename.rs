@@ -0,0 +1,881 @@
+/// Identifier renames: explicit table/column maps, regex rules and case conventions.
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::ir::{Expr, Ident, IndexColumn, QualifiedName, SchemaModel, TableConstraint};
+use crate::transform::metadata::ColumnOrigin;
+
+/// Case convention applied to names not renamed by an explicit map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameCase {
+    /// Keep names as they are (default).
+    #[default]
+    Preserve,
+    /// Lowercase names.
+    Lower,
+    /// Convert `UserAccount` and `userID` to `user_account` and `user_id`.
+    Snake,
+}
+
+impl std::str::FromStr for NameCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "preserve" => Ok(NameCase::Preserve),
+            "lower" => Ok(NameCase::Lower),
+            "snake" => Ok(NameCase::Snake),
+            other => Err(format!(
+                "unknown name case '{other}' (expected preserve, lower or snake)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for NameCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameCase::Preserve => write!(f, "preserve"),
+            NameCase::Lower => write!(f, "lower"),
+            NameCase::Snake => write!(f, "snake"),
+        }
+    }
+}
+
+/// Which names a rename rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenameTarget {
+    /// Table and column names (default).
+    #[default]
+    All,
+    Tables,
+    Columns,
+}
+
+impl RenameTarget {
+    fn tables(self) -> bool {
+        self != RenameTarget::Columns
+    }
+
+    fn columns(self) -> bool {
+        self != RenameTarget::Tables
+    }
+}
+
+impl std::str::FromStr for RenameTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "all" => Ok(RenameTarget::All),
+            "tables" => Ok(RenameTarget::Tables),
+            "columns" => Ok(RenameTarget::Columns),
+            other => Err(format!(
+                "unknown rename target '{other}' (expected all, tables or columns)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RenameTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenameTarget::All => write!(f, "all"),
+            RenameTarget::Tables => write!(f, "tables"),
+            RenameTarget::Columns => write!(f, "columns"),
+        }
+    }
+}
+
+/// A regex replacement applied to every matching table or column name.
+#[derive(Debug, Clone)]
+pub struct RenameRule {
+    pub target: RenameTarget,
+    pub pattern: Regex,
+    /// Replacement text; `$1` or `${name}` refer to capture groups.
+    pub replacement: String,
+}
+
+impl RenameRule {
+    pub fn new(
+        target: RenameTarget,
+        pattern: &str,
+        replacement: &str,
+    ) -> Result<Self, regex::Error> {
+        Ok(Self {
+            target,
+            pattern: Regex::new(pattern)?,
+            replacement: replacement.to_string(),
+        })
+    }
+
+    /// A rule removing a literal prefix.
+    pub fn strip_prefix(target: RenameTarget, prefix: &str) -> Self {
+        Self {
+            target,
+            pattern: Regex::new(&format!("^{}", regex::escape(prefix)))
+                .expect("escaped prefix is a valid regex"),
+            replacement: String::new(),
+        }
+    }
+}
+
+/// How to rename tables and columns in the output.
+#[derive(Debug, Clone, Default)]
+pub struct RenameOptions {
+    /// Convention for names without an explicit mapping.
+    pub case: NameCase,
+    /// New table names, keyed by table name.
+    pub tables: HashMap<String, String>,
+    /// New column names, keyed by `table.column` with the original table name.
+    pub columns: HashMap<String, String>,
+    /// Regex rules, applied in order before the case convention.
+    pub rules: Vec<RenameRule>,
+}
+
+impl RenameOptions {
+    fn is_empty(&self) -> bool {
+        self.case == NameCase::Preserve
+            && self.tables.is_empty()
+            && self.columns.is_empty()
+            && self.rules.is_empty()
+    }
+}
+
+/// Renames that cannot be applied.
+#[derive(Debug, thiserror::Error)]
+pub enum RenameError {
+    #[error("tables '{first}' and '{second}' would both be named '{name}'")]
+    TableCollision {
+        first: String,
+        second: String,
+        name: String,
+    },
+
+    #[error("columns '{table}.{first}' and '{table}.{second}' would both be named '{name}'")]
+    ColumnCollision {
+        table: String,
+        first: String,
+        second: String,
+        name: String,
+    },
+
+    #[error("'{original}' would be renamed to an empty name")]
+    EmptyName { original: String },
+}
+
+/// Rename tables and columns, and every reference to them.
+///
+/// Must run after [`resolve_names`](crate::transform::name_resolve::resolve_names),
+/// so table names no longer carry a schema, except in the attach layout,
+/// where tables of different schemas may share a name. Names with an explicit mapping
+/// take it verbatim; others go through the regex rules, then the case
+/// convention. Column lists of keys, foreign keys and indexes, and column
+/// references in defaults, CHECKs and index expressions follow the new
+/// names. Renames that make two tables, or two columns of a table, equal
+/// (ignoring case, as SQLite does) are rejected. `origins` is rekeyed by the
+/// new column names.
+pub fn apply_renames(
+    model: &mut SchemaModel,
+    origins: &mut [HashMap<String, ColumnOrigin>],
+    opts: &RenameOptions,
+) -> Result<(), RenameError> {
+    if opts.is_empty() {
+        return Ok(());
+    }
+    let renames = Renames::plan(model, opts)?;
+
+    for (table, origins) in model.tables.iter_mut().zip(origins.iter_mut()) {
+        let owner = table.name.name.normalized.to_lowercase();
+        for col in &mut table.columns {
+            let old = col.name.normalized.clone();
+            if renames.column(&owner, &mut col.name)
+                && let Some(origin) = origins.remove(&old)
+            {
+                origins.insert(col.name.normalized.clone(), origin);
+            }
+            if let Some(fk) = &mut col.references {
+                if let Some(column) = &mut fk.column {
+                    renames.column(&fk.table.name.normalized.to_lowercase(), column);
+                }
+                renames.table(&mut fk.table);
+            }
+            for expr in [&mut col.default, &mut col.check].into_iter().flatten() {
+                renames.expr(&owner, expr);
+            }
+        }
+        for constraint in &mut table.constraints {
+            renames.constraint(&owner, constraint);
+        }
+        renames.table(&mut table.name);
+    }
+
+    for index in &mut model.indexes {
+        let owner = index.table.name.normalized.to_lowercase();
+        for column in &mut index.columns {
+            match column {
+                IndexColumn::Column(name) => {
+                    renames.column(&owner, name);
+                }
+                IndexColumn::Expression(expr) => renames.expr(&owner, expr),
+            }
+        }
+        if let Some(expr) = &mut index.where_clause {
+            renames.expr(&owner, expr);
+        }
+        renames.table(&mut index.table);
+    }
+
+    for alter in &mut model.alter_constraints {
+        let owner = alter.table.name.normalized.to_lowercase();
+        renames.constraint(&owner, &mut alter.constraint);
+        renames.table(&mut alter.table);
+    }
+    for identity in &mut model.identity_columns {
+        let owner = identity.table.name.normalized.to_lowercase();
+        renames.column(&owner, &mut identity.column);
+        renames.table(&mut identity.table);
+    }
+    for seq in &mut model.sequences {
+        if let Some((table, column)) = &mut seq.owned_by {
+            renames.column(&table.name.normalized.to_lowercase(), column);
+            renames.table(table);
+        }
+    }
+    Ok(())
+}
+
+/// New names keyed by lowercased original names.
+struct Renames {
+    tables: HashMap<String, Ident>,
+    /// Column renames per original table name.
+    columns: HashMap<String, HashMap<String, Ident>>,
+}
+
+impl Renames {
+    fn plan(model: &SchemaModel, opts: &RenameOptions) -> Result<Self, RenameError> {
+        let table_map: HashMap<String, &String> = opts
+            .tables
+            .iter()
+            .map(|(old, new)| (old.trim().to_lowercase(), new))
+            .collect();
+        let column_map: HashMap<String, &String> = opts
+            .columns
+            .iter()
+            .map(|(old, new)| (old.trim().to_lowercase(), new))
+            .collect();
+
+        let mut renames = Renames {
+            tables: HashMap::new(),
+            columns: HashMap::new(),
+        };
+        let mut table_names: HashMap<(Option<String>, String), String> = HashMap::new();
+        for table in &model.tables {
+            let old = &table.name.name.normalized;
+            let key = old.to_lowercase();
+            let new = match table_map.get(&key) {
+                Some(new) => new.to_string(),
+                None => convert(old, opts, RenameTarget::tables),
+            };
+            if new.is_empty() {
+                return Err(RenameError::EmptyName {
+                    original: old.clone(),
+                });
+            }
+            let schema = table.name.schema.as_ref().map(|s| s.normalized.clone());
+            if let Some(first) = table_names.insert((schema, new.to_lowercase()), old.clone()) {
+                return Err(RenameError::TableCollision {
+                    first,
+                    second: old.clone(),
+                    name: new,
+                });
+            }
+            if new != *old {
+                renames.tables.insert(key.clone(), Ident::quoted(&new));
+            }
+
+            let mut column_names: HashMap<String, String> = HashMap::new();
+            let mut columns = HashMap::new();
+            for col in &table.columns {
+                let old_col = &col.name.normalized;
+                let col_key = old_col.to_lowercase();
+                let new = match column_map.get(&format!("{key}.{col_key}")) {
+                    Some(new) => new.to_string(),
+                    None => convert(old_col, opts, RenameTarget::columns),
+                };
+                if new.is_empty() {
+                    return Err(RenameError::EmptyName {
+                        original: format!("{old}.{old_col}"),
+                    });
+                }
+                if let Some(first) = column_names.insert(new.to_lowercase(), old_col.clone()) {
+                    return Err(RenameError::ColumnCollision {
+                        table: old.clone(),
+                        first,
+                        second: old_col.clone(),
+                        name: new,
+                    });
+                }
+                if new != *old_col {
+                    columns.insert(col_key, Ident::quoted(&new));
+                }
+            }
+            if !columns.is_empty() {
+                renames.columns.entry(key).or_default().extend(columns);
+            }
+        }
+        Ok(renames)
+    }
+
+    fn table(&self, name: &mut QualifiedName) {
+        if let Some(new) = self.tables.get(&name.name.normalized.to_lowercase()) {
+            name.name = new.clone();
+        }
+    }
+
+    /// Rename a column of `table` (original, lowercased), returning whether it changed.
+    fn column(&self, table: &str, name: &mut Ident) -> bool {
+        match self.new_column(table, &name.normalized) {
+            Some(new) => {
+                *name = new.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn new_column(&self, table: &str, column: &str) -> Option<&Ident> {
+        self.columns.get(table)?.get(&column.to_lowercase())
+    }
+
+    fn constraint(&self, owner: &str, constraint: &mut TableConstraint) {
+        match constraint {
+            TableConstraint::PrimaryKey { columns, .. }
+            | TableConstraint::Unique { columns, .. } => {
+                for column in columns {
+                    self.column(owner, column);
+                }
+            }
+            TableConstraint::ForeignKey {
+                columns,
+                ref_table,
+                ref_columns,
+                ..
+            } => {
+                for column in columns {
+                    self.column(owner, column);
+                }
+                let target = ref_table.name.normalized.to_lowercase();
+                for column in ref_columns {
+                    self.column(&target, column);
+                }
+                self.table(ref_table);
+            }
+            TableConstraint::Check { expr, .. } => self.expr(owner, expr),
+        }
+    }
+
+    /// Rename the column references in an expression on `owner`'s columns.
+    fn expr(&self, owner: &str, expr: &mut Expr) {
+        expr.walk_mut(&mut |e| match e {
+            Expr::ColumnRef(name) => {
+                let renamed = match name.rsplit_once('.') {
+                    Some((table, column)) => {
+                        let key = table.to_lowercase();
+                        let table = self
+                            .tables
+                            .get(&key)
+                            .map_or(table, |new| new.normalized.as_str());
+                        let column = self
+                            .new_column(&key, column)
+                            .map_or(column, |new| new.normalized.as_str());
+                        format!("{table}.{column}")
+                    }
+                    None => match self.new_column(owner, name) {
+                        Some(new) => new.normalized.clone(),
+                        None => return,
+                    },
+                };
+                *name = renamed;
+            }
+            Expr::Raw(sql) => *sql = self.raw(owner, sql),
+            _ => {}
+        });
+    }
+
+    /// Rename identifiers in raw SQL, leaving string literals, function
+    /// names and keywords alone. `t.col` is read as a column of table `t`.
+    fn raw(&self, owner: &str, sql: &str) -> String {
+        let chars: Vec<char> = sql.chars().collect();
+        let mut out = String::with_capacity(sql.len());
+        // Original table named by the qualifier just before the current token
+        let mut qualifier: Option<String> = None;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let start = i;
+            let (token, quoted) = if c == '\'' {
+                i = skip_quoted(&chars, i, '\'');
+                out.extend(&chars[start..i]);
+                qualifier = None;
+                continue;
+            } else if c == '"' {
+                i = skip_quoted(&chars, i, '"');
+                let inner: String = chars[start + 1..i.saturating_sub(1).max(start + 1)]
+                    .iter()
+                    .collect();
+                (inner.replace("\"\"", "\""), true)
+            } else if c.is_alphabetic() || c == '_' {
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$'))
+                {
+                    i += 1;
+                }
+                (chars[start..i].iter().collect::<String>(), false)
+            } else if c.is_ascii_digit() {
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.'))
+                {
+                    i += 1;
+                }
+                out.extend(&chars[start..i]);
+                qualifier = None;
+                continue;
+            } else {
+                out.push(c);
+                i += 1;
+                if c != '.' {
+                    qualifier = None;
+                }
+                continue;
+            };
+
+            let next = chars[i..].iter().find(|c| !c.is_whitespace());
+            let original: String = chars[start..i].iter().collect();
+            let is_keyword = !quoted && Ident::new(&token).needs_quotes();
+            let renamed = if next == Some(&'(') || is_keyword {
+                None
+            } else if chars.get(i) == Some(&'.') {
+                qualifier = Some(token.to_lowercase());
+                out.push_str(
+                    &self
+                        .tables
+                        .get(&token.to_lowercase())
+                        .map_or(original, |new| new.to_sql()),
+                );
+                continue;
+            } else {
+                let table = match chars[..start].last() {
+                    Some('.') => qualifier.as_deref(),
+                    _ => Some(owner),
+                };
+                table.and_then(|table| self.new_column(table, &token))
+            };
+            qualifier = None;
+            match renamed {
+                Some(new) => out.push_str(&new.to_sql()),
+                None => out.push_str(&original),
+            }
+        }
+        out
+    }
+}
+
+/// Index just past the quoted run starting at `start`, honoring doubled quotes.
+fn skip_quoted(chars: &[char], start: usize, quote: char) -> usize {
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Apply the rules for `applies` names, then the case convention.
+fn convert(name: &str, opts: &RenameOptions, applies: fn(RenameTarget) -> bool) -> String {
+    let mut name = name.to_string();
+    for rule in opts.rules.iter().filter(|r| applies(r.target)) {
+        name = rule
+            .pattern
+            .replace_all(&name, rule.replacement.as_str())
+            .into_owned();
+    }
+    match opts.case {
+        NameCase::Preserve => name,
+        NameCase::Lower => name.to_lowercase(),
+        NameCase::Snake => snake_case(&name),
+    }
+}
+
+/// Lowercase a name, splitting words at case changes and separators with `_`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut separate = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            separate = true;
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                separate = true;
+            }
+        }
+        if separate && !out.is_empty() {
+            out.push('_');
+        }
+        separate = false;
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{
+        AlterConstraint, AlterIdentity, Column, ForeignKeyRef, Index, PgType, SqliteType, Table,
+    };
+
+    fn column(name: &str) -> Column {
+        Column {
+            name: Ident::quoted(name),
+            pg_type: PgType::Integer,
+            sqlite_type: None,
+            not_null: false,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn sample() -> SchemaModel {
+        let mut owner = column("OwnerId");
+        owner.references = Some(ForeignKeyRef {
+            table: QualifiedName::new(Ident::quoted("tbl_UserAccount")),
+            column: Some(Ident::quoted("UserId")),
+            on_delete: None,
+            on_update: None,
+        });
+        owner.check = Some(Expr::Raw(
+            "\"OwnerId\" > 0 AND OwnerId <> 'OwnerId'".to_string(),
+        ));
+        SchemaModel {
+            tables: vec![
+                Table {
+                    name: QualifiedName::new(Ident::quoted("tbl_UserAccount")),
+                    columns: vec![column("UserId"), column("DisplayName")],
+                    constraints: vec![
+                        TableConstraint::PrimaryKey {
+                            name: None,
+                            columns: vec![Ident::quoted("UserId")],
+                        },
+                        TableConstraint::Check {
+                            name: None,
+                            expr: Expr::func("length", vec![Expr::ColumnRef("displayname".into())]),
+                        },
+                    ],
+                    without_rowid: false,
+                    note: None,
+                },
+                Table {
+                    name: QualifiedName::new(Ident::quoted("tbl_Document")),
+                    columns: vec![column("DocId"), owner],
+                    constraints: vec![TableConstraint::ForeignKey {
+                        name: None,
+                        columns: vec![Ident::quoted("OwnerId")],
+                        ref_table: QualifiedName::new(Ident::quoted("tbl_UserAccount")),
+                        ref_columns: vec![Ident::quoted("UserId")],
+                        on_delete: None,
+                        on_update: None,
+                        deferrable: false,
+                    }],
+                    without_rowid: false,
+                    note: None,
+                },
+            ],
+            indexes: vec![Index {
+                name: Ident::new("idx_doc_owner"),
+                table: QualifiedName::new(Ident::quoted("tbl_Document")),
+                columns: vec![IndexColumn::Column(Ident::quoted("OwnerId"))],
+                unique: false,
+                method: None,
+                where_clause: Some(Expr::IsNull {
+                    expr: Box::new(Expr::ColumnRef("tbl_document.docid".into())),
+                    negated: true,
+                }),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn snake_without_prefix() -> RenameOptions {
+        RenameOptions {
+            case: NameCase::Snake,
+            tables: HashMap::from([("TBL_DOCUMENT".to_string(), "documents".to_string())]),
+            rules: vec![RenameRule::strip_prefix(RenameTarget::Tables, "tbl_")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("UserAccount"), "user_account");
+        assert_eq!(snake_case("userID2"), "user_id2");
+        assert_eq!(snake_case("HTTPServer"), "http_server");
+        assert_eq!(snake_case("tbl_UserAccount"), "tbl_user_account");
+        assert_eq!(snake_case("Order Line-Item"), "order_line_item");
+        assert_eq!(snake_case("already_snake"), "already_snake");
+    }
+
+    #[test]
+    fn test_renames_every_reference() {
+        let mut model = sample();
+        let mut origins = crate::transform::metadata::collect_origins(&model);
+        apply_renames(&mut model, &mut origins, &snake_without_prefix()).unwrap();
+
+        let users = &model.tables[0];
+        assert_eq!(users.name.name.normalized, "user_account");
+        assert_eq!(users.columns[0].name.normalized, "user_id");
+        assert!(matches!(
+            &users.constraints[0],
+            TableConstraint::PrimaryKey { columns, .. } if columns[0].normalized == "user_id"
+        ));
+        let TableConstraint::Check { expr, .. } = &users.constraints[1] else {
+            panic!("expected CHECK");
+        };
+        assert_eq!(expr.to_sql(), "length(display_name)");
+
+        let docs = &model.tables[1];
+        assert_eq!(docs.name.name.normalized, "documents");
+        let owner = &docs.columns[1];
+        assert_eq!(owner.name.normalized, "owner_id");
+        let fk = owner.references.as_ref().unwrap();
+        assert_eq!(fk.table.name.normalized, "user_account");
+        assert_eq!(fk.column.as_ref().unwrap().normalized, "user_id");
+        assert_eq!(
+            owner.check.as_ref().unwrap().to_sql(),
+            "owner_id > 0 AND owner_id <> 'OwnerId'"
+        );
+        let TableConstraint::ForeignKey {
+            columns,
+            ref_table,
+            ref_columns,
+            ..
+        } = &docs.constraints[0]
+        else {
+            panic!("expected FK");
+        };
+        assert_eq!(columns[0].normalized, "owner_id");
+        assert_eq!(ref_table.name.normalized, "user_account");
+        assert_eq!(ref_columns[0].normalized, "user_id");
+
+        let index = &model.indexes[0];
+        assert_eq!(index.table.name.normalized, "documents");
+        assert!(matches!(&index.columns[0], IndexColumn::Column(c) if c.normalized == "owner_id"));
+        assert_eq!(
+            index.where_clause.as_ref().unwrap().to_sql(),
+            "documents.doc_id IS NOT NULL"
+        );
+        assert!(origins[1].contains_key("owner_id"));
+    }
+
+    #[test]
+    fn test_collisions_rejected() {
+        let mut model = sample();
+        let mut origins = crate::transform::metadata::collect_origins(&model);
+        let opts = RenameOptions {
+            rules: vec![RenameRule::new(RenameTarget::Tables, "^tbl_.*", "t").unwrap()],
+            ..Default::default()
+        };
+        let err = apply_renames(&mut model, &mut origins, &opts).unwrap_err();
+        assert!(matches!(err, RenameError::TableCollision { .. }));
+
+        let opts = RenameOptions {
+            columns: HashMap::from([(
+                "tbl_useraccount.displayname".to_string(),
+                "USERID".to_string(),
+            )]),
+            ..Default::default()
+        };
+        let err = apply_renames(&mut model, &mut origins, &opts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "columns 'tbl_UserAccount.UserId' and 'tbl_UserAccount.DisplayName' would both be named 'USERID'"
+        );
+        // Nothing is renamed when a collision is found
+        assert_eq!(model.tables[0].name.name.normalized, "tbl_UserAccount");
+    }
+
+    #[test]
+    fn test_renames_reach_expressions_and_alters() {
+        let mut model = sample();
+        model.tables[0].columns[1].check = Some(Expr::binary(
+            Expr::ColumnRef("tbl_useraccount.displayname".into()),
+            "<>",
+            Expr::StringLiteral("DisplayName".into()),
+        ));
+        model.indexes.push(Index {
+            name: Ident::new("idx_user_name"),
+            table: QualifiedName::new(Ident::quoted("tbl_UserAccount")),
+            columns: vec![
+                IndexColumn::Expression(Expr::func(
+                    "lower",
+                    vec![Expr::ColumnRef("DisplayName".into())],
+                )),
+                IndexColumn::Column(Ident::quoted("UserId")),
+            ],
+            unique: true,
+            method: None,
+            where_clause: None,
+        });
+        model.alter_constraints.push(AlterConstraint {
+            table: QualifiedName::new(Ident::quoted("tbl_Document")),
+            constraint: TableConstraint::ForeignKey {
+                name: None,
+                columns: vec![Ident::quoted("DocId")],
+                ref_table: QualifiedName::new(Ident::quoted("tbl_UserAccount")),
+                ref_columns: vec![Ident::quoted("UserId")],
+                on_delete: None,
+                on_update: None,
+                deferrable: false,
+            },
+        });
+        model.identity_columns.push(AlterIdentity {
+            table: QualifiedName::new(Ident::quoted("tbl_UserAccount")),
+            column: Ident::quoted("UserId"),
+        });
+        let mut origins = crate::transform::metadata::collect_origins(&model);
+        apply_renames(&mut model, &mut origins, &snake_without_prefix()).unwrap();
+
+        assert_eq!(
+            model.tables[0].columns[1].check.as_ref().unwrap().to_sql(),
+            "user_account.display_name <> 'DisplayName'"
+        );
+        let index = &model.indexes[1];
+        assert_eq!(index.table.name.normalized, "user_account");
+        assert!(matches!(
+            &index.columns[0],
+            IndexColumn::Expression(e) if e.to_sql() == "lower(display_name)"
+        ));
+        assert!(matches!(&index.columns[1], IndexColumn::Column(c) if c.normalized == "user_id"));
+
+        let alter = &model.alter_constraints[0];
+        assert_eq!(alter.table.name.normalized, "documents");
+        let TableConstraint::ForeignKey {
+            columns,
+            ref_table,
+            ref_columns,
+            ..
+        } = &alter.constraint
+        else {
+            panic!("expected FK");
+        };
+        assert_eq!(columns[0].normalized, "doc_id");
+        assert_eq!(ref_table.name.normalized, "user_account");
+        assert_eq!(ref_columns[0].normalized, "user_id");
+        let identity = &model.identity_columns[0];
+        assert_eq!(identity.table.name.normalized, "user_account");
+        assert_eq!(identity.column.normalized, "user_id");
+    }
+
+    #[test]
+    fn test_renames_reach_column_metadata() {
+        let mut model = sample();
+        model.tables[1].columns[1].pg_type = PgType::BigInt;
+        let mut origins = crate::transform::metadata::collect_origins(&model);
+        // Transforms may retype a column after its origin is recorded
+        for table in &mut model.tables {
+            for col in &mut table.columns {
+                col.pg_type = PgType::Integer;
+                col.sqlite_type = Some(SqliteType::Integer);
+            }
+        }
+        apply_renames(&mut model, &mut origins, &snake_without_prefix()).unwrap();
+        crate::transform::metadata::build_column_metadata(&mut model, &origins, Default::default());
+
+        let rows: Vec<(&str, &str, &str)> = model
+            .column_metadata
+            .iter()
+            .map(|m| (m.table.as_str(), m.column.as_str(), m.pg_type.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("user_account", "user_id", "integer"),
+                ("user_account", "display_name", "integer"),
+                ("documents", "doc_id", "integer"),
+                ("documents", "owner_id", "bigint"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_colliding_outputs_rejected() {
+        let mut origins = crate::transform::metadata::collect_origins(&sample());
+
+        // Equal ignoring case, as SQLite compares names
+        let mut model = sample();
+        let opts = RenameOptions {
+            case: NameCase::Snake,
+            tables: HashMap::from([("tbl_document".to_string(), "TBL_USER_ACCOUNT".to_string())]),
+            ..Default::default()
+        };
+        let err = apply_renames(&mut model, &mut origins, &opts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "tables 'tbl_UserAccount' and 'tbl_Document' would both be named 'TBL_USER_ACCOUNT'"
+        );
+
+        // A case convention folding two columns together
+        let mut model = sample();
+        model.tables[0].columns.push(column("userid"));
+        let opts = RenameOptions {
+            case: NameCase::Lower,
+            ..Default::default()
+        };
+        let err = apply_renames(&mut model, &mut origins, &opts).unwrap_err();
+        assert!(matches!(
+            err,
+            RenameError::ColumnCollision { ref table, ref name, .. }
+                if table == "tbl_UserAccount" && name == "userid"
+        ));
+
+        let mut model = sample();
+        let opts = RenameOptions {
+            rules: vec![RenameRule::new(RenameTarget::Columns, "^Doc.*", "").unwrap()],
+            ..Default::default()
+        };
+        let err = apply_renames(&mut model, &mut origins, &opts).unwrap_err();
+        assert!(matches!(
+            err,
+            RenameError::EmptyName { ref original } if original == "tbl_Document.DocId"
+        ));
+
+        // Tables of different schemas may share a name in the attach layout
+        let mut model = sample();
+        for (table, schema) in model.tables.iter_mut().zip(["app", "docs"]) {
+            table.name.schema = Some(Ident::new(schema));
+        }
+        let opts = RenameOptions {
+            rules: vec![RenameRule::new(RenameTarget::Tables, "^tbl_.*", "t").unwrap()],
+            ..Default::default()
+        };
+        apply_renames(&mut model, &mut origins, &opts).unwrap();
+        assert!(model.tables.iter().all(|t| t.name.name.normalized == "t"));
+    }
+}
@@ -0,0 +1,223 @@
+/// STRICT tables: restrict column types to those STRICT allows and validate defaults.
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Expr, SchemaModel, SqliteType};
+
+/// Declared type for NUMERIC-affinity columns, which STRICT tables disallow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrictNumeric {
+    /// `REAL`: values are enforced as numbers but lose decimal precision (default).
+    #[default]
+    Real,
+    /// `TEXT`: exact decimal text, compared lexically.
+    Text,
+    /// `ANY`: values are stored exactly as given, without type enforcement.
+    Any,
+}
+
+impl std::str::FromStr for StrictNumeric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "real" => Ok(StrictNumeric::Real),
+            "text" => Ok(StrictNumeric::Text),
+            "any" => Ok(StrictNumeric::Any),
+            other => Err(format!(
+                "unknown strict numeric type '{other}' (expected real, text or any)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for StrictNumeric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StrictNumeric::Real => write!(f, "real"),
+            StrictNumeric::Text => write!(f, "text"),
+            StrictNumeric::Any => write!(f, "any"),
+        }
+    }
+}
+
+/// Replace NUMERIC column types and drop literal defaults a STRICT table would reject.
+pub fn apply_strict_tables(
+    model: &mut SchemaModel,
+    numeric: StrictNumeric,
+    warnings: &mut Vec<Warning>,
+) {
+    for table in &mut model.tables {
+        for col in &mut table.columns {
+            let obj = format!("{}.{}", table.name.name.normalized, col.name.normalized);
+
+            if col.sqlite_type == Some(SqliteType::Numeric) {
+                let (sqlite_type, severity) = match numeric {
+                    StrictNumeric::Real => (SqliteType::Real, Severity::Lossy),
+                    StrictNumeric::Text => (SqliteType::Text, Severity::Lossy),
+                    StrictNumeric::Any => (SqliteType::Any, Severity::Info),
+                };
+                warnings.push(
+                    Warning::new(
+                        warning::STRICT_TYPE_CHANGED,
+                        severity,
+                        format!(
+                            "NUMERIC is not allowed in STRICT tables; declared as {sqlite_type}"
+                        ),
+                    )
+                    .with_object(&obj),
+                );
+                col.sqlite_type = Some(sqlite_type);
+            }
+
+            let (Some(sqlite_type), Some(default)) = (col.sqlite_type, &col.default) else {
+                continue;
+            };
+            if default_fits(default, sqlite_type) == Some(false) {
+                warnings.push(
+                    Warning::new(
+                        warning::STRICT_DEFAULT_INVALID,
+                        Severity::Lossy,
+                        format!(
+                            "default {} cannot be stored in a STRICT {sqlite_type} column; dropped",
+                            default.to_sql()
+                        ),
+                    )
+                    .with_object(&obj),
+                );
+                col.default = None;
+            }
+        }
+    }
+}
+
+/// The storage class of a literal default, with text kept for numeric conversion.
+enum Value<'a> {
+    Integer,
+    Real(f64),
+    Text(&'a str),
+    Blob,
+}
+
+/// Whether a default can be stored in a STRICT column of `sqlite_type`, following
+/// SQLite's lossless conversion rules. `None` when the default is not a literal.
+fn default_fits(default: &Expr, sqlite_type: SqliteType) -> Option<bool> {
+    let value = match default {
+        Expr::Null => return Some(true),
+        Expr::Nested(inner) => return default_fits(inner, sqlite_type),
+        Expr::IntegerLiteral(_) | Expr::BooleanLiteral(_) => Value::Integer,
+        Expr::FloatLiteral(n) => Value::Real(*n),
+        Expr::UnaryOp { op, expr } if op == "-" => match &**expr {
+            Expr::IntegerLiteral(_) => Value::Integer,
+            Expr::FloatLiteral(n) => Value::Real(-n),
+            _ => return None,
+        },
+        Expr::StringLiteral(s) => Value::Text(s),
+        Expr::CurrentTimestamp => Value::Text("CURRENT_TIMESTAMP"),
+        Expr::Raw(sql) if matches!(sql.as_str(), "CURRENT_DATE" | "CURRENT_TIME") => {
+            Value::Text(sql)
+        }
+        Expr::Raw(sql) if is_blob_literal(sql) => Value::Blob,
+        _ => return None,
+    };
+
+    let text_number = |s: &str| s.trim().parse::<f64>().ok().filter(|n| n.is_finite());
+    Some(match (sqlite_type, value) {
+        (SqliteType::Text | SqliteType::Any, _) => true,
+        (SqliteType::Blob, value) => matches!(value, Value::Blob),
+        (_, Value::Blob) => false,
+        (SqliteType::Integer, Value::Integer) => true,
+        (SqliteType::Integer, Value::Real(n)) => n.fract() == 0.0,
+        (SqliteType::Integer, Value::Text(s)) => text_number(s).is_some_and(|n| n.fract() == 0.0),
+        (SqliteType::Real | SqliteType::Numeric, Value::Integer | Value::Real(_)) => true,
+        (SqliteType::Real | SqliteType::Numeric, Value::Text(s)) => text_number(s).is_some(),
+    })
+}
+
+fn is_blob_literal(sql: &str) -> bool {
+    let sql = sql.trim();
+    (sql.starts_with("x'") || sql.starts_with("X'")) && sql.ends_with('\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, Ident, PgType, QualifiedName, Table};
+
+    fn column(name: &str, sqlite_type: SqliteType, default: Option<Expr>) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type: PgType::Text,
+            sqlite_type: Some(sqlite_type),
+            not_null: false,
+            default,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn model(columns: Vec<Column>) -> SchemaModel {
+        SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("t")),
+                columns,
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_strict_numeric_from_str() {
+        assert_eq!("ANY".parse(), Ok(StrictNumeric::Any));
+        assert!("numeric".parse::<StrictNumeric>().is_err());
+    }
+
+    #[test]
+    fn test_numeric_columns_replaced() {
+        for (numeric, expected) in [
+            (StrictNumeric::Real, SqliteType::Real),
+            (StrictNumeric::Text, SqliteType::Text),
+            (StrictNumeric::Any, SqliteType::Any),
+        ] {
+            let mut m = model(vec![column("price", SqliteType::Numeric, None)]);
+            let mut w = Vec::new();
+            apply_strict_tables(&mut m, numeric, &mut w);
+            assert_eq!(m.tables[0].columns[0].sqlite_type, Some(expected));
+            assert_eq!(w[0].code, warning::STRICT_TYPE_CHANGED);
+        }
+    }
+
+    #[test]
+    fn test_defaults_validated() {
+        let text = |s: &str| Some(Expr::StringLiteral(s.to_string()));
+        let mut m = model(vec![
+            column("a", SqliteType::Integer, text("12")),
+            column("b", SqliteType::Integer, text("abc")),
+            column("c", SqliteType::Integer, Some(Expr::FloatLiteral(1.5))),
+            column("d", SqliteType::Real, text(" 2.5")),
+            column("e", SqliteType::Blob, text("x")),
+            column("f", SqliteType::Blob, Some(Expr::Raw("x'00'".to_string()))),
+            column("g", SqliteType::Integer, Some(Expr::CurrentTimestamp)),
+            column("h", SqliteType::Text, Some(Expr::IntegerLiteral(1))),
+            column("i", SqliteType::Integer, Some(Expr::func("random", vec![]))),
+        ]);
+        let mut w = Vec::new();
+        apply_strict_tables(&mut m, StrictNumeric::Real, &mut w);
+        let kept: Vec<bool> = m.tables[0]
+            .columns
+            .iter()
+            .map(|c| c.default.is_some())
+            .collect();
+        assert_eq!(
+            kept,
+            [true, false, false, true, false, true, false, true, true]
+        );
+        assert!(w.iter().all(|w| w.code == warning::STRICT_DEFAULT_INVALID));
+        assert_eq!(w.len(), 4);
+    }
+}
@@ -1,28 +1,38 @@
 /// Topological sort for FK dependencies.
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::VecDeque;
 
 use crate::diagnostics::{Severity, Warning, warning};
-use crate::ir::{Table, TableConstraint};
+use crate::ir::{QualifiedName, Table, TableConstraint};
+use crate::transform::catalog::{self, Catalog, Lookup};
 
 /// Sort tables in dependency order (tables referenced by FKs come first).
 /// Falls back to alphabetical order if cycles are detected.
 pub fn topological_sort(tables: &mut Vec<Table>, warnings: &mut Vec<Warning>) {
-    let name_to_idx: HashMap<String, usize> = tables
-        .iter()
-        .enumerate()
-        .map(|(i, t)| (t.name.name.normalized.clone(), i))
-        .collect();
+    // Cataloged here rather than once after parsing: tables have been renamed since
+    let catalog = Catalog::from_tables(tables);
 
     // Build adjacency list: edges from table → tables it depends on
     let mut in_degree: Vec<usize> = vec![0; tables.len()];
     let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); tables.len()];
 
     for (i, table) in tables.iter().enumerate() {
-        let deps = get_fk_dependencies(table);
-        for dep_name in deps {
-            if let Some(&dep_idx) = name_to_idx.get(&dep_name)
-                && dep_idx != i
-            {
+        let mut deps: Vec<usize> = Vec::new();
+        for dep_name in get_fk_dependencies(table) {
+            match catalog.table_index(tables, dep_name) {
+                Lookup::Found(dep_idx) => deps.push(dep_idx),
+                Lookup::Ambiguous(candidates) => warnings.push(catalog::ambiguous_reference(
+                    &dep_name.name.normalized,
+                    &candidates,
+                    "foreign key ignored for table ordering",
+                    &table.name.name.normalized,
+                )),
+                Lookup::Missing => {}
+            }
+        }
+        deps.sort_unstable();
+        deps.dedup();
+        for dep_idx in deps {
+            if dep_idx != i {
                 // dep must come before i
                 dependents[dep_idx].push(i);
                 in_degree[i] += 1;
@@ -81,6 +91,8 @@ pub fn topological_sort(tables: &mut Vec<Table>, warnings: &mut Vec<Warning>) {
                     name: crate::ir::QualifiedName::new(crate::ir::Ident::new("")),
                     columns: vec![],
                     constraints: vec![],
+                    without_rowid: false,
+                    note: None,
                 },
             ));
         }
@@ -105,19 +117,19 @@ pub fn topological_sort(tables: &mut Vec<Table>, warnings: &mut Vec<Warning>) {
     }
 }
 
-/// Extract FK dependency table names from a table.
-fn get_fk_dependencies(table: &Table) -> HashSet<String> {
-    let mut deps = HashSet::new();
+/// Extract the tables a table's foreign keys reference.
+fn get_fk_dependencies(table: &Table) -> Vec<&QualifiedName> {
+    let mut deps = Vec::new();
 
     for constraint in &table.constraints {
         if let TableConstraint::ForeignKey { ref_table, .. } = constraint {
-            deps.insert(ref_table.name.normalized.clone());
+            deps.push(ref_table);
         }
     }
 
     for col in &table.columns {
         if let Some(fk) = &col.references {
-            deps.insert(fk.table.name.normalized.clone());
+            deps.push(&fk.table);
         }
     }
 
@@ -147,6 +159,8 @@ mod tests {
             name: QualifiedName::new(Ident::new(name)),
             columns: vec![],
             constraints,
+            without_rowid: false,
+            note: None,
         }
     }
 
@@ -1,9 +1,187 @@
 /// PostgreSQL type → SQLite type affinity mapping.
+use std::collections::HashMap;
+
 use crate::diagnostics::warning::{self, Severity, Warning};
 use crate::ir::types::{PgType, SqliteType};
 
+/// Storage representation for `uuid` columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UuidMode {
+    /// Canonical 36-character text form (default).
+    #[default]
+    Text,
+    /// Raw 16-byte BLOB with a `length(col) = 16` CHECK.
+    Blob,
+}
+
+impl std::str::FromStr for UuidMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(UuidMode::Text),
+            "blob" => Ok(UuidMode::Blob),
+            other => Err(format!(
+                "unknown uuid mode '{other}' (expected text or blob)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for UuidMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UuidMode::Text => write!(f, "text"),
+            UuidMode::Blob => write!(f, "blob"),
+        }
+    }
+}
+
+/// Storage representation for exact numeric (`numeric`/`decimal`) and `money` columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumericMode {
+    /// NUMERIC affinity for numeric, TEXT for money (default).
+    #[default]
+    Native,
+    /// INTEGER minor units, scaled by 10^scale (e.g. cents).
+    Integer,
+    /// Exact decimal TEXT with a format CHECK.
+    Text,
+}
+
+impl std::str::FromStr for NumericMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "native" => Ok(NumericMode::Native),
+            "integer" => Ok(NumericMode::Integer),
+            "text" => Ok(NumericMode::Text),
+            other => Err(format!(
+                "unknown numeric mode '{other}' (expected native, integer or text)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for NumericMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumericMode::Native => write!(f, "native"),
+            NumericMode::Integer => write!(f, "integer"),
+            NumericMode::Text => write!(f, "text"),
+        }
+    }
+}
+
+/// Storage representation for `interval` columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntervalMode {
+    /// Interval text exactly as written in the dump (default).
+    #[default]
+    Text,
+    /// Normalized ISO-8601 duration text, e.g. `P1DT2H`.
+    Iso8601,
+    /// INTEGER seconds.
+    Seconds,
+    /// INTEGER milliseconds.
+    Milliseconds,
+}
+
+impl std::str::FromStr for IntervalMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(IntervalMode::Text),
+            "iso8601" => Ok(IntervalMode::Iso8601),
+            "seconds" => Ok(IntervalMode::Seconds),
+            "milliseconds" => Ok(IntervalMode::Milliseconds),
+            other => Err(format!(
+                "unknown interval mode '{other}' (expected text, iso8601, seconds or milliseconds)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for IntervalMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntervalMode::Text => write!(f, "text"),
+            IntervalMode::Iso8601 => write!(f, "iso8601"),
+            IntervalMode::Seconds => write!(f, "seconds"),
+            IntervalMode::Milliseconds => write!(f, "milliseconds"),
+        }
+    }
+}
+
+/// Options that select between alternative storage strategies for PG types.
+#[derive(Debug, Clone, Default)]
+pub struct TypeMapOptions {
+    /// How `uuid` columns are stored.
+    pub uuid_mode: UuidMode,
+    /// How exact numeric and money columns are stored.
+    pub numeric_mode: NumericMode,
+    /// Per-column numeric mode overrides, keyed by `table.column`.
+    pub numeric_columns: HashMap<String, NumericMode>,
+    /// How `interval` columns are stored.
+    pub interval_mode: IntervalMode,
+}
+
+impl TypeMapOptions {
+    /// Requested numeric mode for a `table.column` object, honouring per-column overrides.
+    ///
+    /// Overrides given in lowercase also match quoted mixed-case names.
+    pub fn requested_numeric_mode(&self, object: &str) -> NumericMode {
+        self.numeric_columns
+            .get(object)
+            .or_else(|| self.numeric_columns.get(&object.to_lowercase()))
+            .copied()
+            .unwrap_or(self.numeric_mode)
+    }
+
+    /// Effective numeric mode for a column.
+    ///
+    /// Scaled INTEGER needs a known scale, so unconstrained `numeric` falls back
+    /// to decimal TEXT.
+    /// Non-exact types always report `Native`.
+    pub fn numeric_mode_for(&self, object: &str, pg_type: &PgType) -> NumericMode {
+        if !matches!(pg_type, PgType::Numeric { .. } | PgType::Money) {
+            return NumericMode::Native;
+        }
+        match self.requested_numeric_mode(object) {
+            NumericMode::Integer if numeric_scale(pg_type).is_none() => NumericMode::Text,
+            mode => mode,
+        }
+    }
+}
+
+/// Largest precision whose values always fit in a 64-bit INTEGER.
+pub const MAX_INTEGER_PRECISION: u32 = 18;
+
+/// Number of digits after the decimal point for exact numeric types, if known.
+pub fn numeric_scale(pg_type: &PgType) -> Option<u32> {
+    match pg_type {
+        PgType::Numeric {
+            scale: Some(scale), ..
+        } => Some(*scale),
+        // numeric(p) has scale 0
+        PgType::Numeric {
+            precision: Some(_),
+            scale: None,
+        } => Some(0),
+        PgType::Money => Some(2),
+        _ => None,
+    }
+}
+
 /// Map a PostgreSQL type to a SQLite type affinity, emitting warnings for lossy conversions.
-pub fn map_type(pg_type: &PgType, object: &str, warnings: &mut Vec<Warning>) -> SqliteType {
+pub fn map_type(
+    pg_type: &PgType,
+    opts: &TypeMapOptions,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> SqliteType {
     match pg_type {
         // Integer types
         PgType::SmallInt => {
@@ -27,17 +205,34 @@ pub fn map_type(pg_type: &PgType, object: &str, warnings: &mut Vec<Warning>) ->
         }
 
         // Numeric types
-        PgType::Numeric { .. } => {
-            warnings.push(
-                Warning::new(
-                    warning::NUMERIC_PRECISION_LOSS,
-                    Severity::Lossy,
-                    "numeric precision/scale not enforced in SQLite",
-                )
-                .with_object(object),
-            );
-            SqliteType::Numeric
-        }
+        PgType::Numeric { precision, scale } => match opts.numeric_mode_for(object, pg_type) {
+            NumericMode::Native => {
+                // Integral values up to 18 digits are stored exactly as INTEGER
+                let exact = scale.unwrap_or(0) == 0
+                    && precision.is_some_and(|p| p <= MAX_INTEGER_PRECISION);
+                if exact {
+                    warnings.push(
+                        Warning::new(
+                            warning::TYPE_WIDTH_IGNORED,
+                            Severity::Info,
+                            "numeric precision not enforced in SQLite",
+                        )
+                        .with_object(object),
+                    );
+                } else {
+                    warnings.push(
+                        Warning::new(
+                            warning::NUMERIC_PRECISION_LOSS,
+                            Severity::Lossy,
+                            "numeric precision/scale not enforced in SQLite",
+                        )
+                        .with_object(object),
+                    );
+                }
+                SqliteType::Numeric
+            }
+            mode => map_exact_numeric(pg_type, opts, mode, object, warnings),
+        },
         PgType::Real | PgType::DoublePrecision => SqliteType::Real,
 
         // Text types
@@ -136,22 +331,57 @@ pub fn map_type(pg_type: &PgType, object: &str, warnings: &mut Vec<Warning>) ->
             }
             SqliteType::Text
         }
-        PgType::Interval => {
+        PgType::Interval => match opts.interval_mode {
+            IntervalMode::Text => {
+                warnings.push(
+                    Warning::new(
+                        warning::INTERVAL_AS_TEXT,
+                        Severity::Lossy,
+                        "interval stored as TEXT in SQLite",
+                    )
+                    .with_object(object),
+                );
+                SqliteType::Text
+            }
+            IntervalMode::Iso8601 => {
+                warnings.push(
+                    Warning::new(
+                        warning::INTERVAL_AS_ISO8601,
+                        Severity::Info,
+                        "interval stored as ISO-8601 duration TEXT",
+                    )
+                    .with_object(object),
+                );
+                SqliteType::Text
+            }
+            mode => {
+                warnings.push(
+                    Warning::new(
+                        warning::INTERVAL_AS_INTEGER,
+                        Severity::Lossy,
+                        format!("interval stored as INTEGER {mode}; months count as 30 days"),
+                    )
+                    .with_object(object),
+                );
+                SqliteType::Integer
+            }
+        },
+
+        // Binary
+        PgType::Bytea => SqliteType::Blob,
+
+        // UUID
+        PgType::Uuid if opts.uuid_mode == UuidMode::Blob => {
             warnings.push(
                 Warning::new(
-                    warning::INTERVAL_AS_TEXT,
-                    Severity::Lossy,
-                    "interval stored as TEXT in SQLite",
+                    warning::UUID_AS_BLOB,
+                    Severity::Info,
+                    "uuid stored as 16-byte BLOB in SQLite",
                 )
                 .with_object(object),
             );
-            SqliteType::Text
+            SqliteType::Blob
         }
-
-        // Binary
-        PgType::Bytea => SqliteType::Blob,
-
-        // UUID
         PgType::Uuid => {
             warnings.push(
                 Warning::new(
@@ -211,6 +441,15 @@ pub fn map_type(pg_type: &PgType, object: &str, warnings: &mut Vec<Warning>) ->
         }
 
         // Monetary
+        PgType::Money if opts.requested_numeric_mode(object) != NumericMode::Native => {
+            map_exact_numeric(
+                pg_type,
+                opts,
+                opts.numeric_mode_for(object, pg_type),
+                object,
+                warnings,
+            )
+        }
         PgType::Money => {
             warnings.push(
                 Warning::new(
@@ -267,6 +506,52 @@ pub fn map_type(pg_type: &PgType, object: &str, warnings: &mut Vec<Warning>) ->
             SqliteType::Text
         }
 
+        // Extension types
+        PgType::Citext => {
+            warnings.push(
+                Warning::new(
+                    warning::CITEXT_AS_NOCASE,
+                    Severity::Lossy,
+                    "citext stored as TEXT COLLATE NOCASE (ASCII-only case folding)",
+                )
+                .with_object(object),
+            );
+            SqliteType::Text
+        }
+        PgType::Hstore => {
+            warnings.push(
+                Warning::new(
+                    warning::HSTORE_AS_JSON,
+                    Severity::Info,
+                    "hstore stored as JSON object TEXT",
+                )
+                .with_object(object),
+            );
+            SqliteType::Text
+        }
+        PgType::Ltree => {
+            warnings.push(
+                Warning::new(
+                    warning::LTREE_AS_TEXT,
+                    Severity::Info,
+                    "ltree stored as TEXT with a label path CHECK",
+                )
+                .with_object(object),
+            );
+            SqliteType::Text
+        }
+        PgType::Geometry { .. } => {
+            warnings.push(
+                Warning::new(
+                    warning::GEOMETRY_AS_WKB,
+                    Severity::Info,
+                    format!("{pg_type} stored as WKB BLOB"),
+                )
+                .with_object(object),
+            );
+            SqliteType::Blob
+        }
+
         // Enum → TEXT
         PgType::Enum { .. } => {
             warnings.push(
@@ -280,6 +565,19 @@ pub fn map_type(pg_type: &PgType, object: &str, warnings: &mut Vec<Warning>) ->
             SqliteType::Text
         }
 
+        // Composite → JSON object TEXT (expanded into columns earlier in columns mode)
+        PgType::Composite { name } => {
+            warnings.push(
+                Warning::new(
+                    warning::COMPOSITE_AS_JSON,
+                    Severity::Info,
+                    format!("composite type '{name}' stored as a JSON object in TEXT"),
+                )
+                .with_object(object),
+            );
+            SqliteType::Text
+        }
+
         // Domain → flatten to base type (handled in planner, but map here as fallback)
         PgType::Domain { name } => {
             warnings.push(
@@ -321,6 +619,54 @@ pub fn map_type(pg_type: &PgType, object: &str, warnings: &mut Vec<Warning>) ->
     }
 }
 
+/// Map numeric/money to scaled INTEGER or decimal TEXT storage.
+fn map_exact_numeric(
+    pg_type: &PgType,
+    opts: &TypeMapOptions,
+    mode: NumericMode,
+    object: &str,
+    warnings: &mut Vec<Warning>,
+) -> SqliteType {
+    match (mode, numeric_scale(pg_type)) {
+        (NumericMode::Integer, Some(scale)) => {
+            warnings.push(
+                Warning::new(
+                    warning::NUMERIC_AS_INTEGER,
+                    Severity::Info,
+                    format!("{pg_type} stored as INTEGER minor units (x 10^{scale})"),
+                )
+                .with_object(object),
+            );
+            let precision = match pg_type {
+                PgType::Numeric { precision, .. } => *precision,
+                _ => None,
+            };
+            if precision.is_some_and(|p| p > MAX_INTEGER_PRECISION) {
+                warnings.push(
+                    Warning::new(
+                        warning::NUMERIC_PRECISION_LOSS,
+                        Severity::Lossy,
+                        format!("{pg_type} may overflow a 64-bit INTEGER"),
+                    )
+                    .with_object(object),
+                );
+            }
+            SqliteType::Integer
+        }
+        _ => {
+            let message = if opts.requested_numeric_mode(object) == NumericMode::Integer {
+                format!("{pg_type} has no fixed scale; stored as exact decimal TEXT")
+            } else {
+                format!("{pg_type} stored as exact decimal TEXT")
+            };
+            warnings.push(
+                Warning::new(warning::NUMERIC_AS_TEXT, Severity::Info, message).with_object(object),
+            );
+            SqliteType::Text
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,19 +675,24 @@ mod tests {
     fn test_integer_types() {
         let mut w = Vec::new();
         assert_eq!(
-            map_type(&PgType::Integer, "t.id", &mut w),
+            map_type(&PgType::Integer, &TypeMapOptions::default(), "t.id", &mut w),
             SqliteType::Integer
         );
         assert!(w.is_empty());
 
         assert_eq!(
-            map_type(&PgType::BigInt, "t.id", &mut w),
+            map_type(&PgType::BigInt, &TypeMapOptions::default(), "t.id", &mut w),
             SqliteType::Integer
         );
         assert!(w.is_empty());
 
         assert_eq!(
-            map_type(&PgType::SmallInt, "t.id", &mut w),
+            map_type(
+                &PgType::SmallInt,
+                &TypeMapOptions::default(),
+                "t.id",
+                &mut w
+            ),
             SqliteType::Integer
         );
         assert_eq!(w.len(), 1);
@@ -355,15 +706,94 @@ mod tests {
             precision: Some(10),
             scale: Some(2),
         };
-        assert_eq!(map_type(&t, "t.price", &mut w), SqliteType::Numeric);
+        assert_eq!(
+            map_type(&t, &TypeMapOptions::default(), "t.price", &mut w),
+            SqliteType::Numeric
+        );
         assert_eq!(w[0].code, warning::NUMERIC_PRECISION_LOSS);
     }
 
+    #[test]
+    fn test_numeric_integral_is_not_lossy() {
+        let mut w = Vec::new();
+        let t = PgType::Numeric {
+            precision: Some(12),
+            scale: Some(0),
+        };
+        let opts = TypeMapOptions::default();
+        assert_eq!(map_type(&t, &opts, "t.qty", &mut w), SqliteType::Numeric);
+        assert_eq!(w[0].code, warning::TYPE_WIDTH_IGNORED);
+    }
+
+    #[test]
+    fn test_numeric_integer_mode() {
+        let mut w = Vec::new();
+        let t = PgType::Numeric {
+            precision: Some(10),
+            scale: Some(2),
+        };
+        let opts = TypeMapOptions {
+            numeric_mode: NumericMode::Integer,
+            ..Default::default()
+        };
+        assert_eq!(map_type(&t, &opts, "t.price", &mut w), SqliteType::Integer);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].code, warning::NUMERIC_AS_INTEGER);
+
+        // Precision beyond 18 digits can overflow
+        let wide = PgType::Numeric {
+            precision: Some(30),
+            scale: Some(4),
+        };
+        map_type(&wide, &opts, "t.big", &mut w);
+        assert!(w.iter().any(|w| w.code == warning::NUMERIC_PRECISION_LOSS));
+    }
+
+    #[test]
+    fn test_numeric_integer_mode_without_scale_falls_back_to_text() {
+        let mut w = Vec::new();
+        let t = PgType::Numeric {
+            precision: None,
+            scale: None,
+        };
+        let opts = TypeMapOptions {
+            numeric_mode: NumericMode::Integer,
+            ..Default::default()
+        };
+        assert_eq!(map_type(&t, &opts, "t.amount", &mut w), SqliteType::Text);
+        assert_eq!(w[0].code, warning::NUMERIC_AS_TEXT);
+    }
+
+    #[test]
+    fn test_money_per_column_override() {
+        let mut w = Vec::new();
+        let mut opts = TypeMapOptions::default();
+        opts.numeric_columns
+            .insert("invoices.total".to_string(), NumericMode::Integer);
+        assert_eq!(
+            map_type(&PgType::Money, &opts, "invoices.total", &mut w),
+            SqliteType::Integer
+        );
+        assert_eq!(w[0].code, warning::NUMERIC_AS_INTEGER);
+
+        w.clear();
+        assert_eq!(
+            map_type(&PgType::Money, &opts, "invoices.fee", &mut w),
+            SqliteType::Text
+        );
+        assert_eq!(w[0].code, warning::MONEY_AS_TEXT);
+    }
+
     #[test]
     fn test_boolean_type() {
         let mut w = Vec::new();
         assert_eq!(
-            map_type(&PgType::Boolean, "t.active", &mut w),
+            map_type(
+                &PgType::Boolean,
+                &TypeMapOptions::default(),
+                "t.active",
+                &mut w
+            ),
             SqliteType::Integer
         );
         assert_eq!(w[0].code, warning::BOOLEAN_AS_INTEGER);
@@ -373,21 +803,45 @@ mod tests {
     fn test_timestamp_with_tz() {
         let mut w = Vec::new();
         let t = PgType::Timestamp { with_tz: true };
-        assert_eq!(map_type(&t, "t.ts", &mut w), SqliteType::Text);
+        assert_eq!(
+            map_type(&t, &TypeMapOptions::default(), "t.ts", &mut w),
+            SqliteType::Text
+        );
         assert!(w.iter().any(|w| w.code == warning::TIMEZONE_LOSS));
     }
 
     #[test]
     fn test_uuid_type() {
         let mut w = Vec::new();
-        assert_eq!(map_type(&PgType::Uuid, "t.id", &mut w), SqliteType::Text);
+        assert_eq!(
+            map_type(&PgType::Uuid, &TypeMapOptions::default(), "t.id", &mut w),
+            SqliteType::Text
+        );
         assert_eq!(w[0].code, warning::UUID_AS_TEXT);
     }
 
+    #[test]
+    fn test_uuid_blob_mode() {
+        let mut w = Vec::new();
+        let opts = TypeMapOptions {
+            uuid_mode: UuidMode::Blob,
+            ..Default::default()
+        };
+        assert_eq!(
+            map_type(&PgType::Uuid, &opts, "t.id", &mut w),
+            SqliteType::Blob
+        );
+        assert_eq!(w[0].code, warning::UUID_AS_BLOB);
+        assert_eq!(w[0].severity, Severity::Info);
+    }
+
     #[test]
     fn test_bytea_type() {
         let mut w = Vec::new();
-        assert_eq!(map_type(&PgType::Bytea, "t.data", &mut w), SqliteType::Blob);
+        assert_eq!(
+            map_type(&PgType::Bytea, &TypeMapOptions::default(), "t.data", &mut w),
+            SqliteType::Blob
+        );
         assert!(w.is_empty());
     }
 
@@ -397,7 +851,10 @@ mod tests {
         let t = PgType::Array {
             element: std::boxed::Box::new(PgType::Integer),
         };
-        assert_eq!(map_type(&t, "t.tags", &mut w), SqliteType::Text);
+        assert_eq!(
+            map_type(&t, &TypeMapOptions::default(), "t.tags", &mut w),
+            SqliteType::Text
+        );
         assert_eq!(w[0].code, warning::ARRAY_LOSSY);
     }
 
@@ -407,18 +864,29 @@ mod tests {
         let t = PgType::Enum {
             name: "mood".to_string(),
         };
-        assert_eq!(map_type(&t, "t.mood", &mut w), SqliteType::Text);
+        assert_eq!(
+            map_type(&t, &TypeMapOptions::default(), "t.mood", &mut w),
+            SqliteType::Text
+        );
         assert_eq!(w[0].code, warning::ENUM_AS_TEXT);
     }
 
     #[test]
     fn test_text_types() {
         let mut w = Vec::new();
-        assert_eq!(map_type(&PgType::Text, "t.name", &mut w), SqliteType::Text);
+        assert_eq!(
+            map_type(&PgType::Text, &TypeMapOptions::default(), "t.name", &mut w),
+            SqliteType::Text
+        );
         assert!(w.is_empty());
 
         assert_eq!(
-            map_type(&PgType::Varchar { length: Some(255) }, "t.name", &mut w),
+            map_type(
+                &PgType::Varchar { length: Some(255) },
+                &TypeMapOptions::default(),
+                "t.name",
+                &mut w
+            ),
             SqliteType::Text
         );
         assert_eq!(w[0].code, warning::VARCHAR_LENGTH_IGNORED);
@@ -427,10 +895,18 @@ mod tests {
     #[test]
     fn test_real_types() {
         let mut w = Vec::new();
-        assert_eq!(map_type(&PgType::Real, "t.val", &mut w), SqliteType::Real);
+        assert_eq!(
+            map_type(&PgType::Real, &TypeMapOptions::default(), "t.val", &mut w),
+            SqliteType::Real
+        );
         assert!(w.is_empty());
         assert_eq!(
-            map_type(&PgType::DoublePrecision, "t.val", &mut w),
+            map_type(
+                &PgType::DoublePrecision,
+                &TypeMapOptions::default(),
+                "t.val",
+                &mut w
+            ),
             SqliteType::Real
         );
         assert!(w.is_empty());
@@ -0,0 +1,379 @@
+/// UUID storage strategy: BLOB columns, length CHECKs and v4 generation defaults.
+use std::collections::{HashMap, HashSet};
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Expr, PgType, SchemaModel, TableConstraint};
+use crate::transform::type_map::UuidMode;
+
+/// PG functions that generate a random (version 4) UUID.
+const UUID_GENERATORS: &[&str] = &["gen_random_uuid", "uuid_generate_v4"];
+
+/// Check whether a function name is a PG v4 UUID generator.
+pub fn is_uuid_generator(name: &str) -> bool {
+    UUID_GENERATORS.contains(&name)
+}
+
+/// Build a pure SQLite expression producing a random v4 UUID.
+///
+/// The version nibble is fixed to `4` and the variant nibble is drawn from `89ab`.
+/// In BLOB mode the hex digits are decoded with `unhex()` (SQLite 3.41+).
+pub fn uuid_v4_expr(mode: UuidMode) -> Expr {
+    let rand_hex = |bytes: i64| {
+        Expr::func(
+            "hex",
+            vec![Expr::func("randomblob", vec![Expr::IntegerLiteral(bytes)])],
+        )
+    };
+    // Three random hex digits (one 2-byte group with its first digit dropped)
+    let rand_hex3 = || Expr::func("substr", vec![rand_hex(2), Expr::IntegerLiteral(2)]);
+    let variant = Expr::func(
+        "substr",
+        vec![
+            Expr::StringLiteral("89AB".to_string()),
+            Expr::binary(
+                Expr::IntegerLiteral(1),
+                "+",
+                Expr::Nested(Box::new(Expr::binary(
+                    Expr::func("abs", vec![Expr::func("random", vec![])]),
+                    "%",
+                    Expr::IntegerLiteral(4),
+                ))),
+            ),
+            Expr::IntegerLiteral(1),
+        ],
+    );
+    let dash = || Expr::StringLiteral("-".to_string());
+
+    let parts = match mode {
+        UuidMode::Text => vec![
+            rand_hex(4),
+            dash(),
+            rand_hex(2),
+            dash(),
+            Expr::StringLiteral("4".to_string()),
+            rand_hex3(),
+            dash(),
+            variant,
+            rand_hex3(),
+            dash(),
+            rand_hex(6),
+        ],
+        UuidMode::Blob => vec![
+            rand_hex(6),
+            Expr::StringLiteral("4".to_string()),
+            rand_hex3(),
+            variant,
+            rand_hex3(),
+            rand_hex(6),
+        ],
+    };
+
+    let concatenated = parts
+        .into_iter()
+        .reduce(|left, right| Expr::binary(left, "||", right))
+        .expect("uuid expression has parts");
+
+    match mode {
+        UuidMode::Text => Expr::func("lower", vec![concatenated]),
+        UuidMode::Blob => Expr::func("unhex", vec![concatenated]),
+    }
+}
+
+/// Apply the UUID storage mode to every `uuid` column in the model.
+///
+/// Text mode needs no rewriting (generator defaults are handled by `expr_map`).
+/// Blob mode rewrites generator defaults to the BLOB form, converts uuid text
+/// literals in defaults, CHECKs and partial index predicates to `X'…'` BLOB
+/// literals, and adds a `length(col) = 16` CHECK. FK columns referencing uuid
+/// keys are uuid-typed themselves, so they pick up the same representation.
+pub fn apply_uuid_mode(model: &mut SchemaModel, mode: UuidMode, warnings: &mut Vec<Warning>) {
+    if mode == UuidMode::Text {
+        return;
+    }
+
+    let mut uuid_by_table: HashMap<String, HashSet<String>> = HashMap::new();
+    for table in &mut model.tables {
+        let uuid_columns: HashSet<String> = table
+            .columns
+            .iter()
+            .filter(|c| c.pg_type == PgType::Uuid)
+            .map(|c| c.name.normalized.clone())
+            .collect();
+        if uuid_columns.is_empty() {
+            continue;
+        }
+
+        for col in &mut table.columns {
+            let obj = format!("{}.{}", table.name.name.normalized, col.name.normalized);
+            if let Some(check) = &mut col.check {
+                convert_compared_literals(check, &uuid_columns);
+            }
+            if col.pg_type != PgType::Uuid {
+                continue;
+            }
+
+            match &col.default {
+                Some(Expr::FunctionCall { name, .. }) if is_uuid_generator(name) => {
+                    let name = name.clone();
+                    col.default = Some(Expr::native(uuid_v4_expr(mode)));
+                    warnings.push(
+                        Warning::new(
+                            warning::UUID_DEFAULT_EMULATED,
+                            Severity::Info,
+                            format!("{name}() default emulated with randomblob(); requires unhex() (SQLite 3.41+)"),
+                        )
+                        .with_object(&obj),
+                    );
+                }
+                Some(default) => {
+                    if let Some(text) = uuid_text_literal(default) {
+                        let blob = blob_literal(text);
+                        if blob.is_none() {
+                            warnings.push(
+                                Warning::new(
+                                    warning::DEFAULT_UNSUPPORTED,
+                                    Severity::Unsupported,
+                                    format!("invalid uuid default '{text}' dropped"),
+                                )
+                                .with_object(&obj),
+                            );
+                        }
+                        col.default = blob;
+                    }
+                }
+                None => {}
+            }
+
+            let length_check = Expr::binary(
+                Expr::func("length", vec![Expr::ColumnRef(col.name.normalized.clone())]),
+                "=",
+                Expr::IntegerLiteral(16),
+            );
+            col.add_check(Expr::native(length_check));
+        }
+
+        for constraint in &mut table.constraints {
+            if let TableConstraint::Check { expr, .. } = constraint {
+                convert_compared_literals(expr, &uuid_columns);
+            }
+        }
+        uuid_by_table.insert(table.name.name.normalized.clone(), uuid_columns);
+    }
+
+    for index in &mut model.indexes {
+        if let (Some(uuid_columns), Some(where_clause)) = (
+            uuid_by_table.get(&index.table.name.normalized),
+            &mut index.where_clause,
+        ) {
+            convert_compared_literals(where_clause, uuid_columns);
+        }
+    }
+}
+
+/// The text of a uuid literal: `'…'` or `'…'::uuid`.
+fn uuid_text_literal(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::StringLiteral(text) => Some(text),
+        Expr::Cast { expr, type_name } if type_name.eq_ignore_ascii_case("uuid") => {
+            uuid_text_literal(expr)
+        }
+        Expr::Nested(inner) => uuid_text_literal(inner),
+        _ => None,
+    }
+}
+
+/// Convert uuid text in any of PG's input forms (hyphenated, braced or plain hex)
+/// to a 16-byte `X'…'` BLOB literal; `None` if it isn't a valid uuid.
+fn blob_literal(text: &str) -> Option<Expr> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix('{')
+        .and_then(|t| t.strip_suffix('}'))
+        .unwrap_or(text);
+    let hex: String = text.chars().filter(|&c| c != '-').collect();
+    (hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| Expr::Raw(format!("X'{}'", hex.to_ascii_uppercase())))
+}
+
+/// Replace uuid literals compared with uuid columns (`=`, `<>`, `IN (…)`, …) by BLOB literals.
+///
+/// Literals that aren't valid uuids are left alone; PG rejects them anyway.
+fn convert_compared_literals(expr: &mut Expr, uuid_columns: &HashSet<String>) {
+    let is_uuid_column =
+        |e: &Expr| matches!(e, Expr::ColumnRef(name) if uuid_columns.contains(name));
+    let convert = |e: &mut Expr| {
+        if let Some(blob) = uuid_text_literal(e).and_then(blob_literal) {
+            *e = blob;
+        }
+    };
+    expr.walk_mut(&mut |e| match e {
+        Expr::BinaryOp { left, op, right }
+            if matches!(
+                op.as_str(),
+                "=" | "<>"
+                    | "!="
+                    | "<"
+                    | "<="
+                    | ">"
+                    | ">="
+                    | "IS DISTINCT FROM"
+                    | "IS NOT DISTINCT FROM"
+            ) =>
+        {
+            if is_uuid_column(left) {
+                convert(right);
+            } else if is_uuid_column(right) {
+                convert(left);
+            }
+        }
+        Expr::InList { expr, list, .. } if is_uuid_column(expr) => {
+            for item in list {
+                convert(item);
+            }
+        }
+        _ => {}
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Column, Ident, QualifiedName, Table};
+
+    fn make_uuid_column(name: &str, default: Option<Expr>) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type: PgType::Uuid,
+            sqlite_type: None,
+            not_null: false,
+            default,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    #[test]
+    fn test_text_uuid_expr_shape() {
+        let sql = uuid_v4_expr(UuidMode::Text).to_sql();
+        assert!(sql.starts_with("lower(hex(randomblob(4)) || '-'"));
+        assert!(sql.contains("'-' || '4' || substr(hex(randomblob(2)), 2)"));
+        assert!(sql.ends_with("'-' || hex(randomblob(6)))"));
+    }
+
+    #[test]
+    fn test_blob_uuid_expr_shape() {
+        let sql = uuid_v4_expr(UuidMode::Blob).to_sql();
+        assert!(sql.starts_with("unhex(hex(randomblob(6)) || '4'"));
+        assert!(!sql.contains("'-'"));
+    }
+
+    #[test]
+    fn test_blob_mode_rewrites_default_and_adds_check() {
+        let mut model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("users")),
+                columns: vec![make_uuid_column(
+                    "id",
+                    Some(Expr::FunctionCall {
+                        name: "gen_random_uuid".to_string(),
+                        args: vec![],
+                    }),
+                )],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        };
+        let mut w = Vec::new();
+        apply_uuid_mode(&mut model, UuidMode::Blob, &mut w);
+
+        let col = &model.tables[0].columns[0];
+        assert_eq!(
+            col.default,
+            Some(Expr::native(uuid_v4_expr(UuidMode::Blob)))
+        );
+        assert_eq!(col.check.as_ref().unwrap().to_sql(), "length(id) = 16");
+        assert!(w.iter().any(|w| w.code == warning::UUID_DEFAULT_EMULATED));
+    }
+
+    #[test]
+    fn test_blob_mode_converts_uuid_literals() {
+        let nil = "00000000-0000-0000-0000-000000000000";
+        let mut status = make_uuid_column("status", None);
+        status.check = Some(Expr::InList {
+            expr: Box::new(Expr::ColumnRef("status".to_string())),
+            list: vec![Expr::StringLiteral(
+                "{a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11}".to_string(),
+            )],
+            negated: false,
+        });
+        let mut model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("users")),
+                columns: vec![
+                    make_uuid_column(
+                        "id",
+                        Some(Expr::Cast {
+                            expr: Box::new(Expr::StringLiteral(nil.to_string())),
+                            type_name: "uuid".to_string(),
+                        }),
+                    ),
+                    make_uuid_column("bad", Some(Expr::StringLiteral("nope".to_string()))),
+                    status,
+                ],
+                constraints: vec![TableConstraint::Check {
+                    name: None,
+                    expr: Expr::binary(
+                        Expr::ColumnRef("id".to_string()),
+                        "<>",
+                        Expr::StringLiteral("A0EEBC999C0B4EF8BB6D6BB9BD380A11".to_string()),
+                    ),
+                }],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        };
+        let mut w = Vec::new();
+        apply_uuid_mode(&mut model, UuidMode::Blob, &mut w);
+
+        let table = &model.tables[0];
+        assert_eq!(
+            table.columns[0].default.as_ref().map(|d| d.to_sql()),
+            Some("X'00000000000000000000000000000000'".to_string())
+        );
+        assert_eq!(table.columns[1].default, None);
+        assert!(w.iter().any(|w| w.code == warning::DEFAULT_UNSUPPORTED));
+        assert_eq!(
+            table.columns[2].check.as_ref().unwrap().to_sql(),
+            "(status IN (X'A0EEBC999C0B4EF8BB6D6BB9BD380A11')) AND length(status) = 16"
+        );
+        let TableConstraint::Check { expr, .. } = &table.constraints[0] else {
+            panic!("expected CHECK");
+        };
+        assert_eq!(expr.to_sql(), "id <> X'A0EEBC999C0B4EF8BB6D6BB9BD380A11'");
+    }
+
+    #[test]
+    fn test_text_mode_is_noop() {
+        let mut model = SchemaModel {
+            tables: vec![Table {
+                name: QualifiedName::new(Ident::new("users")),
+                columns: vec![make_uuid_column("id", None)],
+                constraints: vec![],
+                without_rowid: false,
+                note: None,
+            }],
+            ..Default::default()
+        };
+        let mut w = Vec::new();
+        apply_uuid_mode(&mut model, UuidMode::Text, &mut w);
+        assert!(model.tables[0].columns[0].check.is_none());
+        assert!(w.is_empty());
+    }
+}
@@ -0,0 +1,351 @@
+/// WITHOUT ROWID tables: choose tables keyed by natural or composite primary keys.
+use std::collections::HashSet;
+
+use crate::diagnostics::warning::{self, Severity, Warning};
+use crate::ir::{Column, PgType, SchemaModel, SqliteType, Table, TableConstraint};
+
+/// Rows larger than about 1/20 of the default 4096-byte page are better
+/// stored in a rowid table, per the SQLite documentation.
+const ROW_SIZE_LIMIT: usize = 200;
+
+/// Which tables are rendered as `WITHOUT ROWID`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WithoutRowidMode {
+    /// Every table keeps its rowid (default).
+    #[default]
+    Off,
+    /// Tables whose primary key is composite or not a single integer column.
+    Auto,
+    /// Every table that satisfies the WITHOUT ROWID restrictions.
+    All,
+}
+
+impl std::str::FromStr for WithoutRowidMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" => Ok(WithoutRowidMode::Off),
+            "auto" => Ok(WithoutRowidMode::Auto),
+            "all" => Ok(WithoutRowidMode::All),
+            other => Err(format!(
+                "unknown without-rowid mode '{other}' (expected off, auto or all)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for WithoutRowidMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WithoutRowidMode::Off => write!(f, "off"),
+            WithoutRowidMode::Auto => write!(f, "auto"),
+            WithoutRowidMode::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Mark tables as `WITHOUT ROWID` according to `mode`, skipping tables that
+/// violate SQLite's restrictions and flagging rows likely too large for it.
+pub fn apply_without_rowid(
+    model: &mut SchemaModel,
+    mode: WithoutRowidMode,
+    warnings: &mut Vec<Warning>,
+) {
+    if mode == WithoutRowidMode::Off {
+        return;
+    }
+
+    // SERIAL keys are rewritten to plain INTEGER by the planner, which
+    // records each one it turns into a rowid alias.
+    let rowid_keys: HashSet<String> = warnings
+        .iter()
+        .filter(|w| w.code == warning::SERIAL_TO_ROWID)
+        .filter_map(|w| w.object.clone())
+        .collect();
+    // Unbounded columns of WITHOUT ROWID tables, reported together
+    let mut unbounded: Vec<String> = Vec::new();
+
+    for table in &mut model.tables {
+        let obj = table.name.name.normalized.clone();
+        let pk = primary_key(table);
+
+        if mode == WithoutRowidMode::Auto && !is_natural_key(table, &pk) {
+            continue;
+        }
+
+        let reason = if pk.is_empty() {
+            Some("no PRIMARY KEY")
+        } else if table.columns.iter().any(|c| c.autoincrement) {
+            Some("AUTOINCREMENT requires a rowid")
+        } else if pk.len() == 1 && rowid_keys.contains(&format!("{obj}.{}", pk[0])) {
+            Some("SERIAL keys are generated through the rowid")
+        } else if table
+            .columns
+            .iter()
+            .any(|c| matches!(c.pg_type, PgType::Geometry { .. }))
+        {
+            Some("spatial indexes on geometry columns require a rowid")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            warnings.push(
+                Warning::new(
+                    warning::WITHOUT_ROWID_SKIPPED,
+                    Severity::Info,
+                    format!("table cannot be WITHOUT ROWID: {reason}"),
+                )
+                .with_object(&obj),
+            );
+            continue;
+        }
+
+        unbounded.extend(
+            table
+                .columns
+                .iter()
+                .filter(|c| column_size(c).is_none())
+                .map(|c| format!("{obj}.{}", c.name.normalized)),
+        );
+        let size: usize = table.columns.iter().filter_map(column_size).sum();
+        if size > ROW_SIZE_LIMIT {
+            warnings.push(
+                Warning::new(
+                    warning::WITHOUT_ROWID_ROW_SIZE,
+                    Severity::Info,
+                    format!(
+                        "WITHOUT ROWID rows may reach {size} bytes, above the recommended {ROW_SIZE_LIMIT}"
+                    ),
+                )
+                .with_object(&obj),
+            );
+        }
+
+        table.without_rowid = true;
+    }
+
+    if !unbounded.is_empty() {
+        warnings.push(Warning::new(
+            warning::WITHOUT_ROWID_ROW_SIZE,
+            Severity::Info,
+            format!(
+                "WITHOUT ROWID columns without a length bound ({}); rows with large values are slower than in a rowid table",
+                unbounded.join(", ")
+            ),
+        ));
+    }
+}
+
+/// Names of the primary key columns, from a column or table constraint.
+fn primary_key(table: &Table) -> Vec<&str> {
+    for constraint in &table.constraints {
+        if let TableConstraint::PrimaryKey { columns, .. } = constraint {
+            return columns.iter().map(|c| c.normalized.as_str()).collect();
+        }
+    }
+    table
+        .columns
+        .iter()
+        .filter(|c| c.is_primary_key)
+        .map(|c| c.name.normalized.as_str())
+        .collect()
+}
+
+/// A composite key, or a single key column that is not an INTEGER rowid alias.
+fn is_natural_key(table: &Table, pk: &[&str]) -> bool {
+    match pk {
+        [] => false,
+        [name] => table
+            .columns
+            .iter()
+            .find(|c| c.name.normalized == *name)
+            .is_some_and(|c| c.sqlite_type != Some(SqliteType::Integer)),
+        _ => true,
+    }
+}
+
+/// Estimated maximum stored size of a column in bytes, or `None` when unbounded.
+fn column_size(col: &Column) -> Option<usize> {
+    match (col.sqlite_type?, &col.pg_type) {
+        (SqliteType::Integer | SqliteType::Real | SqliteType::Numeric, _) => Some(8),
+        (SqliteType::Blob, PgType::Uuid) => Some(16),
+        (SqliteType::Text, PgType::Uuid) => Some(36),
+        (
+            SqliteType::Text,
+            PgType::Varchar { length: Some(n) } | PgType::Char { length: Some(n) },
+        ) => Some(*n as usize),
+        (SqliteType::Text, PgType::Char { length: None }) => Some(1),
+        (SqliteType::Text, PgType::Date) => Some(10),
+        (SqliteType::Text, PgType::Time { .. }) => Some(21),
+        (SqliteType::Text, PgType::Timestamp { .. }) => Some(32),
+        (SqliteType::Text, PgType::Inet | PgType::Cidr) => Some(43),
+        (SqliteType::Text, PgType::MacAddr) => Some(17),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Ident, QualifiedName};
+
+    fn column(name: &str, pg_type: PgType, sqlite_type: SqliteType) -> Column {
+        Column {
+            name: Ident::new(name),
+            pg_type,
+            sqlite_type: Some(sqlite_type),
+            not_null: true,
+            default: None,
+            is_primary_key: false,
+            is_unique: false,
+            autoincrement: false,
+            references: None,
+            check: None,
+            collation: None,
+        }
+    }
+
+    fn pk(mut col: Column) -> Column {
+        col.is_primary_key = true;
+        col
+    }
+
+    fn table(name: &str, columns: Vec<Column>, constraints: Vec<TableConstraint>) -> Table {
+        Table {
+            name: QualifiedName::new(Ident::new(name)),
+            columns,
+            constraints,
+            without_rowid: false,
+            note: None,
+        }
+    }
+
+    fn sample() -> SchemaModel {
+        let int = || (PgType::Integer, SqliteType::Integer);
+        let code = || (PgType::Char { length: Some(3) }, SqliteType::Text);
+        let col = |name: &str, (pg, sqlite): (PgType, SqliteType)| column(name, pg, sqlite);
+        SchemaModel {
+            tables: vec![
+                table("users", vec![pk(col("id", int()))], vec![]),
+                table(
+                    "user_roles",
+                    vec![col("user_id", int()), col("role_id", int())],
+                    vec![TableConstraint::PrimaryKey {
+                        name: None,
+                        columns: vec![Ident::new("user_id"), Ident::new("role_id")],
+                    }],
+                ),
+                table(
+                    "currencies",
+                    vec![
+                        pk(col("code", code())),
+                        col("name", (PgType::Text, SqliteType::Text)),
+                    ],
+                    vec![],
+                ),
+                table("events", vec![col("payload", int())], vec![]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn marked(model: &SchemaModel) -> Vec<bool> {
+        model.tables.iter().map(|t| t.without_rowid).collect()
+    }
+
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!("AUTO".parse(), Ok(WithoutRowidMode::Auto));
+        assert!("some".parse::<WithoutRowidMode>().is_err());
+    }
+
+    #[test]
+    fn test_auto_mode_picks_natural_keys() {
+        let mut m = sample();
+        let mut w = Vec::new();
+        apply_without_rowid(&mut m, WithoutRowidMode::Auto, &mut w);
+        assert_eq!(marked(&m), [false, true, true, false]);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].code, warning::WITHOUT_ROWID_ROW_SIZE);
+        assert_eq!(w[0].object, None);
+        assert!(w[0].message.contains("(currencies.name)"));
+
+        let mut m = sample();
+        apply_without_rowid(&mut m, WithoutRowidMode::Off, &mut Vec::new());
+        assert_eq!(marked(&m), [false; 4]);
+    }
+
+    #[test]
+    fn test_all_mode_validates_restrictions() {
+        let mut m = sample();
+        let mut w = Vec::new();
+        apply_without_rowid(&mut m.clone(), WithoutRowidMode::All, &mut w);
+        assert!(
+            w.iter().all(|w| w.code != warning::WITHOUT_ROWID_SKIPPED
+                || w.object.as_deref() == Some("events"))
+        );
+
+        m.tables[0].columns[0].autoincrement = true;
+        let mut w = Vec::new();
+        apply_without_rowid(&mut m.clone(), WithoutRowidMode::All, &mut w);
+        assert!(w.iter().any(
+            |w| w.code == warning::WITHOUT_ROWID_SKIPPED && w.message.contains("AUTOINCREMENT")
+        ));
+
+        m.tables[0].columns[0].autoincrement = false;
+        let mut w = vec![
+            Warning::new(warning::SERIAL_TO_ROWID, Severity::Lossy, "").with_object("users.id"),
+        ];
+        apply_without_rowid(&mut m, WithoutRowidMode::All, &mut w);
+        assert_eq!(marked(&m), [false, true, true, false]);
+        let skipped: Vec<&str> = w
+            .iter()
+            .filter(|w| w.code == warning::WITHOUT_ROWID_SKIPPED)
+            .filter_map(|w| w.object.as_deref())
+            .collect();
+        assert_eq!(skipped, ["users", "events"]);
+    }
+
+    #[test]
+    fn test_row_size_estimate() {
+        let mut m = SchemaModel {
+            tables: vec![table(
+                "docs",
+                vec![
+                    pk(column(
+                        "slug",
+                        PgType::Varchar { length: Some(150) },
+                        SqliteType::Text,
+                    )),
+                    column("id", PgType::Uuid, SqliteType::Text),
+                    column("version", PgType::Integer, SqliteType::Integer),
+                ],
+                vec![],
+            )],
+            ..Default::default()
+        };
+        let mut w = Vec::new();
+        apply_without_rowid(&mut m.clone(), WithoutRowidMode::Auto, &mut w);
+        assert!(w.is_empty());
+
+        m.tables[0].columns[0].pg_type = PgType::Varchar { length: Some(200) };
+        apply_without_rowid(&mut m.clone(), WithoutRowidMode::Auto, &mut w);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].code, warning::WITHOUT_ROWID_ROW_SIZE);
+        assert_eq!(w[0].object.as_deref(), Some("docs"));
+        assert!(w[0].message.contains("244 bytes"));
+
+        // Unbounded columns only count towards the aggregated note
+        m.tables[0].columns[0].pg_type = PgType::Varchar { length: Some(150) };
+        m.tables[0]
+            .columns
+            .push(column("body", PgType::Text, SqliteType::Text));
+        let mut w = Vec::new();
+        apply_without_rowid(&mut m, WithoutRowidMode::Auto, &mut w);
+        assert!(m.tables[0].without_rowid);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].object, None);
+        assert!(w[0].message.contains("(docs.body)"));
+    }
+}
@@ -105,3 +105,21 @@ fn test_golden_alter_identity() {
         &ConvertOptions::default(),
     );
 }
+
+#[test]
+fn test_golden_function_defaults() {
+    run_golden_test(
+        "tests/fixtures/function_defaults.sql",
+        "tests/golden/function_defaults.out.sql",
+        &ConvertOptions::default(),
+    );
+}
+
+#[test]
+fn test_golden_extension_types() {
+    run_golden_test(
+        "tests/fixtures/extension_types.sql",
+        "tests/golden/extension_types.out.sql",
+        &ConvertOptions::default(),
+    );
+}
@@ -0,0 +1,9 @@
+-- Extension types with generated CHECK constraints
+CREATE EXTENSION IF NOT EXISTS ltree;
+CREATE EXTENSION IF NOT EXISTS hstore;
+CREATE TABLE category (
+    id bigint NOT NULL,
+    path ltree NOT NULL,
+    attrs hstore,
+    CONSTRAINT category_pkey PRIMARY KEY (id)
+);
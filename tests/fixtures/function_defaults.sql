@@ -0,0 +1,15 @@
+-- Function calls in defaults and CHECK constraints
+CREATE TABLE event (
+    id bigint NOT NULL,
+    code text NOT NULL,
+    tags text,
+    happened_on date DEFAULT CURRENT_DATE,
+    logged_at timestamp without time zone DEFAULT LOCALTIMESTAMP,
+    received_at timestamp without time zone DEFAULT clock_timestamp(),
+    bucket timestamp without time zone DEFAULT date_trunc('hour', now()),
+    day_key text DEFAULT to_char(now(), 'YYYYMMDD'),
+    priority integer DEFAULT greatest(1, 0),
+    CONSTRAINT event_code_check CHECK ((char_length(btrim(code)) BETWEEN 1 AND 16)),
+    CONSTRAINT event_prefix_check CHECK ((left(code, 2) <> 'x-')),
+    CONSTRAINT event_tags_check CHECK ((split_part(tags, ',', 1) <> ''))
+);
@@ -6,7 +6,7 @@ CREATE TABLE address (
   account TEXT NOT NULL,
   wallet_address TEXT NOT NULL,
   is_allocated INTEGER NOT NULL DEFAULT 0,
-  updated_at TEXT,
+  updated_at TEXT DEFAULT (CURRENT_TIMESTAMP),
   CHECK ((account IN ('client', 'deposit', 'payment', 'stored'))),
   CHECK ((coin IN ('btc', 'bch', 'eth', 'xrp', 'hyt')))
 );
@@ -0,0 +1,7 @@
+-- Code generated by `pg2sqlite`. DO NOT EDIT.
+
+CREATE TABLE category (
+  id INTEGER PRIMARY KEY,
+  path TEXT NOT NULL CHECK (NOT (path GLOB '*[^A-Za-z0-9_.-]*' OR path GLOB '.*' OR path GLOB '*.' OR path GLOB '*..*')),
+  attrs TEXT CHECK ((json_valid(attrs)) AND json_type(attrs) = 'object')
+);
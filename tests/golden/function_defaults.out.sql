@@ -0,0 +1,16 @@
+-- Code generated by `pg2sqlite`. DO NOT EDIT.
+
+CREATE TABLE event (
+  id INTEGER NOT NULL,
+  code TEXT NOT NULL,
+  tags TEXT,
+  happened_on TEXT DEFAULT (CURRENT_DATE),
+  logged_at TEXT DEFAULT (datetime('now', 'localtime')),
+  received_at TEXT DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+  bucket TEXT DEFAULT (strftime('%Y-%m-%d %H:00:00', CURRENT_TIMESTAMP)),
+  day_key TEXT DEFAULT (strftime('%Y%m%d', CURRENT_TIMESTAMP)),
+  priority INTEGER DEFAULT (max(1, 0)),
+  CHECK ((length(trim(code)) BETWEEN 1 AND 16)),
+  CHECK ((substr(code, 1, 2) <> 'x-')),
+  CHECK ((substr(tags || ',', 1, instr(tags || ',', ',') - 1) <> ''))
+);